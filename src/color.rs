@@ -37,21 +37,26 @@ use std::marker::Sized;
 use std::num::ParseIntError;
 use std::result::Result::Err;
 use std::str::FromStr;
-use std::string::ToString;
 
 use super::coord::Coord;
+use colorpoint::ColorPoint;
 use colors::cielabcolor::CIELABColor;
 use colors::cielchcolor::CIELCHColor;
+use colors::hslcolor::HSLColor;
+use colors::linearrgbcolor::{linear_to_srgb, srgb_to_linear, LinearRGBColor};
+use colors::lmscolor::LMSColor;
 use consts;
 use consts::BRADFORD_TRANSFORM as BRADFORD;
 use consts::BRADFORD_TRANSFORM_LU as BRADFORD_LU;
 use consts::STANDARD_RGB_TRANSFORM as SRGB;
 use consts::STANDARD_RGB_TRANSFORM_LU as SRGB_LU;
-use csscolor::{parse_rgb_str, CSSParseError};
+use csscolor::{parse_hsl_hsv_tuple_strict, parse_rgb_str, parse_rgb_str_strict, CSSParseError};
 use illuminants::Illuminant;
 
 use nalgebra::base::Vector;
+use nalgebra::linalg::LU;
 use nalgebra::vector;
+use nalgebra::{Const, Matrix3};
 
 #[cfg(feature = "terminal")]
 use termion::color::{Bg, Fg, Reset, Rgb};
@@ -59,7 +64,7 @@ use termion::color::{Bg, Fg, Reset, Rgb};
 /// A point in the CIE 1931 XYZ color space. Although any point in XYZ coordinate space is technically
 /// valid, in this library XYZ colors are treated as normalized so that Y=1 is the white point of
 /// whatever illuminant is being worked with.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub struct XYZColor {
     /// The X axis of the CIE 1931 XYZ space, roughly representing the long-wavelength receptors in
     /// the human eye: the red receptors. Usually between 0 and 1, but can range more than that.
@@ -82,6 +87,66 @@ pub struct XYZColor {
     pub illuminant: Illuminant,
 }
 
+/// A method of chromatic adaptation, for use with [`XYZColor::adapt`]. Each of these is a von
+/// Kries-style transform: the color is converted to a space approximating the responses of the
+/// eye's cones, each response is scaled by the ratio between the two white points, and the result
+/// is converted back. They differ only in the matrix used for the first step.
+///
+/// [`XYZColor::adapt`]: struct.XYZColor.html#method.adapt
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AdaptationMethod {
+    /// The Bradford transform, the one Scarlet uses everywhere else and the most widely used in
+    /// color management, including in ICC profiles.
+    Bradford,
+    /// The original von Kries transform, using the Hunt-Pointer-Estévez cone fundamentals
+    /// normalized to D65.
+    VonKries,
+    /// The transform from the CIECAM02 color appearance model, a refinement of Bradford.
+    CAT02,
+    /// Scaling each XYZ coordinate directly, without any cone space. This is the simplest and
+    /// least accurate method, and is mostly useful for comparison with other software.
+    XYZScaling,
+}
+
+impl AdaptationMethod {
+    /// Returns the matrix from XYZ to the cone response space used by this method, along with its
+    /// LU decomposition for inverting it.
+    fn matrices(self) -> (&'static Matrix3<f64>, &'static LU<f64, Const<3>, Const<3>>) {
+        match self {
+            AdaptationMethod::Bradford => (&*BRADFORD, &*BRADFORD_LU),
+            AdaptationMethod::VonKries => (
+                &*consts::VON_KRIES_TRANSFORM,
+                &*consts::VON_KRIES_TRANSFORM_LU,
+            ),
+            AdaptationMethod::CAT02 => (&*consts::CAT02_TRANSFORM, &*consts::CAT02_TRANSFORM_LU),
+            AdaptationMethod::XYZScaling => (
+                &*consts::XYZ_SCALING_TRANSFORM,
+                &*consts::XYZ_SCALING_TRANSFORM_LU,
+            ),
+        }
+    }
+}
+
+/// Computes the single matrix that performs Bradford chromatic adaptation from one illuminant to
+/// another, exactly as [`XYZColor::color_adapt`] does for one color. This is useful when adapting
+/// many colors at once.
+///
+/// [`XYZColor::color_adapt`]: struct.XYZColor.html#method.color_adapt
+fn adaptation_matrix(from: Illuminant, to: Illuminant) -> Matrix3<f64> {
+    if from == to {
+        return Matrix3::identity();
+    }
+    let rgb_w = *BRADFORD * Vector::from(from.white_point().to_vec());
+    let rgb_wr = *BRADFORD * Vector::from(to.white_point().to_vec());
+    let scale = Matrix3::from_diagonal(&vector![
+        rgb_wr[0] / rgb_w[0],
+        rgb_wr[1] / rgb_w[1],
+        rgb_wr[2] / rgb_w[2]
+    ]);
+    let inverse = BRADFORD_LU.try_inverse().expect("Matrix is invertible.");
+    inverse * scale * *BRADFORD
+}
+
 impl XYZColor {
     /// Converts from one illuminant to a different one, such that a human receiving both sets of
     /// sensory stimuli in the corresponding lighting conditions would perceive an object with that
@@ -143,45 +208,67 @@ impl XYZColor {
     /// println!("Gold: {}, White: {}", gold_rgb.to_string(), white_rgb.to_string());
     /// ```
     pub fn color_adapt(&self, other_illuminant: Illuminant) -> XYZColor {
+        self.adapt(other_illuminant, AdaptationMethod::Bradford)
+    }
+    /// Chromatically adapts this color to a different illuminant, like [`XYZColor::color_adapt`],
+    /// but using the given method instead of always using the Bradford transform. The rest of
+    /// Scarlet, including [`Color::convert`], always uses Bradford: this is for when a specific
+    /// transform is needed, for example to match another program's output.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::{AdaptationMethod, XYZColor};
+    /// let color = RGBColor::from_hex_code("#3a7fd0").unwrap().to_xyz(Illuminant::D65);
+    /// let bradford = color.adapt(Illuminant::D50, AdaptationMethod::Bradford);
+    /// let cat02 = color.adapt(Illuminant::D50, AdaptationMethod::CAT02);
+    /// // the methods agree closely, but not exactly
+    /// assert!(bradford.approx_equal(&color.color_adapt(Illuminant::D50)));
+    /// assert!(!bradford.approx_equal(&cat02));
+    /// assert!(bradford.distance(&cat02) < 1.);
+    /// ```
+    ///
+    /// [`XYZColor::color_adapt`]: #method.color_adapt
+    /// [`Color::convert`]: trait.Color.html#method.convert
+    pub fn adapt(&self, to: Illuminant, method: AdaptationMethod) -> XYZColor {
         // no need to transform if same illuminant
-        if other_illuminant == self.illuminant {
-            *self
-        } else {
-            // convert to Bradford RGB space
-            // &* needed because lazy_static uses a different type which implements Deref
-            let rgb = *BRADFORD * vector![self.x, self.y, self.z];
-
-            // get the RGB values for the white point of the illuminant we are currently using and
-            // the one we want: wr here stands for "white reference", i.e., the one we're converting
-            // to
-            let rgb_w = *BRADFORD * Vector::from(self.illuminant.white_point().to_vec());
-            let rgb_wr = *BRADFORD * Vector::from(other_illuminant.white_point().to_vec());
-
-            // perform the transform
-            // this usually includes a parameter indicating how much you want to adapt, but it's
-            // assumed that we want total adaptation: D = 1. Maybe this could change someday?
-
-            // because each white point has already been normalized to Y = 1, we don't need ap
-            // factor for it, which simplifies calculation even more than setting D = 1 and makes it
-            // just a linear transform
-            // scale by the ratio of luminance: it should always be 1, but with rounding error it
-            // isn't
-            let r_c = rgb[0] * rgb_wr[0] / rgb_w[0];
-            let g_c = rgb[1] * rgb_wr[1] / rgb_w[1];
-            // there's a slight nonlinearity here that I will omit
-            let b_c = rgb[2] * rgb_wr[2] / rgb_w[2];
-            // convert back to XYZ using inverse of previous matrix
-
-            // using LU decomposition for accuracy
-            let xyz_c = BRADFORD_LU
-                .solve(&vector![r_c, g_c, b_c])
-                .expect("Matrix is invertible.");
-            XYZColor {
-                x: xyz_c[0],
-                y: xyz_c[1],
-                z: xyz_c[2],
-                illuminant: other_illuminant,
-            }
+        if to == self.illuminant {
+            return *self;
+        }
+        let (matrix, matrix_lu) = method.matrices();
+        // convert to the cone response space
+        let rgb = matrix * vector![self.x, self.y, self.z];
+
+        // get the cone responses for the white point of the illuminant we are currently using and
+        // the one we want: wr here stands for "white reference", i.e., the one we're converting
+        // to
+        let rgb_w = matrix * Vector::from(self.illuminant.white_point().to_vec());
+        let rgb_wr = matrix * Vector::from(to.white_point().to_vec());
+
+        // perform the transform
+        // this usually includes a parameter indicating how much you want to adapt, but it's
+        // assumed that we want total adaptation: D = 1. Maybe this could change someday?
+
+        // because each white point has already been normalized to Y = 1, we don't need ap
+        // factor for it, which simplifies calculation even more than setting D = 1 and makes it
+        // just a linear transform
+        // scale by the ratio of luminance: it should always be 1, but with rounding error it
+        // isn't
+        let r_c = rgb[0] * rgb_wr[0] / rgb_w[0];
+        let g_c = rgb[1] * rgb_wr[1] / rgb_w[1];
+        // there's a slight nonlinearity here that I will omit
+        let b_c = rgb[2] * rgb_wr[2] / rgb_w[2];
+        // convert back to XYZ using inverse of previous matrix
+
+        // using LU decomposition for accuracy
+        let xyz_c = matrix_lu
+            .solve(&vector![r_c, g_c, b_c])
+            .expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz_c[0],
+            y: xyz_c[1],
+            z: xyz_c[2],
+            illuminant: to,
         }
     }
     /// Returns `true` if the given other XYZ color's coordinates are all within acceptable error of
@@ -204,9 +291,28 @@ impl XYZColor {
     ///
     /// [`Color::visually_indistinguishable`]: ../color/trait.Color.html#method.visually_indistinguishable
     pub fn approx_equal(&self, other: &XYZColor) -> bool {
-        (self.x - other.x).abs() <= 1e-15
-            && (self.y - other.y).abs() <= 1e-15
-            && (self.z - other.z).abs() <= 1e-15
+        self.approx_equal_eps(other, 1e-15)
+    }
+    /// Like [`XYZColor::approx_equal`], but with a given tolerance instead of the default of 1e-15:
+    /// returns `true` if all of the coordinates are within `eps` of each other. Conversions through
+    /// several color spaces, or through ones with fractional exponents, can accumulate more error
+    /// than the default allows. Like `approx_equal`, this doesn't take the illuminants into account.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::color::XYZColor;
+    /// # use scarlet::illuminants::Illuminant;
+    /// let xyz1 = XYZColor{x: 0.3, y: 0.4, z: 0.5, illuminant: Illuminant::D65};
+    /// let xyz2 = XYZColor{x: 0.3 + 1e-12, y: 0.4, z: 0.5, illuminant: Illuminant::D65};
+    /// assert!(!xyz1.approx_equal(&xyz2));
+    /// assert!(xyz1.approx_equal_eps(&xyz2, 1e-10));
+    /// ```
+    ///
+    /// [`XYZColor::approx_equal`]: #method.approx_equal
+    pub fn approx_equal_eps(&self, other: &XYZColor, eps: f64) -> bool {
+        (self.x - other.x).abs() <= eps
+            && (self.y - other.y).abs() <= eps
+            && (self.z - other.z).abs() <= eps
     }
 
     /// Returns `true` if the given other XYZ color would look identically in a different color
@@ -223,6 +329,33 @@ impl XYZColor {
         let other_c = other.color_adapt(self.illuminant);
         self.approx_equal(&other_c)
     }
+    /// Estimates the correlated color temperature of this color in kelvin: the temperature of the
+    /// blackbody radiator whose color is closest to it, as returned by
+    /// [`RGBColor::from_temperature`]. This is the usual way of describing the color of a light
+    /// source or a white point. It uses McCamy's cubic approximation on the xy chromaticity
+    /// coordinates, which is accurate to within a few kelvin between about 2000 K and 12500 K.
+    /// The coordinates are used as they are, without adapting to any illuminant, so the white point
+    /// of D65 gives about 6500 K and that of D50 about 5000 K. Color temperature is only meaningful
+    /// for colors close to the Planckian locus, the curve of blackbody colors: for anything else,
+    /// like a saturated green, this still returns a number, but it doesn't mean much. Black has no
+    /// chromaticity, so it gives NaN.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::color::XYZColor;
+    /// # use scarlet::illuminants::Illuminant;
+    /// let cct = XYZColor::white_point(Illuminant::D50).correlated_color_temperature();
+    /// assert!((cct - 5000.).abs() <= 10.);
+    /// ```
+    ///
+    /// [`RGBColor::from_temperature`]: struct.RGBColor.html#method.from_temperature
+    pub fn correlated_color_temperature(&self) -> f64 {
+        let sum = self.x + self.y + self.z;
+        let (x, y) = (self.x / sum, self.y / sum);
+        // McCamy (1992), using the epicenter (0.3320, 0.1858)
+        let n = (x - 0.3320) / (0.1858 - y);
+        449.0 * n.powi(3) + 3525.0 * n.powi(2) + 6823.3 * n + 5520.33
+    }
     /// Gets the XYZColor corresponding to pure white in the given light environment.
     /// # Example
     ///
@@ -244,6 +377,173 @@ impl XYZColor {
     }
 }
 
+/// Finds the largest CIELCH chroma, up to `max_c`, that a color with the given lightness and hue
+/// can have while staying in the sRGB gamut, by bisection. Returns 0 if even the gray of that
+/// lightness is outside of the gamut.
+pub(crate) fn srgb_max_chroma(l: f64, h: f64, max_c: f64) -> f64 {
+    let in_gamut = |c: f64| CIELCHColor { l, c, h }.convert::<RGBColor>().is_in_gamut();
+    if in_gamut(max_c) {
+        return max_c;
+    }
+    let (mut lo, mut hi) = (0.0, max_c);
+    for _i in 0..30 {
+        let mid = (lo + hi) / 2.0;
+        if in_gamut(mid) {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    lo
+}
+
+/// The application that the CIE94 color difference formula is being used for, which determines the
+/// weighting given to differences in lightness and the chroma-dependent scaling of chroma and hue
+/// differences. See [`Color::cie94`] for more.
+///
+/// [`Color::cie94`]: trait.Color.html#method.cie94
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CIE94App {
+    /// Parameters for graphic arts: kL = 1, K1 = 0.045, K2 = 0.015.
+    GraphicArts,
+    /// Parameters for textiles, which tolerate lightness differences more readily: kL = 2,
+    /// K1 = 0.048, K2 = 0.014.
+    Textiles,
+}
+
+impl CIE94App {
+    /// Returns the parameters kL, K1, and K2 used in the CIE94 formula for this application.
+    fn params(self) -> (f64, f64, f64) {
+        match self {
+            CIE94App::GraphicArts => (1.0, 0.045, 0.015),
+            CIE94App::Textiles => (2.0, 0.048, 0.014),
+        }
+    }
+}
+
+/// A strategy for bringing colors outside the sRGB gamut into it, for use with
+/// [`RGBColor::clip_to_gamut`].
+///
+/// [`RGBColor::clip_to_gamut`]: struct.RGBColor.html#method.clip_to_gamut
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum GamutStrategy {
+    /// Clamps each of the red, green, and blue components to 0-1 separately, which is what
+    /// quantizing to integers or hex codes does. This is fast, but can shift the hue and lightness
+    /// of very saturated colors considerably.
+    ClampRGB,
+    /// Keeps the CIELCH lightness and hue, and reduces the chroma as little as possible to fit in
+    /// the gamut. This gives much more faithful results for saturated colors. Colors that are
+    /// lighter than white or darker than black can't be fixed this way, and are clamped as well.
+    ReduceChroma,
+}
+
+/// A blend mode for layering one color on top of another, for use with [`RGBColor::blend`]. These
+/// are the separable blend modes from image editors like Photoshop and GIMP, with the definitions
+/// from the W3C's [Compositing and Blending](https://www.w3.org/TR/compositing-1/) specification
+/// that CSS and SVG use. Each is given in terms of one channel of the backdrop, the color below,
+/// and the source, the color on top.
+///
+/// [`RGBColor::blend`]: struct.RGBColor.html#method.blend
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Multiplies the two, which always darkens, like two transparencies stacked on a light table.
+    /// Black gives black, and white leaves the other color unchanged.
+    Multiply,
+    /// The inverse of multiply, which always lightens, like two images projected on the same
+    /// screen. White gives white, and black leaves the other color unchanged.
+    Screen,
+    /// Multiplies dark parts of the backdrop and screens light parts, boosting contrast while
+    /// keeping the backdrop's highlights and shadows. This is hard light with the two swapped.
+    Overlay,
+    /// Takes the darker of the two in each channel.
+    Darken,
+    /// Takes the lighter of the two in each channel.
+    Lighten,
+    /// Brightens the backdrop to reflect the source, by dividing it by the source's inverse.
+    ColorDodge,
+    /// Darkens the backdrop to reflect the source, the inverse of color dodge.
+    ColorBurn,
+    /// Multiplies where the source is dark and screens where it is light, like shining a harsh
+    /// spotlight of the source onto the backdrop.
+    HardLight,
+    /// A gentler version of hard light, like shining a diffuse spotlight of the source onto the
+    /// backdrop. A source of 50% gray leaves the backdrop unchanged.
+    SoftLight,
+    /// Subtracts the darker of the two from the lighter. Identical colors give black, and white
+    /// inverts the other color.
+    Difference,
+}
+
+impl BlendMode {
+    /// Blends one channel of the source `cs` onto one channel of the backdrop `cb`, both from 0 to
+    /// 1.
+    fn blend_channel(self, cb: f64, cs: f64) -> f64 {
+        let multiply = |cb: f64, cs: f64| cb * cs;
+        let screen = |cb: f64, cs: f64| cb + cs - cb * cs;
+        let hard_light = |cb: f64, cs: f64| {
+            if cs <= 0.5 {
+                multiply(cb, 2.0 * cs)
+            } else {
+                screen(cb, 2.0 * cs - 1.0)
+            }
+        };
+        match self {
+            BlendMode::Multiply => multiply(cb, cs),
+            BlendMode::Screen => screen(cb, cs),
+            BlendMode::Overlay => hard_light(cs, cb),
+            BlendMode::Darken => cb.min(cs),
+            BlendMode::Lighten => cb.max(cs),
+            BlendMode::ColorDodge => {
+                if cb == 0.0 {
+                    0.0
+                } else if cs == 1.0 {
+                    1.0
+                } else {
+                    (cb / (1.0 - cs)).min(1.0)
+                }
+            }
+            BlendMode::ColorBurn => {
+                if cb == 1.0 {
+                    1.0
+                } else if cs == 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - cb) / cs).min(1.0)
+                }
+            }
+            BlendMode::HardLight => hard_light(cb, cs),
+            BlendMode::SoftLight => {
+                if cs <= 0.5 {
+                    cb - (1.0 - 2.0 * cs) * cb * (1.0 - cb)
+                } else {
+                    let d = if cb <= 0.25 {
+                        ((16.0 * cb - 12.0) * cb + 4.0) * cb
+                    } else {
+                        cb.sqrt()
+                    };
+                    cb + (2.0 * cs - 1.0) * (d - cb)
+                }
+            }
+            BlendMode::Difference => (cb - cs).abs(),
+        }
+    }
+}
+
+/// A kind of color vision deficiency, named after the type of cone cell that is missing. See
+/// [`Color::simulate_cvd`] for more.
+///
+/// [`Color::simulate_cvd`]: trait.Color.html#method.simulate_cvd
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CvdKind {
+    /// Missing long-wavelength (L) cones: reds and greens are confused, and reds appear dark.
+    Protanopia,
+    /// Missing medium-wavelength (M) cones: reds and greens are confused. This is the most common
+    /// kind.
+    Deuteranopia,
+    /// Missing short-wavelength (S) cones: blues and greens, and yellows and violets, are confused.
+    Tritanopia,
+}
+
 /// A trait that represents any color representation that can be converted to and from the CIE 1931 XYZ
 /// color space. See module-level documentation for more information and examples.
 pub trait Color: Sized {
@@ -318,6 +618,41 @@ pub trait Color: Sized {
         // it will produce the least error
         T::from_xyz(self.to_xyz(Illuminant::D50))
     }
+    /// Converts an sRGB color into this type. This does the same thing as
+    /// `rgb.convert::<Self>()`, and that's what it does by default, but a type can override it
+    /// with a shortcut that skips the trip through XYZ. `RGBColor` does, so converting it to itself
+    /// costs nothing. Code that works in sRGB internally, like the colormaps, uses this to produce
+    /// its output.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// let rgb = RGBColor::from_hex_code("#4070a0").unwrap();
+    /// let lab1 = CIELABColor::from_rgb(rgb);
+    /// let lab2: CIELABColor = rgb.convert();
+    /// assert!(lab1.distance(&lab2) <= 1e-10);
+    /// ```
+    fn from_rgb(rgb: RGBColor) -> Self {
+        rgb.convert()
+    }
+    /// Returns which of this type's coordinates, if any, is a hue angle in degrees, as an index
+    /// into its [`Coord`]: 0 for `x`, 1 for `y`, and 2 for `z`. Hues wrap around at 360, so
+    /// averaging them like other coordinates goes wrong across the seam, and
+    /// [`ColorPoint::average`] and [`ColorPoint::weighted_average`] use this to average hues with
+    /// [`circular_hue_mean`] instead. The default is `None`: cylindrical color types, like
+    /// [`CIELCHColor`] and [`HSVColor`], should override it.
+    ///
+    /// [`Coord`]: ../coord/struct.Coord.html
+    /// [`ColorPoint::average`]: ../colorpoint/trait.ColorPoint.html#method.average
+    /// [`ColorPoint::weighted_average`]: ../colorpoint/trait.ColorPoint.html#method.weighted_average
+    /// [`circular_hue_mean`]: ../colorpoint/fn.circular_hue_mean.html
+    /// [`CIELCHColor`]: ../colors/cielchcolor/struct.CIELCHColor.html
+    /// [`HSVColor`]: ../colors/hsvcolor/struct.HSVColor.html
+    fn hue_axis() -> Option<usize> {
+        None
+    }
     /// "Colors" a given piece of text with terminal escape codes to allow it to be printed out in the
     /// given foreground color. Will cause problems with terminals that do not support truecolor.
     /// Requires the `terminal` feature.
@@ -732,6 +1067,199 @@ pub trait Color: Sized {
     /// assert!(green1.distance(&green2) / blue1.distance(&blue2) < 0.992);
     /// ```
     fn distance<T: Color>(&self, other: &T) -> f64 {
+        self.ciede2000(other)
+    }
+    /// Generates a (background, foreground) pair of sRGB colors from this color, suitable for
+    /// something like a badge or a tag. The background is a light tint of this color, keeping its
+    /// hue but with high lightness and limited chroma. The foreground is a dark shade of the same
+    /// hue if that meets the WCAG AA contrast ratio of 4.5:1 against the background, and black if
+    /// not. If the tint or shade falls outside of the sRGB gamut, its chroma is reduced until it
+    /// fits, so the hue is always preserved.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let seed = RGBColor::from_hex_code("#2a7ab0").unwrap();
+    /// let (bg, fg) = seed.contrasting_pair();
+    /// assert!(bg.lightness() > seed.lightness());
+    /// assert!(fg.lightness() < seed.lightness());
+    /// ```
+    fn contrasting_pair(&self) -> (RGBColor, RGBColor) {
+        let lch: CIELCHColor = self.convert();
+        // the most chromatic color up to max_c with the given lightness and this hue
+        let tone = |l: f64, max_c: f64| {
+            let c = srgb_max_chroma(l, lch.h, max_c);
+            CIELCHColor { l, c, h: lch.h }.convert()
+        };
+        let background: RGBColor = tone(92.0, lch.c.min(20.0));
+        let shade = tone(25.0, lch.c.min(40.0));
+        let foreground = if background.contrast_ratio(&shade) >= 4.5 {
+            shade
+        } else {
+            RGBColor {
+                r: 0.0,
+                g: 0.0,
+                b: 0.0,
+            }
+        };
+        (background, foreground)
+    }
+    /// Finds a color of the given CIELCH hue that is as readable as possible on both of two
+    /// backgrounds, for text or icons that might sit on either a light or a dark surface. The
+    /// lightness is chosen to maximize the smaller of the two WCAG contrast ratios, and the chroma
+    /// is as high as the sRGB gamut allows at that lightness, up to a moderate 40. For the
+    /// extremes of white and black, the best achievable contrast is about 4.58 against both, just
+    /// enough for WCAG AA: the closer the two backgrounds are in luminance, the lower it gets, and
+    /// if they're too close no color will meet AA. Check with [`RGBColor::meets_aa`] if needed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let teal = RGBColor::readable_on_both(white, black, 200.);
+    /// assert!(teal.meets_aa(&white, false));
+    /// assert!(teal.meets_aa(&black, false));
+    /// ```
+    ///
+    /// [`RGBColor::meets_aa`]: struct.RGBColor.html#method.meets_aa
+    fn readable_on_both(light_bg: RGBColor, dark_bg: RGBColor, hue: f64) -> RGBColor {
+        // the contrast against one background rises with lightness as the other falls, so a fine
+        // scan finds the best balance
+        let mut best = RGBColor {
+            r: 0.0,
+            g: 0.0,
+            b: 0.0,
+        };
+        let mut best_ratio = 0.0;
+        for i in 0..=1000 {
+            let l = i as f64 / 10.0;
+            let c = srgb_max_chroma(l, hue, 40.0);
+            let color: RGBColor = CIELCHColor { l, c, h: hue }.convert();
+            let ratio = color
+                .contrast_ratio(&light_bg)
+                .min(color.contrast_ratio(&dark_bg));
+            if ratio > best_ratio {
+                best = color;
+                best_ratio = ratio;
+            }
+        }
+        best
+    }
+    /// Finds the closest color that is lighter (if `direction_l` is positive) or darker (if it is
+    /// negative) than this one by enough to be told apart, keeping the CIELAB a and b the same.
+    /// `jnd` is the just-noticeable difference to use, as a CIEDE2000 distance: 1 is the usual
+    /// threshold, and larger values give more obviously separated colors. Calling this
+    /// repeatedly builds a scale of swatches that are evenly spaced perceptually. If there isn't
+    /// enough room left before reaching a lightness of 100 (or 0), this returns the color at that
+    /// limit instead, which will be closer than `jnd`. A `direction_l` of 0 returns the color
+    /// unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// // a scale of blues, each 5 units apart
+    /// let mut scale = vec![RGBColor::from_hex_code("#0a2a6a").unwrap()];
+    /// for _i in 0..4 {
+    ///     let next = scale[scale.len() - 1].next_distinct(1., 5.);
+    ///     scale.push(next);
+    /// }
+    /// for pair in scale.windows(2) {
+    ///     assert!((pair[0].distance(&pair[1]) - 5.).abs() <= 1e-6);
+    /// }
+    /// ```
+    fn next_distinct(self, direction_l: f64, jnd: f64) -> Self {
+        let lab: CIELABColor = self.convert();
+        let with_l = |l: f64| CIELABColor { l, ..lab };
+        let limit = if direction_l > 0.0 {
+            100.0
+        } else if direction_l < 0.0 {
+            0.0
+        } else {
+            return self;
+        };
+        if lab.distance(&with_l(limit)) <= jnd {
+            return with_l(limit).convert();
+        }
+        // the difference grows with the change in lightness, so bisect for where it reaches jnd,
+        // keeping the far end on the side that's distinct enough
+        let (mut near, mut far) = (lab.l, limit);
+        for _i in 0..50 {
+            let mid = (near + far) / 2.0;
+            if lab.distance(&with_l(mid)) >= jnd {
+                far = mid;
+            } else {
+                near = mid;
+            }
+        }
+        with_l(far).convert()
+    }
+    /// Gets the sRGB color with the given CIELCH hue, chroma, and lightness, or `None` if there is no
+    /// such color because it falls outside the sRGB gamut. Fixing the lightness and sweeping the
+    /// hue and chroma gives a constant-lightness slice of CIELAB, the building block for
+    /// visualizations of the space that show only colors a screen can actually display.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// // a moderate orange exists...
+    /// let orange = RGBColor::at_lab_lightness(50., 40., 60.).unwrap();
+    /// assert!((orange.lightness() - 60.).abs() <= 1e-8);
+    /// // ...but one this saturated can't be displayed
+    /// assert!(RGBColor::at_lab_lightness(50., 150., 60.).is_none());
+    /// ```
+    fn at_lab_lightness(hue: f64, chroma: f64, lightness: f64) -> Option<RGBColor> {
+        let lab_rgb: RGBColor = CIELCHColor {
+            l: lightness,
+            c: chroma,
+            h: hue,
+        }
+        .convert();
+        // the published D50 and D65 constants don't agree exactly, so colors on the edge of the
+        // gamut, like white, can come out a few hundred-thousandths past it: snap those back onto
+        // the edge, so that anything returned passes is_in_gamut
+        let snap = |x: f64| {
+            if (-1e-4..=1.0 + 1e-4).contains(&x) {
+                x.clamp(0., 1.)
+            } else {
+                x
+            }
+        };
+        let rgb = RGBColor {
+            r: snap(lab_rgb.r),
+            g: snap(lab_rgb.g),
+            b: snap(lab_rgb.b),
+        };
+        if rgb.is_in_gamut() {
+            Some(rgb)
+        } else {
+            None
+        }
+    }
+    /// Computes the CIEDE2000 color difference between this color and another, of any type. Both
+    /// colors are converted to CIELAB first. This is the full formula, including the lightness,
+    /// chroma, and hue weighting functions and the rotation term that corrects for the blue
+    /// region, with all three parametric weights set to 1. This is what [`distance`] uses, and in
+    /// general [`distance`] should be preferred: this method exists to make the choice of metric
+    /// explicit when comparing against other difference formulae.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// // the first pair of the Sharma, Wu, and Dalal test data
+    /// let lab1 = CIELABColor{l: 50., a: 2.6772, b: -79.7751};
+    /// let lab2 = CIELABColor{l: 50., a: 0., b: -82.7485};
+    /// assert!((lab1.ciede2000(&lab2) - 2.0425).abs() <= 1e-4);
+    /// ```
+    ///
+    /// [`distance`]: #method.distance
+    fn ciede2000<T: Color>(&self, other: &T) -> f64 {
         // implementation reference found here:
         // https://pdfs.semanticscholar.org/969b/c38ea067dd22a47a44bcb59c23807037c8d8.pdf
 
@@ -833,6 +1361,111 @@ pub trait Color: Sized {
             + r_t * (delta_c / s_c) * (delta_h / s_h))
             .sqrt()
     }
+    /// Computes the CIE94 color difference between this color and another, of any type, using the
+    /// parameters for the given application. This is a lighter-weight predecessor to CIEDE2000 that
+    /// computes differences in CIELAB lightness, chroma, and hue, weighting the latter two by the
+    /// chroma of `self`. Note that this means the formula is not symmetric: `self` is treated as
+    /// the reference color. For most uses, [`distance`] is the better choice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::CIE94App;
+    /// # use scarlet::colors::CIELABColor;
+    /// let lab1 = CIELABColor{l: 50., a: 2.6772, b: -79.7751};
+    /// let lab2 = CIELABColor{l: 50., a: 0., b: -82.7485};
+    /// assert!((lab1.cie94(&lab2, CIE94App::GraphicArts) - 1.3950).abs() <= 1e-4);
+    /// ```
+    ///
+    /// [`distance`]: #method.distance
+    fn cie94<T: Color>(&self, other: &T, application: CIE94App) -> f64 {
+        let lab1: CIELABColor = self.convert();
+        let lab2: CIELABColor = other.convert();
+        let (k_l, k_1, k_2) = application.params();
+
+        let c_1 = lab1.a.hypot(lab1.b);
+        let c_2 = lab2.a.hypot(lab2.b);
+        let delta_l = lab1.l - lab2.l;
+        let delta_c = c_1 - c_2;
+        let delta_a = lab1.a - lab2.a;
+        let delta_b = lab1.b - lab2.b;
+        // delta H is defined through its square, which can dip below 0 through rounding error
+        let delta_h_sq = (delta_a.powi(2) + delta_b.powi(2) - delta_c.powi(2)).max(0.0);
+
+        // S_L is always 1, and k_C and k_H are 1 for both applications
+        let s_c = 1.0 + k_1 * c_1;
+        let s_h = 1.0 + k_2 * c_1;
+        ((delta_l / k_l).powi(2) + (delta_c / s_c).powi(2) + delta_h_sq / s_h.powi(2)).sqrt()
+    }
+    /// Computes the CMC l:c color difference between this color and another, of any type, as
+    /// defined by the Colour Measurement Committee of the Society of Dyers and Colourists. The
+    /// lightness and chroma weights `l` and `c` set how much those differences count relative to
+    /// hue: a ratio of 2:1 is standard for acceptability and 1:1 for perceptibility, and the
+    /// [`cmc_acceptability`] and [`cmc_perceptibility`] methods provide these. Like CIE94, this is
+    /// not symmetric: `self` is treated as the reference color.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// let lab1 = CIELABColor{l: 0.9, a: 16.3, b: -2.22};
+    /// let lab2 = CIELABColor{l: 0.7, a: 14.2, b: -1.80};
+    /// assert!((lab1.cmc(&lab2, 2., 1.) - 1.443).abs() <= 1e-3);
+    /// ```
+    ///
+    /// [`cmc_acceptability`]: #method.cmc_acceptability
+    /// [`cmc_perceptibility`]: #method.cmc_perceptibility
+    fn cmc<T: Color>(&self, other: &T, l: f64, c: f64) -> f64 {
+        let lab1: CIELABColor = self.convert();
+        let lab2: CIELABColor = other.convert();
+
+        let c_1 = lab1.a.hypot(lab1.b);
+        let c_2 = lab2.a.hypot(lab2.b);
+        let delta_l = lab1.l - lab2.l;
+        let delta_c = c_1 - c_2;
+        // as in CIE94, delta H is only defined through its square
+        let delta_h_sq =
+            ((lab1.a - lab2.a).powi(2) + (lab1.b - lab2.b).powi(2) - delta_c.powi(2)).max(0.0);
+        // the hue angle of the reference, in degrees between 0 and 360
+        let h_1 = {
+            let h = lab1.b.atan2(lab1.a).to_degrees();
+            if h < 0.0 {
+                h + 360.0
+            } else {
+                h
+            }
+        };
+
+        let s_l = if lab1.l < 16.0 {
+            0.511
+        } else {
+            0.040975 * lab1.l / (1.0 + 0.01765 * lab1.l)
+        };
+        let s_c = 0.0638 * c_1 / (1.0 + 0.0131 * c_1) + 0.638;
+        let f = (c_1.powi(4) / (c_1.powi(4) + 1900.0)).sqrt();
+        // the blue-purple-red band of hues uses a different hue weighting
+        let t = if (164.0..=345.0).contains(&h_1) {
+            0.56 + (0.2 * (h_1 + 168.0).to_radians().cos()).abs()
+        } else {
+            0.36 + (0.4 * (h_1 + 35.0).to_radians().cos()).abs()
+        };
+        let s_h = s_c * (f * t + 1.0 - f);
+
+        ((delta_l / (l * s_l)).powi(2) + (delta_c / (c * s_c)).powi(2) + delta_h_sq / s_h.powi(2))
+            .sqrt()
+    }
+    /// The CMC 2:1 color difference, the standard for judging whether two colors are an acceptable
+    /// match. See [`cmc`](#method.cmc) for more.
+    fn cmc_acceptability<T: Color>(&self, other: &T) -> f64 {
+        self.cmc(other, 2.0, 1.0)
+    }
+    /// The CMC 1:1 color difference, the standard for judging whether the difference between two
+    /// colors is perceptible. See [`cmc`](#method.cmc) for more.
+    fn cmc_perceptibility<T: Color>(&self, other: &T) -> f64 {
+        self.cmc(other, 1.0, 1.0)
+    }
     /// Using the metric that two colors with a CIEDE2000 distance of less than 1 are
     /// indistinguishable, determines whether two colors are visually distinguishable from each
     /// other. For more, check out [this guide](../color_distance.html).
@@ -853,6 +1486,319 @@ pub trait Color: Sized {
     fn visually_indistinguishable<T: Color>(&self, other: &T) -> bool {
         self.distance(other) <= 1.0
     }
+    /// Simulates how this color appears to a person with the given kind of color vision deficiency,
+    /// returning the result as an sRGB color to preview it with. `severity` ranges from 0, normal
+    /// vision, which returns the color unchanged, to 1, full dichromacy, in which the affected cone
+    /// type is missing entirely: values in between are interpolated linearly in LMS space to model
+    /// anomalous trichromacy, and values outside that range are clamped. See
+    /// [`LMSColor::to_dichromat`] for the model used. The result may be slightly outside the sRGB
+    /// gamut.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::CvdKind;
+    /// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+    /// let green = RGBColor::from_hex_code("#00ff00").unwrap();
+    /// let red_seen = red.simulate_cvd(CvdKind::Deuteranopia, 1.0);
+    /// let green_seen = green.simulate_cvd(CvdKind::Deuteranopia, 1.0);
+    /// // red and green are much harder to tell apart
+    /// assert!(red_seen.distance(&green_seen) < red.distance(&green) / 2.);
+    /// ```
+    ///
+    /// [`LMSColor::to_dichromat`]: ../colors/lmscolor/struct.LMSColor.html#method.to_dichromat
+    fn simulate_cvd(&self, kind: CvdKind, severity: f64) -> RGBColor {
+        let lms: LMSColor = self.convert();
+        let dichromat = lms.to_dichromat(kind);
+        dichromat
+            .weighted_midpoint(lms, severity.clamp(0.0, 1.0))
+            .convert()
+    }
+}
+
+/// Computes the pairwise CIEDE2000 differences between every color in a palette, as a matrix: the
+/// entry at row `i` and column `j` is the [`distance`] between `colors[i]` and `colors[j]`. The
+/// matrix is symmetric with zeros on the diagonal. This is useful for finding near-duplicate colors
+/// in a palette, which will have small entries off the diagonal.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::similarity_matrix;
+/// let palette: Vec<RGBColor> = ["#ff0000", "#fe0101", "#0000ff"]
+///     .iter()
+///     .map(|x| RGBColor::from_hex_code(x).unwrap())
+///     .collect();
+/// let matrix = similarity_matrix(&palette);
+/// // the first two are indistinguishable
+/// assert!(matrix[0][1] < 1.0);
+/// assert!(matrix[0][2] > 50.0);
+/// ```
+///
+/// [`distance`]: trait.Color.html#method.distance
+pub fn similarity_matrix<T: Color>(colors: &[T]) -> Vec<Vec<f64>> {
+    let n = colors.len();
+    let mut matrix = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let dist = colors[i].distance(&colors[j]);
+            matrix[i][j] = dist;
+            matrix[j][i] = dist;
+        }
+    }
+    matrix
+}
+
+/// Measures how much adding `candidate` to a palette would add to it: the CIEDE2000 [`distance`]
+/// from `candidate` to the closest color already in `palette`. A large value means the candidate
+/// is distinct from everything in the palette, while a value near 0 means it nearly duplicates an
+/// existing color. This is useful for suggesting the next color to add when building a palette.
+/// Anything is new to an empty palette, so that gives infinity.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::coverage_gain;
+/// let palette: Vec<RGBColor> = ["#e41a1c", "#377eb8"]
+///     .iter()
+///     .map(|x| RGBColor::from_hex_code(x).unwrap())
+///     .collect();
+/// let green = RGBColor::from_hex_code("#4daf4a").unwrap();
+/// let another_red = RGBColor::from_hex_code("#e0201c").unwrap();
+/// assert!(coverage_gain(&palette, green) > coverage_gain(&palette, another_red));
+/// ```
+///
+/// [`distance`]: trait.Color.html#method.distance
+pub fn coverage_gain(palette: &[RGBColor], candidate: RGBColor) -> f64 {
+    palette
+        .iter()
+        .map(|color| candidate.distance(color))
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Generates a palette of `n` colors that are as easy to tell apart as possible, for plots of
+/// categorical data. This is a simplified version of [Glasbey's
+/// method](https://doi.org/10.1002/col.20327): starting from a grid of candidate colors evenly
+/// spaced in CIELAB and inside the sRGB gamut, it repeatedly picks the candidate whose CIEDE2000
+/// distance to the closest color already chosen is as large as possible. If a `background` is
+/// given, the colors are also kept distinct from it, so they stand out when drawn on it. Without
+/// one, the first color is the candidate furthest from a middle gray. The first few colors are the
+/// most distinct: each additional color is closer to the others, and past a few dozen they become
+/// hard to tell apart. The result is deterministic.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::distinct_palette;
+/// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+/// let palette = distinct_palette(6, Some(white));
+/// assert_eq!(palette.len(), 6);
+/// for color in palette.iter() {
+///     assert!(color.distance(&white) > 20.);
+/// }
+/// ```
+pub fn distinct_palette(n: usize, background: Option<RGBColor>) -> Vec<RGBColor> {
+    let mut candidates: Vec<RGBColor> = vec![];
+    for l in 1..10 {
+        for a in -10..=10 {
+            for b in -10..=10 {
+                let rgb: RGBColor = CIELABColor {
+                    l: f64::from(l) * 10.0,
+                    a: f64::from(a) * 10.0,
+                    b: f64::from(b) * 10.0,
+                }
+                .convert();
+                if rgb.is_in_gamut() {
+                    candidates.push(rgb);
+                }
+            }
+        }
+    }
+    let gray: RGBColor = CIELABColor {
+        l: 50.0,
+        a: 0.0,
+        b: 0.0,
+    }
+    .convert();
+    // the distance from each candidate to the closest color it needs to be distinct from
+    let mut scores: Vec<f64> = candidates
+        .iter()
+        .map(|color| color.distance(&background.unwrap_or(gray)))
+        .collect();
+    let mut palette = vec![];
+    for i in 0..n.min(candidates.len()) {
+        let mut best = 0;
+        for (j, &score) in scores.iter().enumerate() {
+            if score > scores[best] {
+                best = j;
+            }
+        }
+        let chosen = candidates[best];
+        palette.push(chosen);
+        for (score, color) in scores.iter_mut().zip(candidates.iter()) {
+            let dist = color.distance(&chosen);
+            // the gray was only for picking the first color, so it doesn't count after that
+            *score = if i == 0 && background.is_none() {
+                dist
+            } else {
+                score.min(dist)
+            };
+        }
+    }
+    palette
+}
+
+/// Corrects a color cast in a set of colors, such as the pixels of a photo, using the classic
+/// gray-world white balance: assuming that the scene should average out to a neutral gray, each of
+/// the red, green, and blue channels is scaled so that their averages become equal. The averages
+/// and scaling are in linear light, not on the gamma-encoded sRGB values, and the averages are all
+/// brought to the gray with the same luminance as the average color, so overall brightness is kept.
+/// Channels that come out brighter than 1 are clamped. If a channel is entirely black it can't be
+/// scaled, so it's left alone. This works well for varied scenes, but will wrongly neutralize
+/// scenes that really are mostly one color, like a forest or a sunset.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::gray_world_balance;
+/// // a photo taken under tungsten light, with everything too orange
+/// let photo: Vec<RGBColor> = ["#d0a080", "#806040", "#f0d0b0", "#604838"]
+///     .iter()
+///     .map(|x| RGBColor::from_hex_code(x).unwrap())
+///     .collect();
+/// let balanced = gray_world_balance(&photo);
+/// assert!(balanced[0].chroma() < photo[0].chroma());
+/// ```
+pub fn gray_world_balance(colors: &[RGBColor]) -> Vec<RGBColor> {
+    let n = colors.len() as f64;
+    let mut means = [0.0; 3];
+    for color in colors {
+        means[0] += srgb_to_linear(color.r) / n;
+        means[1] += srgb_to_linear(color.g) / n;
+        means[2] += srgb_to_linear(color.b) / n;
+    }
+    // the gray with the same relative luminance as the average
+    let gray = 0.2126 * means[0] + 0.7152 * means[1] + 0.0722 * means[2];
+    let gains: Vec<f64> = means
+        .iter()
+        .map(|&mean| if mean > 0.0 { gray / mean } else { 1.0 })
+        .collect();
+    let apply = |x: f64, gain: f64| linear_to_srgb((srgb_to_linear(x) * gain).clamp(0.0, 1.0));
+    colors
+        .iter()
+        .map(|color| RGBColor {
+            r: apply(color.r, gains[0]),
+            g: apply(color.g, gains[1]),
+            b: apply(color.b, gains[2]),
+        })
+        .collect()
+}
+
+/// Gets the XYZ coordinates, with a luminance of 1, of a blackbody radiator at the given temperature
+/// in kelvin, clamped to the range 1000 K to 40000 K. This uses Krystek's rational approximation of
+/// the Planckian locus, which is most accurate below 15000 K.
+fn planckian_white(kelvin: f64) -> [f64; 3] {
+    let t = kelvin.clamp(1000.0, 40000.0);
+    // Krystek (1985), in CIE 1960 UCS coordinates
+    let u = (0.860117757 + 1.54118254e-4 * t + 1.28641212e-7 * t * t)
+        / (1.0 + 8.42420235e-4 * t + 7.08145163e-7 * t * t);
+    let v = (0.317398726 + 4.22806245e-5 * t + 4.20481691e-8 * t * t)
+        / (1.0 - 2.89741816e-5 * t + 1.61456053e-7 * t * t);
+    // now to xy chromaticity, and then to XYZ with a luminance of 1
+    let denom = 2.0 * u - 8.0 * v + 4.0;
+    let (x, y) = (3.0 * u / denom, 2.0 * v / denom);
+    [x / y, 1.0, (1.0 - x - y) / y]
+}
+
+/// Reduces an image to the colors in `palette` with Floyd-Steinberg dithering, in place. `pixels`
+/// holds the image row by row from the top left, `width` pixels to a row. Each pixel is replaced
+/// with the palette color nearest to it, as by [`RGBColor::nearest_in_palette`], and the
+/// difference between the two is spread over the neighboring pixels that haven't been replaced
+/// yet: 7/16 to the right, and 3/16, 5/16, and 1/16 to the lower left, below, and lower right.
+/// That way, areas of the image keep their average color even though each pixel can only take a
+/// few values. The error is measured and spread in linear light, as in [`LinearRGBColor`], so
+/// that the averages are averages of light: in gamma-encoded sRGB, dithered areas would come out
+/// too bright. Colors outside of the palette's gamut can't be reproduced on average, and give
+/// their closest approximation.
+///
+/// # Panics
+///
+/// Panics if `palette` is empty, or if `pixels` isn't a whole number of rows of `width` pixels.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::dither_floyd_steinberg;
+/// let black = RGBColor::from_hex_code("#000000").unwrap();
+/// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+/// let gray = RGBColor::from_hex_code("#bcbcbc").unwrap();
+/// // #BCBCBC is half as bright as white, so it becomes about half black and half white
+/// let mut pixels = vec![gray; 64];
+/// dither_floyd_steinberg(&mut pixels, 8, &[black, white]);
+/// let whites = pixels.iter().filter(|c| c.to_string() == "#FFFFFF").count();
+/// assert!(whites > 24 && whites < 40);
+/// ```
+///
+/// [`RGBColor::nearest_in_palette`]: struct.RGBColor.html#method.nearest_in_palette
+/// [`LinearRGBColor`]: ../colors/linearrgbcolor/struct.LinearRGBColor.html
+// `usize::is_multiple_of` needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+pub fn dither_floyd_steinberg(pixels: &mut [RGBColor], width: usize, palette: &[RGBColor]) {
+    assert!(!palette.is_empty(), "palette must not be empty");
+    if pixels.is_empty() {
+        return;
+    }
+    assert!(
+        width > 0 && pixels.len() % width == 0,
+        "pixels must be a whole number of rows"
+    );
+    let height = pixels.len() / width;
+    let linear_palette: Vec<LinearRGBColor> = palette
+        .iter()
+        .map(|&c| LinearRGBColor::from_srgb(c))
+        .collect();
+    let mut linear: Vec<[f64; 3]> = pixels
+        .iter()
+        .map(|&c| {
+            let lin = LinearRGBColor::from_srgb(c);
+            [lin.r, lin.g, lin.b]
+        })
+        .collect();
+    for y in 0..height {
+        for x in 0..width {
+            let i = y * width + x;
+            let [r, g, b] = linear[i];
+            // errors can push pixels out of gamut, but the full error still needs to be passed on
+            let current = LinearRGBColor {
+                r: r.clamp(0.0, 1.0),
+                g: g.clamp(0.0, 1.0),
+                b: b.clamp(0.0, 1.0),
+            };
+            let nearest = current.to_srgb().nearest_in_palette(palette);
+            pixels[i] = palette[nearest];
+            let chosen = linear_palette[nearest];
+            let error = [r - chosen.r, g - chosen.g, b - chosen.b];
+            let mut spread = |dx: isize, dy: usize, weight: f64| {
+                let nx = x as isize + dx;
+                if nx >= 0 && (nx as usize) < width && y + dy < height {
+                    let pixel = &mut linear[(y + dy) * width + nx as usize];
+                    for c in 0..3 {
+                        pixel[c] += error[c] * weight;
+                    }
+                }
+            };
+            spread(1, 0, 7.0 / 16.0);
+            spread(-1, 1, 3.0 / 16.0);
+            spread(0, 1, 5.0 / 16.0);
+            spread(1, 1, 1.0 / 16.0);
+        }
+    }
 }
 
 impl Color for XYZColor {
@@ -865,18 +1811,21 @@ impl Color for XYZColor {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 /// A color with red, green, and blue primaries of specified intensity, specifically in the sRGB
 /// gamut: most computer screens use this to display colors. The attributes `r`, `g`, and `b` are
 /// floating-point numbers from 0 to 1 for visible colors, allowing the avoidance of rounding errors
 /// or clamping errors when converting to and from RGB. Many conveniences are afforded so that
 /// working with RGB as if it were instead three integers from 0-255 is painless. Note that the
-/// integers generated from the underlying floating-point numbers round away from 0.
+/// integers generated from the underlying floating-point numbers round away from 0. Colors
+/// converted from other spaces are never clamped, so the components can fall outside of 0-1 for
+/// colors outside the sRGB gamut: see [`RGBColor::is_in_gamut`].
 ///
 /// Examples of this abound: this is used ubiquitously in Scarlet. Check the
 /// [`Color`] documentation for plenty.
 ///
 /// [`Color`]: ../color/trait.Color.html
+/// [`RGBColor::is_in_gamut`]: #method.is_in_gamut
 pub struct RGBColor {
     /// The red component. Ranges from 0 to 1 for numbers displayable by sRGB machines.
     pub r: f64,
@@ -965,12 +1914,642 @@ impl RGBColor {
     pub fn int_rgb_tup(&self) -> (u8, u8, u8) {
         (self.int_r(), self.int_g(), self.int_b())
     }
-    /// Given a string, returns that string wrapped in codes that will color the foreground. Used
-    /// for the trait implementation of write_colored_str, which should be used instead. Requires
-    /// the `terminal` feature.
-    #[cfg(feature = "terminal")]
-    fn base_write_colored_str(&self, text: &str) -> String {
-        format!(
+    /// Gives the color as a tuple of 8-bit components, the inverse of `RGBColor::from((r, g, b))`.
+    /// This is the same as [`RGBColor::int_rgb_tup`]: components are clamped and rounded exactly
+    /// like the hex code from `to_string()`, so the two always agree.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor{r: 0.5, g: 1.2, b: 0.001};
+    /// assert_eq!(color.to_rgb_tuple(), (128, 255, 0));
+    /// assert_eq!(color.to_string(), "#80FF00");
+    /// ```
+    ///
+    /// [`RGBColor::int_rgb_tup`]: #method.int_rgb_tup
+    pub fn to_rgb_tuple(&self) -> (u8, u8, u8) {
+        self.int_rgb_tup()
+    }
+    /// Checks whether the color is inside the sRGB gamut, i.e., whether it can be displayed as it
+    /// is. Conversions into `RGBColor` never clamp, so a color from a wider space like CIELAB keeps
+    /// components outside of 0-1 in `r`, `g`, and `b` until it's quantized by methods like
+    /// [`RGBColor::int_r`] or [`RGBColor::to_string`]: this reports whether that quantization will
+    /// change the color. A tolerance of 10<sup>-9</sup> allows for floating-point error, so colors
+    /// that round-trip through other spaces still count as in gamut.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// let orange = RGBColor::from_hex_code("#ff8000").unwrap();
+    /// assert!(orange.is_in_gamut());
+    /// // a green far more vivid than any screen can show
+    /// let green: RGBColor = CIELABColor{l: 60., a: -120., b: 40.}.convert();
+    /// assert!(!green.is_in_gamut());
+    /// assert!(green.g > 1. || green.r < 0. || green.b < 0.);
+    /// ```
+    ///
+    /// [`RGBColor::int_r`]: #method.int_r
+    /// [`RGBColor::to_string`]: #method.to_string
+    pub fn is_in_gamut(&self) -> bool {
+        [self.r, self.g, self.b]
+            .iter()
+            .all(|x| (-1e-9..=1.0 + 1e-9).contains(x))
+    }
+    /// Computes the relative luminance of the color as defined by WCAG 2.1: each component is
+    /// linearized with the sRGB transfer function, and the results are weighted by how bright each
+    /// primary looks. This ranges from 0 for black to 1 for white. It's nearly the same as the Y
+    /// value of the color in XYZ, but follows the WCAG formula exactly, so it matches other
+    /// accessibility tools.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// let green = RGBColor::from_hex_code("#00ff00").unwrap();
+    /// assert_eq!(white.relative_luminance(), 1.0);
+    /// assert_eq!(green.relative_luminance(), 0.7152);
+    /// ```
+    pub fn relative_luminance(&self) -> f64 {
+        let lin = LinearRGBColor::from_srgb(*self);
+        0.2126 * lin.r + 0.7152 * lin.g + 0.0722 * lin.b
+    }
+    /// Converts this color to the neutral gray with the same [relative luminance]: the gray that
+    /// emits the same amount of light, by the WCAG definition. The result always has exactly equal
+    /// components, and its relative luminance matches this color's to within floating-point error.
+    ///
+    /// [`Color::grayscale`] is similar, but works by removing the chroma in CIELAB. CIELAB lightness
+    /// is computed from luminance too, so the two usually give nearly the same gray. However, that
+    /// route goes through CIELAB's D50 white point and back, so it doesn't exactly preserve WCAG
+    /// luminance: pure blue comes out with a luminance of about 0.061 instead of 0.0722. Its result
+    /// is also only approximately neutral in sRGB. Use this instead when the gray has to agree with
+    /// WCAG contrast checks or other tools that use the same formula, like a grayscale preview of a
+    /// design.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+    /// let gray = red.to_luminance_gray();
+    /// assert_eq!(gray.to_string(), "#7F7F7F");
+    /// assert!((gray.relative_luminance() - red.relative_luminance()).abs() <= 1e-12);
+    /// ```
+    ///
+    /// [relative luminance]: #method.relative_luminance
+    /// [`Color::grayscale`]: trait.Color.html#method.grayscale
+    pub fn to_luminance_gray(&self) -> RGBColor {
+        let y = self.relative_luminance();
+        LinearRGBColor { r: y, g: y, b: y }.to_srgb()
+    }
+    /// Computes the WCAG 2.1 contrast ratio between this color and another: `(L1 + 0.05) / (L2 +
+    /// 0.05)`, where L1 is the [relative luminance] of the lighter color and L2 that of the
+    /// darker. This ranges from 1, for colors with the same luminance, to 21, for black and white,
+    /// and doesn't depend on the order of the colors.
+    ///
+    /// [relative luminance]: #method.relative_luminance
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// assert_eq!(black.contrast_ratio(&white), 21.0);
+    /// ```
+    pub fn contrast_ratio(&self, other: &RGBColor) -> f64 {
+        let l1 = self.relative_luminance();
+        let l2 = other.relative_luminance();
+        (l1.max(l2) + 0.05) / (l1.min(l2) + 0.05)
+    }
+    /// Checks whether text in one of these colors on a background of the other meets the WCAG 2.1
+    /// level AA contrast requirement: a contrast ratio of at least 4.5, or 3 for large text (at
+    /// least 18 point, or 14 point bold).
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let gray = RGBColor::from_hex_code("#808080").unwrap();
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// // fine for headings, but too faint for body text
+    /// assert!(gray.meets_aa(&white, true));
+    /// assert!(!gray.meets_aa(&white, false));
+    /// ```
+    pub fn meets_aa(&self, other: &RGBColor, large_text: bool) -> bool {
+        let required = if large_text { 3.0 } else { 4.5 };
+        self.contrast_ratio(other) >= required
+    }
+    /// Checks whether text in one of these colors on a background of the other meets the WCAG 2.1
+    /// level AAA contrast requirement: a contrast ratio of at least 7, or 4.5 for large text (at
+    /// least 18 point, or 14 point bold).
+    pub fn meets_aaa(&self, other: &RGBColor, large_text: bool) -> bool {
+        let required = if large_text { 4.5 } else { 7.0 };
+        self.contrast_ratio(other) >= required
+    }
+    /// Constructs an RGB color from an integer packed as `0xRRGGBB`, the format many palettes are
+    /// stored in: red is in bits 16-23, green in bits 8-15, and blue in bits 0-7. The top 8 bits
+    /// are ignored.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let color = RGBColor::from_u32(0x1a2b3c);
+    /// assert_eq!(color.int_rgb_tup(), (0x1a, 0x2b, 0x3c));
+    /// assert_eq!(color.to_string(), "#1A2B3C");
+    /// ```
+    pub fn from_u32(val: u32) -> RGBColor {
+        RGBColor::from((
+            ((val >> 16) & 0xff) as u8,
+            ((val >> 8) & 0xff) as u8,
+            (val & 0xff) as u8,
+        ))
+    }
+    /// Returns a lighter version of this color, raising its CIELAB lightness by `amount` (up to a
+    /// maximum of 100) while keeping a and b the same. Because CIELAB lightness is perceptually
+    /// uniform, lightening two different colors by the same amount looks like the same change,
+    /// which isn't true of scaling RGB values. Negative amounts darken instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let brand = RGBColor::from_hex_code("#2a7ab0").unwrap();
+    /// let hover = brand.lighten(10.);
+    /// assert!((hover.lightness() - brand.lightness() - 10.).abs() <= 1e-8);
+    /// ```
+    pub fn lighten(&self, amount: f64) -> RGBColor {
+        self.with_lightness(self.lightness() + amount)
+    }
+    /// Returns a darker version of this color, lowering its CIELAB lightness by `amount` (down to a
+    /// minimum of 0). This is the same as [`RGBColor::lighten`] with a negated amount.
+    ///
+    /// [`RGBColor::lighten`]: #method.lighten
+    pub fn darken(&self, amount: f64) -> RGBColor {
+        self.lighten(-amount)
+    }
+    /// Returns a copy of this color with its CIELAB lightness set to `lightness`, clamped to the
+    /// range 0-100. This is [`Color::set_lightness`] without needing a mutable copy.
+    ///
+    /// [`Color::set_lightness`]: trait.Color.html#method.set_lightness
+    pub fn with_lightness(&self, lightness: f64) -> RGBColor {
+        let mut color = *self;
+        color.set_lightness(lightness);
+        color
+    }
+    /// Returns a more colorful version of this color, scaling its CIELCH chroma up by a factor of
+    /// `1 + amount` while keeping its lightness and hue: an `amount` of 0.5 makes it 50% more
+    /// chromatic. If that goes outside the sRGB gamut, the chroma is reduced until it fits, as in
+    /// [`GamutStrategy::ReduceChroma`]. Grays have no chroma to scale, so they are unaffected.
+    /// Negative amounts desaturate instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let muted = RGBColor::from_hex_code("#7a8a9a").unwrap();
+    /// let vivid = muted.saturate(1.);
+    /// assert!((vivid.chroma() - 2. * muted.chroma()).abs() <= 1e-6);
+    /// assert!((vivid.lightness() - muted.lightness()).abs() <= 1e-6);
+    /// ```
+    ///
+    /// [`GamutStrategy::ReduceChroma`]: enum.GamutStrategy.html#variant.ReduceChroma
+    pub fn saturate(&self, amount: f64) -> RGBColor {
+        let lch: CIELCHColor = self.convert();
+        let c = (lch.c * (1.0 + amount)).max(0.0);
+        RGBColor::clip_to_gamut(CIELCHColor { c, ..lch }, GamutStrategy::ReduceChroma)
+    }
+    /// Returns a less colorful version of this color, scaling its CIELCH chroma down by a factor
+    /// of `1 - amount` while keeping its lightness and hue: an `amount` of 1 gives a neutral gray,
+    /// the same as [`Color::grayscale`]. This is the same as [`RGBColor::saturate`] with a negated
+    /// amount.
+    ///
+    /// [`Color::grayscale`]: trait.Color.html#method.grayscale
+    /// [`RGBColor::saturate`]: #method.saturate
+    pub fn desaturate(&self, amount: f64) -> RGBColor {
+        self.saturate(-amount)
+    }
+    /// Rotates the color's CIELCH hue by `degrees`, keeping its lightness and chroma, and wrapping
+    /// around so that the hue stays between 0 and 360. Positive angles go from red towards yellow,
+    /// green, and blue. The result isn't clamped, so rotations compose exactly, but it may be
+    /// outside the sRGB gamut for very chromatic colors: use [`RGBColor::clip_to_gamut`] or
+    /// [`RGBColor::is_in_gamut`] if that matters.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let teal = RGBColor::from_hex_code("#2a7ab0").unwrap();
+    /// let rotated = teal.rotate_hue(90.);
+    /// assert!((rotated.hue() - (teal.hue() + 90.) % 360.).abs() <= 1e-8);
+    /// assert!((rotated.lightness() - teal.lightness()).abs() <= 1e-8);
+    /// ```
+    ///
+    /// [`RGBColor::clip_to_gamut`]: #method.clip_to_gamut
+    /// [`RGBColor::is_in_gamut`]: #method.is_in_gamut
+    pub fn rotate_hue(&self, degrees: f64) -> RGBColor {
+        let lch: CIELCHColor = self.convert();
+        CIELCHColor {
+            h: (lch.h + degrees).rem_euclid(360.0),
+            ..lch
+        }
+        .convert()
+    }
+    /// Gets the complementary color, with the opposite CIELCH hue and the same lightness. Like the
+    /// other color scheme generators such as [`RGBColor::triadic`], the chroma is kept if possible
+    /// but reduced if needed to stay in the sRGB gamut: use [`RGBColor::rotate_hue`] by 180 degrees
+    /// to always keep the chroma instead.
+    ///
+    /// [`RGBColor::triadic`]: #method.triadic
+    /// [`RGBColor::rotate_hue`]: #method.rotate_hue
+    pub fn complementary(&self) -> RGBColor {
+        self.rotate_hue_in_gamut(180.0)
+    }
+    /// Rotates the CIELCH hue by `degrees` like [`RGBColor::rotate_hue`], but reduces the chroma if
+    /// needed to stay in the sRGB gamut. Used for the color scheme generators.
+    ///
+    /// [`RGBColor::rotate_hue`]: #method.rotate_hue
+    fn rotate_hue_in_gamut(&self, degrees: f64) -> RGBColor {
+        RGBColor::clip_to_gamut(self.rotate_hue(degrees), GamutStrategy::ReduceChroma)
+    }
+    /// Gets a triadic color scheme: this color and the two colors whose CIELCH hues are a third of
+    /// the way around the hue circle from it, at the same lightness. Working in CIELCH rather than
+    /// HSL keeps the three colors evenly spaced perceptually. If a rotated color would be outside
+    /// of the sRGB gamut, its chroma is reduced to fit, as in [`GamutStrategy::ReduceChroma`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let base = RGBColor::from_hex_code("#2a7ab0").unwrap();
+    /// let [first, second, third] = base.triadic();
+    /// assert_eq!(first.to_string(), base.to_string());
+    /// assert!((second.hue() - (base.hue() + 120.) % 360.).abs() <= 1e-6);
+    /// assert!((third.hue() - (base.hue() + 240.) % 360.).abs() <= 1e-6);
+    /// ```
+    ///
+    /// [`GamutStrategy::ReduceChroma`]: enum.GamutStrategy.html#variant.ReduceChroma
+    pub fn triadic(&self) -> [RGBColor; 3] {
+        [
+            *self,
+            self.rotate_hue_in_gamut(120.0),
+            self.rotate_hue_in_gamut(240.0),
+        ]
+    }
+    /// Gets a split-complementary color scheme: this color and the two colors on either side of
+    /// its complement, 150 and 210 degrees around the CIELCH hue circle. This has most of the
+    /// contrast of a complementary pair, but is less harsh. Chroma is reduced to fit in the gamut
+    /// as in [`RGBColor::triadic`].
+    ///
+    /// [`RGBColor::triadic`]: #method.triadic
+    pub fn split_complementary(&self) -> [RGBColor; 3] {
+        [
+            *self,
+            self.rotate_hue_in_gamut(150.0),
+            self.rotate_hue_in_gamut(210.0),
+        ]
+    }
+    /// Gets a tetradic (square) color scheme: this color and the three colors 90, 180, and 270
+    /// degrees around the CIELCH hue circle from it. Chroma is reduced to fit in the gamut as in
+    /// [`RGBColor::triadic`].
+    ///
+    /// [`RGBColor::triadic`]: #method.triadic
+    pub fn tetradic(&self) -> [RGBColor; 4] {
+        [
+            *self,
+            self.rotate_hue_in_gamut(90.0),
+            self.rotate_hue_in_gamut(180.0),
+            self.rotate_hue_in_gamut(270.0),
+        ]
+    }
+    /// Gets an analogous color scheme: `count` colors with CIELCH hues evenly spread over an arc of
+    /// `spread` degrees centered on this color's hue, in order of increasing hue. With an odd
+    /// `count`, this color is in the middle. Chroma is reduced to fit in the gamut as in
+    /// [`RGBColor::triadic`]. A `count` of 1 gives just this color, and 0 gives nothing.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let base = RGBColor::from_hex_code("#c04020").unwrap();
+    /// let scheme = base.analogous(60., 5);
+    /// assert_eq!(scheme.len(), 5);
+    /// assert_eq!(scheme[2].to_string(), base.to_string());
+    /// ```
+    ///
+    /// [`RGBColor::triadic`]: #method.triadic
+    pub fn analogous(&self, spread: f64, count: usize) -> Vec<RGBColor> {
+        if count == 1 {
+            return vec![*self];
+        }
+        (0..count)
+            .map(|i| {
+                let offset = spread * (i as f64 / (count - 1) as f64 - 0.5);
+                if offset == 0.0 {
+                    *self
+                } else {
+                    self.rotate_hue_in_gamut(offset)
+                }
+            })
+            .collect()
+    }
+    /// Gets `n` tints of this color: steps from it toward white, evenly spaced in CIELAB so that
+    /// each step looks about as big as the last. The base color isn't included, and the last tint
+    /// is white itself, so `tints(4)` gives colors 25%, 50%, 75%, and 100% of the way to white.
+    /// Mixing in CIELAB scales the chroma down as the lightness goes up without changing the hue,
+    /// so the ramp doesn't drift toward purple like blue mixed with white in sRGB does. Any steps
+    /// that fall outside the sRGB gamut have their chroma reduced to fit, as in
+    /// [`GamutStrategy::ReduceChroma`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let brand = RGBColor::from_hex_code("#2a7ab0").unwrap();
+    /// let ramp = brand.tints(4);
+    /// assert_eq!(ramp.len(), 4);
+    /// assert!(ramp[0].lightness() > brand.lightness());
+    /// assert_eq!(ramp[3].to_string(), "#FFFFFF");
+    /// ```
+    ///
+    /// [`GamutStrategy::ReduceChroma`]: enum.GamutStrategy.html#variant.ReduceChroma
+    pub fn tints(&self, n: usize) -> Vec<RGBColor> {
+        self.steps_toward(
+            CIELABColor {
+                l: 100.0,
+                a: 0.0,
+                b: 0.0,
+            },
+            n,
+        )
+    }
+    /// Gets `n` shades of this color: steps from it toward black, evenly spaced in CIELAB. This
+    /// works like [`RGBColor::tints`], so the last shade is black.
+    ///
+    /// [`RGBColor::tints`]: #method.tints
+    pub fn shades(&self, n: usize) -> Vec<RGBColor> {
+        self.steps_toward(
+            CIELABColor {
+                l: 0.0,
+                a: 0.0,
+                b: 0.0,
+            },
+            n,
+        )
+    }
+    /// Gets `n` tones of this color: steps from it toward the neutral gray of the same CIELAB
+    /// lightness, evenly spaced in CIELAB. This works like [`RGBColor::tints`], so the last tone is
+    /// that gray. Unlike mixing with a fixed middle gray, this keeps the lightness of every tone the
+    /// same as the base color, so only the colorfulness changes.
+    ///
+    /// [`RGBColor::tints`]: #method.tints
+    pub fn tones(&self, n: usize) -> Vec<RGBColor> {
+        self.steps_toward(
+            CIELABColor {
+                l: self.lightness(),
+                a: 0.0,
+                b: 0.0,
+            },
+            n,
+        )
+    }
+    /// Gets `n` colors evenly spaced in CIELAB from this color to `target`, not including this
+    /// color but including the target. Used for the tint, shade, and tone generators.
+    fn steps_toward(&self, target: CIELABColor, n: usize) -> Vec<RGBColor> {
+        let base: CIELABColor = self.convert();
+        (1..=n)
+            .map(|i| {
+                let step = target.weighted_midpoint(base, i as f64 / n as f64);
+                RGBColor::clip_to_gamut(step, GamutStrategy::ReduceChroma)
+            })
+            .collect()
+    }
+    /// Blends `other` on top of this color with the given blend mode, as when layering two fully
+    /// opaque layers in an image editor: this color is the backdrop, and `other` is the layer
+    /// above it. Like in CSS, SVG, and most image editors, the blending is done on the
+    /// gamma-encoded sRGB values rather than on linear light, so results match those programs. Each
+    /// channel is blended separately after clamping to 0-1. See [`BlendMode`] for the modes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::BlendMode;
+    /// let photo = RGBColor::from_hex_code("#4080c0").unwrap();
+    /// let tint = RGBColor::from_hex_code("#ffcc00").unwrap();
+    /// assert_eq!(photo.blend(&tint, BlendMode::Multiply).to_string(), "#406600");
+    /// assert_eq!(photo.blend(&tint, BlendMode::Lighten).to_string(), "#FFCCC0");
+    /// ```
+    ///
+    /// [`BlendMode`]: enum.BlendMode.html
+    pub fn blend(&self, other: &RGBColor, mode: BlendMode) -> RGBColor {
+        let channel = |cb: f64, cs: f64| mode.blend_channel(cb.clamp(0.0, 1.0), cs.clamp(0.0, 1.0));
+        RGBColor {
+            r: channel(self.r, other.r),
+            g: channel(self.g, other.g),
+            b: channel(self.b, other.b),
+        }
+    }
+    /// Converts any color to sRGB, using the given strategy to map it into the gamut if it falls
+    /// outside of it. Colors already in the gamut are converted as-is with either strategy. See
+    /// [`GamutStrategy`] for the options.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::GamutStrategy;
+    /// # use scarlet::colors::{CIELABColor, CIELCHColor};
+    /// let vivid = CIELABColor{l: 60., a: 100., b: 60.};
+    /// let clamped = RGBColor::clip_to_gamut(vivid, GamutStrategy::ClampRGB);
+    /// let reduced = RGBColor::clip_to_gamut(vivid, GamutStrategy::ReduceChroma);
+    /// assert!(clamped.is_in_gamut() && reduced.is_in_gamut());
+    /// // clamping makes the color much darker, but reducing chroma keeps the lightness
+    /// assert!(clamped.lightness() < 55.);
+    /// assert!((reduced.lightness() - 60.).abs() <= 1e-4);
+    /// ```
+    ///
+    /// [`GamutStrategy`]: enum.GamutStrategy.html
+    pub fn clip_to_gamut<T: ColorPoint>(color: T, strategy: GamutStrategy) -> RGBColor {
+        let rgb: RGBColor = match strategy {
+            GamutStrategy::ClampRGB => color.convert(),
+            GamutStrategy::ReduceChroma => {
+                let lch: CIELCHColor = color.convert();
+                let c = srgb_max_chroma(lch.l, lch.h, lch.c);
+                CIELCHColor { c, ..lch }.convert()
+            }
+        };
+        RGBColor {
+            r: rgb.r.clamp(0.0, 1.0),
+            g: rgb.g.clamp(0.0, 1.0),
+            b: rgb.b.clamp(0.0, 1.0),
+        }
+    }
+    /// Constructs an approximate color for light of a single wavelength, given in nanometers, for
+    /// coloring spectra and spectroscopy plots. This uses the classic piecewise-linear
+    /// approximation by Dan Bruton: the hues run from violet at 380 nm through blue, green, yellow,
+    /// and orange to red, and the intensity falls off toward both ends of the visible range, below
+    /// 420 nm and above 700 nm. Wavelengths outside of 380-750 nm are invisible and give black.
+    /// Pure spectral colors are all outside the sRGB gamut, so this is a stylized rendering rather
+    /// than a colorimetric one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let yellow = RGBColor::from_wavelength(580.);
+    /// assert_eq!(yellow.to_string(), "#FFFF00");
+    /// let infrared = RGBColor::from_wavelength(900.);
+    /// assert_eq!(infrared.to_string(), "#000000");
+    /// ```
+    pub fn from_wavelength(nm: f64) -> RGBColor {
+        let (r, g, b) = match nm {
+            x if (380.0..440.0).contains(&x) => ((440.0 - x) / 60.0, 0.0, 1.0),
+            x if (440.0..490.0).contains(&x) => (0.0, (x - 440.0) / 50.0, 1.0),
+            x if (490.0..510.0).contains(&x) => (0.0, 1.0, (510.0 - x) / 20.0),
+            x if (510.0..580.0).contains(&x) => ((x - 510.0) / 70.0, 1.0, 0.0),
+            x if (580.0..645.0).contains(&x) => (1.0, (645.0 - x) / 65.0, 0.0),
+            x if (645.0..=750.0).contains(&x) => (1.0, 0.0, 0.0),
+            _ => (0.0, 0.0, 0.0),
+        };
+        // the eye is less sensitive at the edges of the visible range
+        let intensity = if nm < 420.0 {
+            0.3 + 0.7 * (nm - 380.0) / 40.0
+        } else if nm > 700.0 {
+            0.3 + 0.7 * (750.0 - nm) / 50.0
+        } else {
+            1.0
+        };
+        // the original approximation uses a gamma of 0.8
+        let adjust = |c: f64| (c * intensity).powf(0.8);
+        RGBColor {
+            r: adjust(r),
+            g: adjust(g),
+            b: adjust(b),
+        }
+    }
+    /// Converts a whole slice of XYZ colors to sRGB, giving the same result as calling
+    /// [`Color::convert`] on each one (up to floating-point rounding) but much faster. Converting
+    /// a single color involves adapting it to D65 and then applying the sRGB matrix, and most of
+    /// the work of adaptation only depends on the illuminant: here, those two steps are merged into
+    /// one matrix per illuminant, which is computed once and reused for each following color with
+    /// the same illuminant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::color::XYZColor;
+    /// let xyzs: Vec<XYZColor> = (0..100)
+    ///     .map(|i| XYZColor{x: 0.3, y: i as f64 / 100., z: 0.5, illuminant: Illuminant::D50})
+    ///     .collect();
+    /// let fast = RGBColor::convert_slice(&xyzs);
+    /// for (xyz, rgb) in xyzs.iter().zip(fast.iter()) {
+    ///     let slow: RGBColor = xyz.convert();
+    ///     assert_eq!(slow.to_string(), rgb.to_string());
+    /// }
+    /// ```
+    ///
+    /// [`Color::convert`]: trait.Color.html#method.convert
+    pub fn convert_slice(xyzs: &[XYZColor]) -> Vec<RGBColor> {
+        // the combined matrix for the illuminant of the last color seen
+        let mut cached: Option<(Illuminant, Matrix3<f64>)> = None;
+        xyzs.iter()
+            .map(|xyz| {
+                let matrix = match cached {
+                    Some((illuminant, matrix)) if illuminant == xyz.illuminant => matrix,
+                    _ => {
+                        let matrix = *SRGB * adaptation_matrix(xyz.illuminant, Illuminant::D65);
+                        cached = Some((xyz.illuminant, matrix));
+                        matrix
+                    }
+                };
+                let lin_rgb = matrix * vector![xyz.x, xyz.y, xyz.z];
+                RGBColor {
+                    r: linear_to_srgb(lin_rgb[0]),
+                    g: linear_to_srgb(lin_rgb[1]),
+                    b: linear_to_srgb(lin_rgb[2]),
+                }
+            })
+            .collect()
+    }
+    /// Constructs the color of a blackbody radiator at the given temperature in kelvin, the way color
+    /// temperatures of lights and stars are described: low temperatures are a deep orange, around
+    /// 6500 K is white, and higher temperatures are increasingly blue. Temperatures are clamped to
+    /// the range 1000 K to 40000 K. The chromaticity comes from Krystek's rational approximation
+    /// of the Planckian locus, which is most accurate below 15000 K. The result is scaled to be as
+    /// bright as possible, so its largest component is 1, and then clamped to the sRGB gamut.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let candle = RGBColor::from_temperature(1900.);
+    /// let daylight = RGBColor::from_temperature(6500.);
+    /// let sky = RGBColor::from_temperature(15000.);
+    /// assert!(candle.r > candle.g && candle.g > candle.b);
+    /// assert!(daylight.r.min(daylight.g).min(daylight.b) > 0.95);
+    /// assert!(sky.b > sky.g && sky.g > sky.r);
+    /// ```
+    pub fn from_temperature(kelvin: f64) -> RGBColor {
+        let [x, y, z] = planckian_white(kelvin);
+        let xyz = vector![x, y, z];
+        // scale so the brightest linear sRGB component is exactly 1
+        let lin_rgb = *SRGB * xyz;
+        let xyz = xyz / lin_rgb.max();
+        let rgb = RGBColor::from_xyz(XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        });
+        RGBColor {
+            r: rgb.r.clamp(0.0, 1.0),
+            g: rgb.g.clamp(0.0, 1.0),
+            b: rgb.b.clamp(0.0, 1.0),
+        }
+    }
+    /// Shifts the color temperature of this color, like the warm/cool slider in a photo editor. The
+    /// color is chromatically adapted, as by [`XYZColor::color_adapt`], from a white light at 6500 K
+    /// on the Planckian locus to one `kelvin_delta` lower: neutral colors take on the color of the
+    /// new light, and everything else changes with them. Positive values warm the color toward
+    /// orange, and negative values cool it toward blue. A change of 0 leaves the color as it is.
+    /// The new temperature is clamped to the range 1000 K to 40000 K, like in
+    /// [`RGBColor::from_temperature`]. The result is not clamped: large shifts of saturated colors
+    /// can leave the sRGB gamut.
+    ///
+    /// Note that equal changes in kelvin aren't equally visible: 500 K is a large shift around 3000
+    /// K, but a small one around 10000 K.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let gray = RGBColor::from_hex_code("#808080").unwrap();
+    /// let warm = gray.shift_temperature(1000.);
+    /// let cool = gray.shift_temperature(-1000.);
+    /// assert!(warm.r > warm.b);
+    /// assert!(cool.r < cool.b);
+    /// ```
+    ///
+    /// [`XYZColor::color_adapt`]: struct.XYZColor.html#method.color_adapt
+    /// [`RGBColor::from_temperature`]: #method.from_temperature
+    pub fn shift_temperature(&self, kelvin_delta: f64) -> RGBColor {
+        let from = Illuminant::Custom(planckian_white(6500.0));
+        let to = Illuminant::Custom(planckian_white(6500.0 - kelvin_delta));
+        let mut xyz = self.to_xyz(Illuminant::D65);
+        // treat the color as lit by the first white, and see what it looks like under the second
+        xyz.illuminant = from;
+        let mut shifted = xyz.color_adapt(to);
+        // then show the result as is, without adapting back
+        shifted.illuminant = Illuminant::D65;
+        RGBColor::from_xyz(shifted)
+    }
+    /// Given a string, returns that string wrapped in codes that will color the foreground. Used
+    /// for the trait implementation of write_colored_str, which should be used instead. Requires
+    /// the `terminal` feature.
+    #[cfg(feature = "terminal")]
+    fn base_write_colored_str(&self, text: &str) -> String {
+        format!(
             "{code}{text}{reset}",
             code = Fg(Rgb(self.int_r(), self.int_g(), self.int_b())),
             text = text,
@@ -1008,6 +2587,12 @@ impl From<(u8, u8, u8)> for RGBColor {
     }
 }
 
+impl From<[u8; 3]> for RGBColor {
+    fn from(rgb: [u8; 3]) -> RGBColor {
+        RGBColor::from((rgb[0], rgb[1], rgb[2]))
+    }
+}
+
 impl From<RGBColor> for (u8, u8, u8) {
     fn from(val: RGBColor) -> Self {
         (val.int_r(), val.int_g(), val.int_b())
@@ -1034,9 +2619,25 @@ impl From<RGBColor> for Coord {
     }
 }
 
-impl ToString for RGBColor {
-    fn to_string(&self) -> String {
-        format!(
+/// Formats the color as an uppercase hex code `#RRGGBB`, rounding each component to the nearest
+/// 8-bit value. This is also how `to_string()` works. Parsing the result with [`str::parse`] gives
+/// back the same hex code.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// let teal = RGBColor{r: 0., g: 0.5, b: 0.5};
+/// assert_eq!(format!("{}", teal), "#008080");
+/// let parsed: RGBColor = teal.to_string().parse().unwrap();
+/// assert_eq!(parsed.to_string(), teal.to_string());
+/// ```
+///
+/// [`str::parse`]: https://doc.rust-lang.org/std/primitive.str.html#method.parse
+impl fmt::Display for RGBColor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
             "#{:02X}{:02X}{:02X}",
             self.int_r(),
             self.int_g(),
@@ -1054,33 +2655,19 @@ impl Color for RGBColor {
 
         let lin_rgb_vec = *SRGB * vector![xyz_d65.x, xyz_d65.y, xyz_d65.z];
         // now we scale for gamma correction
-        let gamma_correct = |x: &f64| {
-            if x <= &0.0031308 {
-                12.92 * x
-            } else {
-                1.055 * x.powf(1.0 / 2.4) - 0.055
-            }
-        };
-        let float_vec: Vec<f64> = lin_rgb_vec.iter().map(gamma_correct).collect();
-        RGBColor {
-            r: float_vec[0],
-            g: float_vec[1],
-            b: float_vec[2],
+        LinearRGBColor {
+            r: lin_rgb_vec[0],
+            g: lin_rgb_vec[1],
+            b: lin_rgb_vec[2],
         }
+        .to_srgb()
+    }
+    fn from_rgb(rgb: RGBColor) -> RGBColor {
+        rgb
     }
     fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
-        let uncorrect_gamma = |x: &f64| {
-            if x <= &0.04045 {
-                x / 12.92
-            } else {
-                ((x + 0.055) / 1.055).powf(2.4)
-            }
-        };
-        let rgb_vec = vector![
-            uncorrect_gamma(&self.r),
-            uncorrect_gamma(&self.g),
-            uncorrect_gamma(&self.b)
-        ];
+        let lin = LinearRGBColor::from_srgb(*self);
+        let rgb_vec = vector![lin.r, lin.g, lin.b];
 
         // invert the matrix multiplication used in from_xyz()
         // use LU decomposition for accuracy
@@ -1110,6 +2697,14 @@ pub enum RGBParseError {
     InvalidFuncSyntax,
     /// This indicated an invalid color name was supplied to the `from_color_name()` function.
     InvalidX11Name,
+    /// This indicates that a component of a color function wasn't a valid number, or was a number
+    /// of the wrong kind, such as the percentage hue in `"hsl(50%, 100%, 50%)"`.
+    InvalidComponent,
+    /// This indicates that a color function had the wrong number of components, such as the two in
+    /// `"rgb(255, 0)"`.
+    WrongComponentCount,
+    /// This indicates that a string wasn't a color function that is supported, such as `"hsv("`.
+    UnknownFunction,
 }
 
 impl fmt::Display for RGBParseError {
@@ -1125,8 +2720,15 @@ impl From<ParseIntError> for RGBParseError {
 }
 
 impl From<CSSParseError> for RGBParseError {
-    fn from(_err: CSSParseError) -> RGBParseError {
-        RGBParseError::InvalidFuncSyntax
+    fn from(err: CSSParseError) -> RGBParseError {
+        match err {
+            CSSParseError::InvalidNumericCharacters
+            | CSSParseError::InvalidNumericSyntax
+            | CSSParseError::InvalidComponent => RGBParseError::InvalidComponent,
+            CSSParseError::WrongComponentCount => RGBParseError::WrongComponentCount,
+            CSSParseError::OutOfRange => RGBParseError::OutOfRange,
+            CSSParseError::InvalidColorSyntax => RGBParseError::InvalidFuncSyntax,
+        }
     }
 }
 
@@ -1137,6 +2739,9 @@ impl Error for RGBParseError {
             RGBParseError::InvalidHexSyntax => "Invalid hex code syntax",
             RGBParseError::InvalidFuncSyntax => "Invalid \"rgb(\" function call syntax",
             RGBParseError::InvalidX11Name => "Invalid X11 color name",
+            RGBParseError::InvalidComponent => "Invalid color function component",
+            RGBParseError::WrongComponentCount => "Wrong number of color function components",
+            RGBParseError::UnknownFunction => "Unknown color function",
         }
     }
 }
@@ -1168,7 +2773,7 @@ impl RGBColor {
     pub fn from_hex_code(hex: &str) -> Result<RGBColor, RGBParseError> {
         let mut chars: Vec<char> = hex.chars().collect();
         // check if leading hex, remove if so
-        if chars[0] == '#' {
+        if chars.first() == Some(&'#') {
             chars.remove(0);
         }
         // can only have 3 or 6 characters: error if not so
@@ -1203,6 +2808,52 @@ impl RGBColor {
             Ok(RGBColor::from((rgb[0], rgb[1], rgb[2])))
         }
     }
+    /// Parses a color written in CSS functional notation: either `rgb()`, with three components
+    /// that are each an integer from 0 to 255, a float from 0 to 1, or a percentage, or `hsl()`,
+    /// with a hue in degrees and a saturation and lightness given as percentages. Components can
+    /// be separated by commas, as in `"rgb(255, 0, 0)"`, or by whitespace, as in
+    /// `"rgb(100% 0% 0%)"`. Hues wrap around the color wheel, but other values must be in range.
+    /// Leading and trailing whitespace are ignored.
+    ///
+    /// # Errors
+    /// Returns an [`RGBParseError`] describing the problem on invalid input:
+    /// `UnknownFunction` for anything other than `rgb()` or `hsl()`, `InvalidFuncSyntax` if the
+    /// parentheses are missing, `WrongComponentCount` if there aren't three components,
+    /// `InvalidComponent` for a component that isn't a number or is the wrong kind of number, and
+    /// `OutOfRange` for a component outside of its range, like `"rgb(300, 0, 0)"`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # fn try_main() -> Result<(), RGBParseError> {
+    /// let red = RGBColor::from_css_string("rgb(255, 0, 0)")?;
+    /// let red2 = RGBColor::from_css_string("rgb(100% 0% 0%)")?;
+    /// let green = RGBColor::from_css_string("hsl(120, 100%, 50%)")?;
+    /// assert_eq!(red.to_string(), "#FF0000");
+    /// assert_eq!(red2.to_string(), "#FF0000");
+    /// assert_eq!(green.to_string(), "#00FF00");
+    /// assert_eq!(
+    ///     RGBColor::from_css_string("rgb(255, 0)"),
+    ///     Err(RGBParseError::WrongComponentCount)
+    /// );
+    /// # Ok(())
+    /// # }
+    /// # try_main().unwrap();
+    /// ```
+    ///
+    /// [`RGBParseError`]: enum.RGBParseError.html
+    pub fn from_css_string(s: &str) -> Result<RGBColor, RGBParseError> {
+        let s = s.trim();
+        if s.starts_with("rgb(") {
+            Ok(RGBColor::from(parse_rgb_str_strict(s)?))
+        } else if let Some(tup) = s.strip_prefix("hsl") {
+            let (h, s, l) = parse_hsl_hsv_tuple_strict(tup)?;
+            Ok(HSLColor { h, s, l }.convert())
+        } else {
+            Err(RGBParseError::UnknownFunction)
+        }
+    }
     /// Gets the RGB color corresponding to an X11 color name. Case is ignored.
     /// # Example
     ///
@@ -1224,7 +2875,6 @@ impl RGBColor {
         // this is the full list of X11 color names
         // I used a Python script to process it from this site:
         // https://github.com/bahamas10/css-color-names/blob/master/css-color-names.json
-        // I added the special "transparent" referring to #00000000
         let color_names: Vec<&str> = consts::X11_NAMES.to_vec();
         let color_codes: Vec<&str> = consts::X11_COLOR_CODES.to_vec();
         let mut names_to_codes = HashMap::new();
@@ -1239,8 +2889,106 @@ impl RGBColor {
             Some(x) => Self::from_hex_code(x),
         }
     }
+    /// Gets the RGB color corresponding to a CSS named color, like `"rebeccapurple"`, or `None` if
+    /// there isn't one. Case and surrounding whitespace are ignored. The special CSS keyword
+    /// `"transparent"` isn't a color without alpha, so it gives `None`: use [`RGBAColor`] for
+    /// colors with transparency. See [`RGBColor::from_color_name`] for a version that gives an
+    /// error instead.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let purple = RGBColor::from_name(" RebeccaPurple ").unwrap();
+    /// assert_eq!(purple.to_string(), "#663399");
+    /// assert!(RGBColor::from_name("transparent").is_none());
+    /// ```
+    ///
+    /// [`RGBAColor`]: ../colors/rgbacolor/struct.RGBAColor.html
+    /// [`RGBColor::from_color_name`]: #method.from_color_name
+    pub fn from_name(name: &str) -> Option<RGBColor> {
+        RGBColor::from_color_name(name.trim()).ok()
+    }
+    /// Finds the CSS named color closest to this one, measured by Euclidean distance in CIELAB,
+    /// and returns its name. Some names refer to the same color, like `"gray"` and `"grey"`: in
+    /// that case, whichever comes first alphabetically is returned. This is mostly useful for
+    /// debugging and logging, where a name is easier to read than a hex code.
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let almost_red = RGBColor::from_hex_code("#fa0a0a").unwrap();
+    /// assert_eq!(almost_red.nearest_named_color(), "red");
+    /// ```
+    pub fn nearest_named_color(&self) -> &'static str {
+        let lab: CIELABColor = self.convert();
+        let mut best = (consts::X11_NAMES[0], f64::INFINITY);
+        for (name, code) in consts::X11_NAMES.iter().zip(consts::X11_COLOR_CODES.iter()) {
+            let named: CIELABColor = RGBColor::from_hex_code(code).unwrap().convert();
+            let dist = lab.euclidean_distance(named);
+            if dist < best.1 {
+                best = (name, dist);
+            }
+        }
+        best.0
+    }
+    /// Finds the color in `palette` that looks closest to this one, by the CIEDE2000 difference
+    /// that [`Color::distance`] uses, and returns its index. Ties go to the earliest entry. This is
+    /// the core of reducing an image to a fixed set of colors, as for indexed-color formats like
+    /// GIF or for palette-based dithering: measuring closeness perceptually instead of in RGB avoids
+    /// snapping colors to entries that are near numerically but look quite different.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `palette` is empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let palette: Vec<RGBColor> = ["#000000", "#ffffff", "#ff0000", "#0000ff"]
+    ///     .iter()
+    ///     .map(|code| RGBColor::from_hex_code(code).unwrap())
+    ///     .collect();
+    /// let navy = RGBColor::from_hex_code("#1a1a80").unwrap();
+    /// assert_eq!(navy.nearest_in_palette(&palette), 3);
+    /// ```
+    ///
+    /// [`Color::distance`]: trait.Color.html#method.distance
+    pub fn nearest_in_palette(&self, palette: &[RGBColor]) -> usize {
+        assert!(!palette.is_empty(), "palette must not be empty");
+        let lab: CIELABColor = self.convert();
+        let mut best = (0, f64::INFINITY);
+        for (i, color) in palette.iter().enumerate() {
+            let dist = lab.distance(color);
+            if dist < best.1 {
+                best = (i, dist);
+            }
+        }
+        best.0
+    }
 }
 
+/// Parses a color from a string, so that `"#ff0000".parse::<RGBColor>()` works. Three formats are
+/// tried in order: a hex code as accepted by [`RGBColor::from_hex_code`], including lowercase
+/// and the three-digit shorthand `#f00`; an X11 color name as accepted by
+/// [`RGBColor::from_color_name`]; and a CSS-style `rgb(255, 0, 0)` string.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// let red1: RGBColor = "#FF0000".parse().unwrap();
+/// let red2: RGBColor = "#f00".parse().unwrap();
+/// let red3: RGBColor = "red".parse().unwrap();
+/// let red4: RGBColor = "rgb(255, 0, 0)".parse().unwrap();
+/// for red in [red1, red2, red3, red4].iter() {
+///     assert_eq!(red.to_string(), "#FF0000");
+/// }
+/// assert!("#ff00".parse::<RGBColor>().is_err());
+/// ```
+///
+/// [`RGBColor::from_hex_code`]: struct.RGBColor.html#method.from_hex_code
+/// [`RGBColor::from_color_name`]: struct.RGBColor.html#method.from_color_name
 impl FromStr for RGBColor {
     type Err = RGBParseError;
 
@@ -1262,8 +3010,180 @@ impl FromStr for RGBColor {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use colors::cieluvcolor::CIELUVColor;
     use consts::TEST_PRECISION;
+    use csv;
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
 
+    /// Writes a value out as a CSV record and reads it back.
+    fn csv_round_trip<T: Serialize + DeserializeOwned>(val: &T) -> T {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        writer.serialize(val).unwrap();
+        let data = writer.into_inner().unwrap();
+        let mut reader = csv::Reader::from_reader(data.as_slice());
+        let record = reader.deserialize().next().unwrap();
+        record.unwrap()
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let rgb = RGBColor::from_hex_code("#4c8a2e").unwrap();
+        let rgb2: RGBColor = csv_round_trip(&rgb);
+        assert!(rgb.distance(&rgb2) <= TEST_PRECISION);
+        assert_eq!(rgb.to_string(), rgb2.to_string());
+        // the illuminant comes along with the color
+        let xyz = XYZColor {
+            x: 0.2,
+            y: 0.4,
+            z: 0.3,
+            illuminant: Illuminant::D50,
+        };
+        let xyz2: XYZColor = csv_round_trip(&xyz);
+        assert_eq!(xyz2.illuminant, Illuminant::D50);
+        assert!(xyz.approx_equal(&xyz2));
+        let lab: CIELABColor = rgb.convert();
+        let lab2: CIELABColor = csv_round_trip(&lab);
+        assert!(lab.distance(&lab2) <= TEST_PRECISION);
+        let luv: CIELUVColor = rgb.convert();
+        let luv2: CIELUVColor = csv_round_trip(&luv);
+        assert!(luv.distance(&luv2) <= TEST_PRECISION);
+        let coord: Coord = lab.into();
+        assert_eq!(csv_round_trip(&coord), coord);
+    }
+    #[test]
+    fn test_from_css_string() {
+        let forms = [
+            ("rgb(255, 0, 0)", "#FF0000"),
+            ("rgb(255 0 0)", "#FF0000"),
+            ("  rgb(100%, 0%, 0%) ", "#FF0000"),
+            ("rgb(100% 0% 0%)", "#FF0000"),
+            ("rgb(0, 0.5, 20%)", "#007F33"),
+            ("hsl(120, 100%, 50%)", "#00FF00"),
+            ("hsl(240 100% 50%)", "#0000FF"),
+            ("hsl(-240, 100%, 50%)", "#00FF00"),
+        ];
+        for &(css, hex) in forms.iter() {
+            assert_eq!(RGBColor::from_css_string(css).unwrap().to_string(), hex);
+        }
+        let malformed = [
+            ("", RGBParseError::UnknownFunction),
+            ("#ff0000", RGBParseError::UnknownFunction),
+            ("hsv(120, 100%, 50%)", RGBParseError::UnknownFunction),
+            ("rgb(255, 0, 0", RGBParseError::InvalidFuncSyntax),
+            ("hsl 120, 100%, 50%", RGBParseError::InvalidFuncSyntax),
+            ("rgb(255, 0)", RGBParseError::WrongComponentCount),
+            ("rgb(255, 0, 0, 0)", RGBParseError::WrongComponentCount),
+            ("rgb(255 0 0 0)", RGBParseError::WrongComponentCount),
+            ("hsl(120, 100%)", RGBParseError::WrongComponentCount),
+            ("rgb(red, 0, 0)", RGBParseError::InvalidComponent),
+            ("rgb(255,, 0)", RGBParseError::InvalidComponent),
+            ("hsl(120, 100, 50%)", RGBParseError::InvalidComponent),
+            ("hsl(50%, 100%, 50%)", RGBParseError::InvalidComponent),
+            ("rgb(300, 0, 0)", RGBParseError::OutOfRange),
+            ("rgb(0, -4, 0)", RGBParseError::OutOfRange),
+            ("rgb(0, 0, 1.5)", RGBParseError::OutOfRange),
+            ("hsl(120, 101%, 50%)", RGBParseError::OutOfRange),
+        ];
+        for &(css, err) in malformed.iter() {
+            assert_eq!(RGBColor::from_css_string(css), Err(err), "{}", css);
+        }
+    }
+    #[test]
+    fn test_named_colors() {
+        let names = [
+            ("rebeccapurple", "#663399"),
+            ("  Tomato\n", "#FF6347"),
+            ("LIGHTGOLDENRODYELLOW", "#FAFAD2"),
+            ("grey", "#808080"),
+        ];
+        for &(name, hex) in names.iter() {
+            assert_eq!(RGBColor::from_name(name).unwrap().to_string(), hex);
+        }
+        assert!(RGBColor::from_name("transparent").is_none());
+        assert!(RGBColor::from_name("notacolor").is_none());
+        assert!(RGBColor::from_name("").is_none());
+
+        for &name in consts::X11_NAMES.iter() {
+            let color = RGBColor::from_name(name).unwrap();
+            let nearest = color.nearest_named_color();
+            // duplicates give the same color, so either name is right
+            assert_eq!(
+                RGBColor::from_name(nearest).unwrap().to_string(),
+                color.to_string()
+            );
+        }
+        assert_eq!(
+            RGBColor::from_name("grey").unwrap().nearest_named_color(),
+            "gray"
+        );
+        let off_orange = RGBColor::from_hex_code("#ffa010").unwrap();
+        assert_eq!(off_orange.nearest_named_color(), "orange");
+    }
+    #[test]
+    fn test_nearest_in_palette() {
+        let palette: Vec<RGBColor> = ["#ffffff", "#ff8000", "#ff0000", "#000000"]
+            .iter()
+            .map(|code| RGBColor::from_hex_code(code).unwrap())
+            .collect();
+        // a slightly orange red is still red
+        let off_red = RGBColor::from_hex_code("#f02010").unwrap();
+        assert_eq!(off_red.nearest_in_palette(&palette), 2);
+        let off_orange = RGBColor::from_hex_code("#f07020").unwrap();
+        assert_eq!(off_orange.nearest_in_palette(&palette), 1);
+        // each entry is its own nearest
+        for (i, color) in palette.iter().enumerate() {
+            assert_eq!(color.nearest_in_palette(&palette), i);
+        }
+        // ties go to the first entry
+        let doubled = [palette[2], palette[2]];
+        assert_eq!(off_red.nearest_in_palette(&doubled), 0);
+    }
+    #[test]
+    fn test_wcag_contrast() {
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        assert_eq!(black.relative_luminance(), 0.0);
+        assert_eq!(white.relative_luminance(), 1.0);
+        assert_eq!(black.contrast_ratio(&white), 21.0);
+        assert_eq!(white.contrast_ratio(&black), 21.0);
+        assert_eq!(white.contrast_ratio(&white), 1.0);
+        // known pairs, with the ratios given by the WebAIM contrast checker
+        let pairs = [
+            ("#777777", "#ffffff", 4.48),
+            ("#767676", "#ffffff", 4.54),
+            ("#0000ff", "#ffffff", 8.59),
+            ("#ff0000", "#ffffff", 4.0),
+            ("#595959", "#ffffff", 7.0),
+        ];
+        for &(fg, bg, ratio) in pairs.iter() {
+            let fg = RGBColor::from_hex_code(fg).unwrap();
+            let bg = RGBColor::from_hex_code(bg).unwrap();
+            assert!((fg.contrast_ratio(&bg) - ratio).abs() <= 0.01);
+        }
+        // #777 just misses AA for body text, while #767676 just makes it
+        let gray77 = RGBColor::from_hex_code("#777777").unwrap();
+        let gray76 = RGBColor::from_hex_code("#767676").unwrap();
+        assert!(!gray77.meets_aa(&white, false));
+        assert!(gray77.meets_aa(&white, true));
+        assert!(gray76.meets_aa(&white, false));
+        assert!(!gray76.meets_aaa(&white, false));
+        assert!(gray76.meets_aaa(&white, true));
+        assert!(black.meets_aaa(&white, false));
+    }
+    #[test]
+    fn test_coverage_gain() {
+        let palette: Vec<RGBColor> = ["#1b9e77", "#d95f02", "#7570b3"]
+            .iter()
+            .map(|x| RGBColor::from_hex_code(x).unwrap())
+            .collect();
+        let yellow = RGBColor::from_hex_code("#ffff33").unwrap();
+        let near_orange = RGBColor::from_hex_code("#d96004").unwrap();
+        assert!(coverage_gain(&palette, yellow) > 30.);
+        assert!(coverage_gain(&palette, near_orange) < 1.);
+        assert_eq!(coverage_gain(&palette, palette[1]), 0.);
+        assert_eq!(coverage_gain(&[], yellow), f64::INFINITY);
+    }
     #[test]
     fn test_visual_distinguishability() {
         let color1 = RGBColor::from_hex_code("#123456").unwrap();
@@ -1569,7 +3489,7 @@ mod tests {
         let rgb: RGBColor = "rgb(67%, 205, .937)".parse().unwrap();
         assert_eq!(*"#ABCDEF", rgb.to_string());
         assert_eq!(
-            Err(RGBParseError::InvalidFuncSyntax),
+            Err(RGBParseError::InvalidComponent),
             "rgb(53%%, 23, 44)".parse::<RGBColor>()
         );
     }
@@ -1683,8 +3603,164 @@ mod tests {
                 b: b_2[i],
             };
             // only good to 4 decimal points
-            assert!((lab1.distance(&lab2) - d_e[i]).abs() <= 1e-4);
-            assert!((lab2.distance(&lab1) - d_e[i]).abs() <= 1e-4);
+            assert!((lab1.ciede2000(&lab2) - d_e[i]).abs() <= 1e-4);
+            assert!((lab2.ciede2000(&lab1) - d_e[i]).abs() <= 1e-4);
+            // distance is CIEDE2000 under another name
+            assert_eq!(lab1.distance(&lab2), lab1.ciede2000(&lab2));
+        }
+    }
+    #[test]
+    fn test_similarity_matrix() {
+        let palette: Vec<RGBColor> = ["#1F77B4", "#FF7F0E", "#2CA02C", "#1F78B4"]
+            .iter()
+            .map(|x| RGBColor::from_hex_code(x).unwrap())
+            .collect();
+        let matrix = similarity_matrix(&palette);
+        assert_eq!(matrix.len(), 4);
+        for i in 0..4 {
+            assert_eq!(matrix[i].len(), 4);
+            assert_eq!(matrix[i][i], 0.0);
+            for j in 0..4 {
+                assert_eq!(matrix[i][j], matrix[j][i]);
+                assert!((matrix[i][j] - palette[i].distance(&palette[j])).abs() <= 1e-10);
+            }
+        }
+        // the first and last colors are near-identical
+        assert!(matrix[0][3] < 1.0);
+        assert!(matrix[0][1] > 10.0);
+        assert!(matrix[1][2] > 10.0);
+        assert!(similarity_matrix::<RGBColor>(&[]).is_empty());
+    }
+    #[test]
+    fn test_contrasting_pair() {
+        for code in [
+            "#2A7AB0", "#C03030", "#F0E040", "#208040", "#101010", "#FFFFFF",
+        ]
+        .iter()
+        {
+            let seed = RGBColor::from_hex_code(code).unwrap();
+            let (bg, fg) = seed.contrasting_pair();
+            assert!(bg.contrast_ratio(&fg) >= 4.5);
+            // gray has no meaningful hue
+            if seed.chroma() > 10.0 {
+                let diff = (bg.hue() - seed.hue()).abs();
+                assert!(diff.min(360.0 - diff) <= 1e-4);
+            }
+        }
+    }
+    #[test]
+    fn test_cie94() {
+        // computed by hand from the CIE94 definition
+        let pairs = [
+            (
+                CIELABColor {
+                    l: 50.0,
+                    a: 2.6772,
+                    b: -79.7751,
+                },
+                CIELABColor {
+                    l: 50.0,
+                    a: 0.0,
+                    b: -82.7485,
+                },
+                1.3950389,
+                1.4230462,
+            ),
+            (
+                CIELABColor {
+                    l: 22.7233,
+                    a: 20.0904,
+                    b: -46.694,
+                },
+                CIELABColor {
+                    l: 23.0331,
+                    a: 14.973,
+                    b: -42.5619,
+                },
+                2.5561331,
+                2.5309889,
+            ),
+        ];
+        for (lab1, lab2, graphic_arts, textiles) in pairs.iter() {
+            let d_g = lab1.cie94(lab2, CIE94App::GraphicArts);
+            let d_t = lab1.cie94(lab2, CIE94App::Textiles);
+            assert!((d_g - graphic_arts).abs() <= 1e-6);
+            assert!((d_t - textiles).abs() <= 1e-6);
+            assert!((d_g - d_t).abs() >= 1e-3);
+            // identical colors have no difference
+            assert!(lab1.cie94(lab1, CIE94App::Textiles) <= TEST_PRECISION);
+        }
+    }
+    #[test]
+    fn test_cmc() {
+        // the first pair is from the colormath test suite: the rest were computed by hand
+        let pairs = [
+            // reference hue of 352 degrees, with low lightness
+            (
+                CIELABColor {
+                    l: 0.9,
+                    a: 16.3,
+                    b: -2.22,
+                },
+                CIELABColor {
+                    l: 0.7,
+                    a: 14.2,
+                    b: -1.80,
+                },
+                1.4426051,
+                1.4818903,
+            ),
+            // reference hue of 272 degrees, inside the 164-345 band
+            (
+                CIELABColor {
+                    l: 50.0,
+                    a: 2.6772,
+                    b: -79.7751,
+                },
+                CIELABColor {
+                    l: 50.0,
+                    a: 0.0,
+                    b: -82.7485,
+                },
+                1.7387361,
+                1.7387361,
+            ),
+            // reference hue of 305 degrees, inside the band
+            (
+                CIELABColor {
+                    l: 61.2901,
+                    a: 3.7196,
+                    b: -5.3901,
+                },
+                CIELABColor {
+                    l: 61.4292,
+                    a: 2.248,
+                    b: -4.962,
+                },
+                1.7655590,
+                1.7683806,
+            ),
+            // reference hue of 133 degrees, outside the band
+            (
+                CIELABColor {
+                    l: 60.2574,
+                    a: -34.0099,
+                    b: 36.2677,
+                },
+                CIELABColor {
+                    l: 60.4626,
+                    a: -34.1751,
+                    b: 39.4387,
+                },
+                1.4204860,
+                1.4282295,
+            ),
+        ];
+        for (lab1, lab2, acceptability, perceptibility) in pairs.iter() {
+            assert!((lab1.cmc_acceptability(lab2) - acceptability).abs() <= 1e-6);
+            assert!((lab1.cmc_perceptibility(lab2) - perceptibility).abs() <= 1e-6);
+            assert_eq!(lab1.cmc(lab2, 2., 1.), lab1.cmc_acceptability(lab2));
+            assert!(lab1.cmc_acceptability(lab1) <= TEST_PRECISION);
         }
     }
     #[test]
@@ -1735,6 +3811,843 @@ mod tests {
         }
     }
     #[test]
+    fn test_at_lab_lightness() {
+        // a mid-lightness, moderate-chroma blue is displayable
+        let blue = RGBColor::at_lab_lightness(260., 30., 50.).unwrap();
+        let lch: CIELCHColor = blue.convert();
+        assert!((lch.l - 50.).abs() <= 1e-8);
+        assert!((lch.c - 30.).abs() <= 1e-8);
+        assert!((lch.h - 260.).abs() <= 1e-8);
+        // the same color can be found through any Color type
+        assert_eq!(
+            CIELABColor::at_lab_lightness(260., 30., 50.).map(|c| c.to_string()),
+            Some(blue.to_string())
+        );
+        // very high chroma or lightness are out of gamut
+        assert!(RGBColor::at_lab_lightness(260., 180., 50.).is_none());
+        assert!(RGBColor::at_lab_lightness(260., 30., 120.).is_none());
+        // every gray is fine, and anything returned is in gamut
+        for l in [0., 25., 50., 75., 100.].iter() {
+            assert!(RGBColor::at_lab_lightness(0., 0., *l)
+                .unwrap()
+                .is_in_gamut());
+        }
+        for h in 0..36 {
+            for c in 0..30 {
+                if let Some(rgb) = RGBColor::at_lab_lightness(h as f64 * 10., c as f64 * 5., 70.) {
+                    assert!(rgb.is_in_gamut());
+                }
+            }
+        }
+    }
+    #[test]
+    fn test_u8_conversions() {
+        let red = RGBColor::from((255, 0, 0));
+        assert_eq!((red.r, red.g, red.b), (1., 0., 0.));
+        assert_eq!(red, RGBColor::from([255, 0, 0]));
+        assert_eq!(red.to_rgb_tuple(), (255, 0, 0));
+        // every 8-bit value survives the round trip
+        for i in 0..=255u8 {
+            let color = RGBColor::from([i, 255 - i, i / 3]);
+            assert_eq!(color.to_rgb_tuple(), (i, 255 - i, i / 3));
+        }
+        // rounding matches the hex code, including at the halfway points
+        for i in 0..=1020 {
+            let x = i as f64 / 1020.;
+            let color = RGBColor {
+                r: x,
+                g: 1. - x,
+                b: x * 0.5,
+            };
+            let (r, g, b) = color.to_rgb_tuple();
+            assert_eq!(format!("#{:02X}{:02X}{:02X}", r, g, b), color.to_string());
+        }
+    }
+    #[test]
+    fn test_rgb_string_round_trip() {
+        // every 8-bit value survives formatting and parsing
+        for i in 0..=255u8 {
+            let color = RGBColor::from((i, 255 - i, i / 2));
+            let formatted = format!("{}", color);
+            assert_eq!(formatted, color.to_string());
+            let parsed: RGBColor = formatted.parse().unwrap();
+            assert_eq!(parsed.int_rgb_tup(), color.int_rgb_tup());
+            let lower: RGBColor = formatted.to_lowercase().parse().unwrap();
+            assert_eq!(lower.int_rgb_tup(), color.int_rgb_tup());
+        }
+        // three-digit shorthand doubles each digit, with or without #
+        for (short, long) in [("#f00", "#FF0000"), ("#1aF", "#11AAFF"), ("abc", "#AABBCC")].iter() {
+            let parsed: RGBColor = short.parse().unwrap();
+            assert_eq!(parsed.to_string(), *long);
+        }
+        for bad in ["", "#", "#ff", "#ff00", "#gg0000", "#ff00000"].iter() {
+            assert!(bad.parse::<RGBColor>().is_err());
+        }
+    }
+    #[test]
+    fn test_convert_slice() {
+        let illuminants = [
+            Illuminant::D50,
+            Illuminant::D65,
+            Illuminant::D75,
+            Illuminant::Custom([0.9, 1.0, 0.7]),
+        ];
+        // several runs of each illuminant, so the cached matrix gets both reused and replaced
+        let xyzs: Vec<XYZColor> = (0..1000)
+            .map(|i| XYZColor {
+                x: (i as f64 * 0.37).fract() * 0.95,
+                y: (i as f64 * 0.61).fract(),
+                z: (i as f64 * 0.83).fract() * 1.08,
+                illuminant: illuminants[(i / 7) % 4],
+            })
+            .collect();
+        let fast = RGBColor::convert_slice(&xyzs);
+        assert_eq!(fast.len(), xyzs.len());
+        for (xyz, rgb) in xyzs.iter().zip(fast.iter()) {
+            let slow: RGBColor = xyz.convert();
+            assert_eq!(slow.int_rgb_tup(), rgb.int_rgb_tup());
+            assert!((slow.r - rgb.r).abs() <= 1e-12);
+            assert!((slow.g - rgb.g).abs() <= 1e-12);
+            assert!((slow.b - rgb.b).abs() <= 1e-12);
+        }
+        assert!(RGBColor::convert_slice(&[]).is_empty());
+    }
+    #[test]
+    fn test_from_temperature() {
+        let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+        // D65 was defined to approximate a 6500 K blackbody
+        let daylight = RGBColor::from_temperature(6500.);
+        // D65 sits slightly off the Planckian locus, so the match isn't exact
+        assert!(daylight.distance(&white) < 6.);
+        assert!(daylight.r.min(daylight.g).min(daylight.b) > 0.95);
+        // 2000 K is distinctly orange
+        let warm = RGBColor::from_temperature(2000.);
+        assert_eq!(warm.int_r(), 255);
+        assert!((30. ..=70.).contains(&warm.hue()));
+        assert!(warm.chroma() > 50.);
+        // colors get steadily cooler across the whole range
+        let mut last = RGBColor::from_temperature(1000.);
+        for k in (1..=40).map(|i| i as f64 * 1000.) {
+            let rgb = RGBColor::from_temperature(k);
+            for c in [rgb.r, rgb.g, rgb.b].iter() {
+                assert!((0.0..=1.0).contains(c));
+            }
+            assert!((rgb.r.max(rgb.g).max(rgb.b) - 1.).abs() <= 1e-10);
+            assert!(rgb.b / rgb.r >= last.b / last.r - 1e-10);
+            last = rgb;
+        }
+        assert!(last.b > last.r);
+        // and clamp outside it
+        assert_eq!(
+            RGBColor::from_temperature(500.),
+            RGBColor::from_temperature(1000.)
+        );
+        assert_eq!(
+            RGBColor::from_temperature(1e6),
+            RGBColor::from_temperature(40000.)
+        );
+    }
+    #[test]
+    fn test_correlated_color_temperature() {
+        let d65 = XYZColor::white_point(Illuminant::D65).correlated_color_temperature();
+        assert!((d65 - 6504.).abs() <= 10.);
+        let d75 = XYZColor::white_point(Illuminant::D75).correlated_color_temperature();
+        assert!((d75 - 7504.).abs() <= 10.);
+        // brightness doesn't matter, only chromaticity
+        let dim = XYZColor {
+            x: 0.2 * 0.95047,
+            y: 0.2,
+            z: 0.2 * 1.08883,
+            illuminant: Illuminant::D65,
+        };
+        assert!((dim.correlated_color_temperature() - d65).abs() <= 1.);
+        // undoes from_temperature over the range where both approximations are good
+        for k in [2500., 3000., 4000., 5000., 6500., 8000., 10000.].iter() {
+            let xyz = RGBColor::from_temperature(*k).to_xyz(Illuminant::D65);
+            assert!((xyz.correlated_color_temperature() - k).abs() <= k * 0.02);
+        }
+        let black = XYZColor {
+            x: 0.,
+            y: 0.,
+            z: 0.,
+            illuminant: Illuminant::D65,
+        };
+        assert!(black.correlated_color_temperature().is_nan());
+    }
+    #[test]
+    fn test_adaptation_methods() {
+        // published D65 to D50 matrices, from Bruce Lindbloom's tables
+        let expected = [
+            (
+                AdaptationMethod::Bradford,
+                [
+                    [1.0478112, 0.0228866, -0.0501270],
+                    [0.0295424, 0.9904844, -0.0170491],
+                    [-0.0092345, 0.0150436, 0.7521316],
+                ],
+            ),
+            (
+                AdaptationMethod::VonKries,
+                [
+                    [1.0160803, 0.0552297, -0.0521326],
+                    [0.0060666, 0.9955661, -0.0012235],
+                    [0.0000000, 0.0000000, 0.7578869],
+                ],
+            ),
+            (
+                AdaptationMethod::XYZScaling,
+                [
+                    [1.0144665, 0.0000000, 0.0000000],
+                    [0.0000000, 1.0000000, 0.0000000],
+                    [0.0000000, 0.0000000, 0.7578869],
+                ],
+            ),
+        ];
+        for (method, matrix) in expected.iter() {
+            // adapting each axis gives one column of the matrix
+            for col in 0..3 {
+                let mut coords = [0.; 3];
+                coords[col] = 1.;
+                let adapted = XYZColor {
+                    x: coords[0],
+                    y: coords[1],
+                    z: coords[2],
+                    illuminant: Illuminant::D65,
+                }
+                .adapt(Illuminant::D50, *method);
+                assert_eq!(adapted.illuminant, Illuminant::D50);
+                // Scarlet's D65 differs from Lindbloom's in the last digit of Z
+                assert!((adapted.x - matrix[0][col]).abs() <= 1e-4);
+                assert!((adapted.y - matrix[1][col]).abs() <= 1e-4);
+                assert!((adapted.z - matrix[2][col]).abs() <= 1e-4);
+            }
+        }
+        // every method maps white to white and can be undone
+        let color = RGBColor::from_hex_code("#3a7fd0")
+            .unwrap()
+            .to_xyz(Illuminant::D65);
+        for method in [
+            AdaptationMethod::Bradford,
+            AdaptationMethod::VonKries,
+            AdaptationMethod::CAT02,
+            AdaptationMethod::XYZScaling,
+        ]
+        .iter()
+        {
+            let white = XYZColor::white_point(Illuminant::D65).adapt(Illuminant::D50, *method);
+            let d50 = XYZColor::white_point(Illuminant::D50);
+            assert!((white.x - d50.x).abs() <= 1e-12);
+            assert!((white.y - d50.y).abs() <= 1e-12);
+            assert!((white.z - d50.z).abs() <= 1e-12);
+            let back = color
+                .adapt(Illuminant::D50, *method)
+                .adapt(Illuminant::D65, *method);
+            assert!((back.x - color.x).abs() <= 1e-12);
+            assert!((back.y - color.y).abs() <= 1e-12);
+            assert!((back.z - color.z).abs() <= 1e-12);
+        }
+    }
+    #[test]
+    fn test_readable_on_both() {
+        let white = RGBColor::from_hex_code("#FFFFFF").unwrap();
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        for hue in [0., 60., 140., 200., 280.].iter() {
+            let color = RGBColor::readable_on_both(white, black, *hue);
+            // both ratios meet AA, and they're balanced close to the best possible, sqrt(21)
+            assert!(color.meets_aa(&white, false));
+            assert!(color.meets_aa(&black, false));
+            let worst = color
+                .contrast_ratio(&white)
+                .min(color.contrast_ratio(&black));
+            assert!((worst - 21f64.sqrt()).abs() <= 0.05);
+            // and it has the hue that was asked for
+            let lch: CIELCHColor = color.convert();
+            assert!(lch.c > 10.);
+            let diff = (lch.h - hue).rem_euclid(360.);
+            assert!(diff.min(360. - diff) <= 1e-6);
+        }
+        // with backgrounds closer together, it lands between them
+        let light = RGBColor::from_hex_code("#F0E8D8").unwrap();
+        let dark = RGBColor::from_hex_code("#203040").unwrap();
+        let color = RGBColor::readable_on_both(light, dark, 30.);
+        assert!(color.lightness() < light.lightness());
+        assert!(color.lightness() > dark.lightness());
+        assert!((color.contrast_ratio(&light) - color.contrast_ratio(&dark)).abs() <= 0.1);
+    }
+    #[test]
+    fn test_is_in_gamut() {
+        for code in ["#000000", "#FFFFFF", "#FF0000", "#2266AA", "#00FF00"].iter() {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            assert!(rgb.is_in_gamut());
+            // round trips pick up some floating-point error, but stay in gamut
+            let lab: CIELABColor = rgb.convert();
+            let back: RGBColor = lab.convert();
+            assert!(back.is_in_gamut());
+        }
+        // very saturated CIELAB colors are outside of sRGB, and the components show it
+        let cyan: RGBColor = CIELABColor {
+            l: 90.,
+            a: -60.,
+            b: -30.,
+        }
+        .convert();
+        assert!(!cyan.is_in_gamut());
+        assert!(cyan.r < 0.);
+        let red: RGBColor = CIELABColor {
+            l: 50.,
+            a: 110.,
+            b: 70.,
+        }
+        .convert();
+        assert!(!red.is_in_gamut());
+        assert!(red.r > 1.);
+        // but quantization hides that
+        assert_eq!(red.int_r(), 255);
+        assert!(!RGBColor {
+            r: 0.5,
+            g: -0.01,
+            b: 0.5
+        }
+        .is_in_gamut());
+    }
+    #[test]
+    fn test_from_wavelength() {
+        let red = RGBColor::from_wavelength(650.);
+        assert_eq!(red.to_string(), "#FF0000");
+        let green = RGBColor::from_wavelength(510.);
+        assert_eq!(green.to_string(), "#00FF00");
+        let blue = RGBColor::from_wavelength(470.);
+        assert_eq!(blue.int_b(), 255);
+        assert!(blue.b > blue.g && blue.r == 0.);
+        assert!((200. ..=280.).contains(&blue.hue()));
+        // invisible wavelengths are black
+        for nm in [300., 379.9, 750.1, 1000.].iter() {
+            assert_eq!(RGBColor::from_wavelength(*nm).to_string(), "#000000");
+        }
+        // the edges of the spectrum are dim, but not black
+        for nm in [380., 400., 730., 750.].iter() {
+            let edge = RGBColor::from_wavelength(*nm);
+            assert!(edge.r.max(edge.b) < 1.);
+            assert!(edge.r.max(edge.b) > 0.);
+        }
+        // the colors change continuously across the spectrum
+        for i in 380..750 {
+            let a = RGBColor::from_wavelength(i as f64);
+            let b = RGBColor::from_wavelength(i as f64 + 1.);
+            assert!(a.distance(&b) < 10.);
+        }
+    }
+    #[test]
+    fn test_clip_to_gamut() {
+        // a cyan brighter and more saturated than sRGB can show
+        let cyan = CIELCHColor {
+            l: 85.,
+            c: 70.,
+            h: 200.,
+        };
+        assert!(!cyan.convert::<RGBColor>().is_in_gamut());
+        let reduced = RGBColor::clip_to_gamut(cyan, GamutStrategy::ReduceChroma);
+        assert!(reduced.is_in_gamut());
+        let lch: CIELCHColor = reduced.convert();
+        assert!((lch.h - 200.).abs() <= 1e-6);
+        assert!((lch.l - 85.).abs() <= 1e-6);
+        assert!(lch.c < 70.);
+        // clamping doesn't keep the hue as well
+        let clamped = RGBColor::clip_to_gamut(cyan, GamutStrategy::ClampRGB);
+        assert!(clamped.is_in_gamut());
+        assert!((clamped.convert::<CIELCHColor>().h - 200.).abs() > 1.);
+        // colors in the gamut are left alone
+        let teal = RGBColor::from_hex_code("#2a7ab0").unwrap();
+        for strategy in [GamutStrategy::ClampRGB, GamutStrategy::ReduceChroma].iter() {
+            assert!(RGBColor::clip_to_gamut(teal, *strategy).distance(&teal) <= 1e-8);
+        }
+        // something brighter than white has to be clamped too
+        let glare = CIELCHColor {
+            l: 120.,
+            c: 10.,
+            h: 90.,
+        };
+        let white = RGBColor::clip_to_gamut(glare, GamutStrategy::ReduceChroma);
+        assert_eq!(white.to_string(), "#FFFFFF");
+    }
+    #[test]
+    fn test_blend() {
+        let gray = RGBColor {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+        };
+        let channel = |color: RGBColor| {
+            assert_eq!(color.r, color.g);
+            assert_eq!(color.g, color.b);
+            color.r
+        };
+        // 50% gray with itself
+        let expected = [
+            (BlendMode::Multiply, 0.25),
+            (BlendMode::Screen, 0.75),
+            (BlendMode::Overlay, 0.5),
+            (BlendMode::Darken, 0.5),
+            (BlendMode::Lighten, 0.5),
+            (BlendMode::ColorDodge, 1.0),
+            (BlendMode::ColorBurn, 0.0),
+            (BlendMode::HardLight, 0.5),
+            (BlendMode::SoftLight, 0.5),
+            (BlendMode::Difference, 0.0),
+        ];
+        for (mode, val) in expected.iter() {
+            assert!((channel(gray.blend(&gray, *mode)) - val).abs() <= 1e-10);
+        }
+        // a dark backdrop under a light source, worked out by hand from the W3C definitions
+        let dark = RGBColor {
+            r: 0.2,
+            g: 0.2,
+            b: 0.2,
+        };
+        let light = RGBColor {
+            r: 0.8,
+            g: 0.8,
+            b: 0.8,
+        };
+        let expected = [
+            (BlendMode::Multiply, 0.16),
+            (BlendMode::Screen, 0.84),
+            (BlendMode::Overlay, 0.32),
+            (BlendMode::ColorDodge, 1.0),
+            (BlendMode::ColorBurn, 0.0),
+            (BlendMode::HardLight, 0.68),
+            (
+                BlendMode::SoftLight,
+                0.2 + 0.6 * (((16. * 0.2 - 12.) * 0.2 + 4.) * 0.2 - 0.2),
+            ),
+            (BlendMode::Difference, 0.6),
+        ];
+        for (mode, val) in expected.iter() {
+            assert!((channel(dark.blend(&light, *mode)) - val).abs() <= 1e-10);
+        }
+        // identities: black and white are neutral for screen and multiply
+        let color = RGBColor::from_hex_code("#3a7fd0").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        assert_eq!(color.blend(&white, BlendMode::Multiply), color);
+        assert_eq!(color.blend(&black, BlendMode::Screen), color);
+        assert_eq!(color.blend(&gray, BlendMode::SoftLight), color);
+        assert_eq!(color.blend(&color, BlendMode::Difference), black);
+        // inputs outside of the gamut are clamped first
+        let bright = RGBColor {
+            r: 1.5,
+            g: 1.5,
+            b: 1.5,
+        };
+        assert_eq!(color.blend(&bright, BlendMode::Multiply), color);
+    }
+    #[test]
+    fn test_next_distinct() {
+        let start = RGBColor::from_hex_code("#203040").unwrap();
+        let mut scale = vec![start];
+        while scale[scale.len() - 1].lightness() < 100. - 1e-6 {
+            let next = scale[scale.len() - 1].next_distinct(1., 1.);
+            scale.push(next);
+        }
+        assert!(scale.len() > 20);
+        for (i, pair) in scale.windows(2).enumerate() {
+            assert!(pair[1].lightness() > pair[0].lightness());
+            let dist = pair[0].distance(&pair[1]);
+            if i + 2 < scale.len() {
+                // each step is just noticeable
+                assert!(dist >= 1. - 1e-9);
+                assert!(dist <= 1. + 1e-6);
+            } else {
+                // except maybe the last, which stops at white
+                assert!(dist <= 1. + 1e-6);
+            }
+        }
+        // going darker works the same way, and chromaticity is kept
+        let lab: CIELABColor = start.convert();
+        let darker: CIELABColor = lab.next_distinct(-1., 3.);
+        assert!(darker.l < lab.l);
+        assert!((darker.distance(&lab) - 3.).abs() <= 1e-6);
+        assert_eq!((darker.a, darker.b), (lab.a, lab.b));
+        // no direction means no change
+        assert_eq!(lab.next_distinct(0., 3.).l, lab.l);
+    }
+    #[test]
+    fn test_lighten_darken() {
+        for code in ["#2A7AB0", "#808080", "#C04020", "#466223"].iter() {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            let l = rgb.lightness();
+            let lighter = rgb.lighten(15.);
+            assert!((lighter.lightness() - (l + 15.)).abs() <= 1e-8);
+            let darker = rgb.darken(15.);
+            assert!((darker.lightness() - (l - 15.)).abs() <= 1e-8);
+            // they undo each other
+            assert!(lighter.darken(15.).distance(&rgb) <= 1e-8);
+            assert!(darker.lighten(15.).distance(&rgb) <= 1e-8);
+            assert_eq!(lighter.darken(15.).to_string(), String::from(*code));
+            // lightness is clamped
+            assert!((rgb.lighten(500.).lightness() - 100.).abs() <= 1e-8);
+            assert!(rgb.darken(500.).lightness().abs() <= 1e-8);
+            assert!((rgb.with_lightness(-10.).lightness()).abs() <= 1e-8);
+            assert!((rgb.with_lightness(42.).lightness() - 42.).abs() <= 1e-8);
+        }
+    }
+    #[test]
+    fn test_saturate_desaturate() {
+        for code in ["#2A7AB0", "#C04020", "#466223", "#7A8A9A"].iter() {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            let lch: CIELCHColor = rgb.convert();
+            // desaturating all the way gives a neutral gray of the same lightness
+            let gray = rgb.desaturate(1.);
+            assert!(gray.chroma() <= 1e-6);
+            assert!((gray.lightness() - lch.l).abs() <= 1e-6);
+            assert_eq!(gray.to_string(), rgb.grayscale().to_string());
+            // partial changes keep lightness and hue
+            let half = rgb.desaturate(0.5);
+            assert!((half.chroma() - lch.c / 2.).abs() <= 1e-6);
+            for color in [half, rgb.saturate(0.2), rgb.saturate(5.)].iter() {
+                let new: CIELCHColor = color.convert();
+                assert!((new.l - lch.l).abs() <= 1e-6);
+                assert!((new.h - lch.h).abs() <= 1e-6);
+                assert!(color.is_in_gamut());
+            }
+            // saturating a lot hits the edge of the gamut
+            assert!(rgb.saturate(5.).chroma() < lch.c * 6.);
+            assert!(rgb.desaturate(2.).chroma() <= 1e-6);
+        }
+        // grays stay gray
+        for code in ["#000000", "#777777", "#FFFFFF"].iter() {
+            let gray = RGBColor::from_hex_code(code).unwrap();
+            assert_eq!(gray.saturate(3.).to_string(), String::from(*code));
+        }
+    }
+    #[test]
+    fn test_tints_shades_tones() {
+        let base = RGBColor::from_hex_code("#2a7ab0").unwrap();
+        let tints = base.tints(3);
+        assert_eq!(tints.len(), 3);
+        assert_eq!(tints[2].to_string(), "#FFFFFF");
+        let shades = base.shades(3);
+        assert_eq!(shades[2].to_string(), "#000000");
+        // lightness moves evenly toward the end, and the hue doesn't move
+        for (i, (tint, shade)) in tints.iter().zip(shades.iter()).take(2).enumerate() {
+            let t = (i + 1) as f64 / 3.0;
+            assert!(
+                (tint.lightness() - (base.lightness() + t * (100. - base.lightness()))).abs()
+                    <= 0.5
+            );
+            assert!((shade.lightness() - (1. - t) * base.lightness()).abs() <= 0.5);
+            assert!((tint.hue() - base.hue()).abs() <= 1.0);
+            assert!((shade.hue() - base.hue()).abs() <= 1.0);
+        }
+        // tones keep lightness and lose chroma
+        let tones = base.tones(4);
+        let mut last_chroma = base.chroma();
+        for tone in &tones {
+            assert!((tone.lightness() - base.lightness()).abs() <= 0.5);
+            assert!(tone.chroma() < last_chroma);
+            last_chroma = tone.chroma();
+        }
+        assert!(tones[3].chroma() <= 0.5);
+        // pure blue stays blue on the way to white
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        for tint in blue.tints(4).iter().take(3) {
+            assert!(tint.is_in_gamut());
+            assert!((tint.hue() - blue.hue()).abs() <= 1.0);
+        }
+        assert!(base.tints(0).is_empty());
+    }
+    #[test]
+    fn test_shift_temperature() {
+        let gray = RGBColor::from_hex_code("#808080").unwrap();
+        let ratio = |c: RGBColor| c.r / c.b;
+        // warmer and warmer
+        let mut last = ratio(gray);
+        for &delta in [500., 1000., 2000., 3000.].iter() {
+            let warm = gray.shift_temperature(delta);
+            assert!(ratio(warm) > last);
+            last = ratio(warm);
+        }
+        // and cooler
+        let cool = gray.shift_temperature(-2000.);
+        assert!(ratio(cool) < ratio(gray));
+        // no change is no change
+        for code in ["#808080", "#3a7fd0", "#ff0000"].iter() {
+            let color = RGBColor::from_hex_code(code).unwrap();
+            assert!(color.distance(&color.shift_temperature(0.)) <= 1e-10);
+        }
+        // white takes on the color of the light, up to brightness
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let warm_white = white.shift_temperature(3500.);
+        let light = RGBColor::from_temperature(3000.);
+        assert!((warm_white.g / warm_white.r - light.g / light.r).abs() <= 0.02);
+        assert!((warm_white.b / warm_white.r - light.b / light.r).abs() <= 0.02);
+        // the shift clamps like from_temperature
+        assert!(
+            gray.shift_temperature(6000.)
+                .distance(&gray.shift_temperature(10000.))
+                <= 1e-10
+        );
+    }
+    #[test]
+    fn test_to_luminance_gray() {
+        let green = RGBColor::from_hex_code("#00ff00")
+            .unwrap()
+            .to_luminance_gray();
+        let blue = RGBColor::from_hex_code("#0000ff")
+            .unwrap()
+            .to_luminance_gray();
+        // green looks far brighter than blue
+        assert!(green.r > blue.r);
+        assert!((green.relative_luminance() - 0.7152).abs() <= 1e-12);
+        assert!((blue.relative_luminance() - 0.0722).abs() <= 1e-12);
+        for gray in [green, blue].iter() {
+            assert_eq!(gray.r, gray.g);
+            assert_eq!(gray.g, gray.b);
+        }
+        // grays stay the same
+        for code in ["#000000", "#808080", "#ffffff"].iter() {
+            let gray = RGBColor::from_hex_code(code).unwrap();
+            assert_eq!(gray.to_luminance_gray().to_string(), gray.to_string());
+        }
+        // the CIELAB grayscale is close, but doesn't keep WCAG luminance
+        let lab_blue: RGBColor = RGBColor::from_hex_code("#0000ff").unwrap().grayscale();
+        assert!((lab_blue.relative_luminance() - 0.0722).abs() > 0.005);
+        assert!((lab_blue.r - blue.r).abs() <= 0.05);
+    }
+    #[test]
+    fn test_dither_floyd_steinberg() {
+        let palette: Vec<RGBColor> = ["#000000", "#ffffff", "#ff0000", "#0000ff"]
+            .iter()
+            .map(|x| RGBColor::from_hex_code(x).unwrap())
+            .collect();
+        // a horizontal gradient from black to a purple the palette can mix, 32 rows of 32
+        let width = 32;
+        let end = RGBColor::from_hex_code("#a060c0").unwrap();
+        let black = palette[0];
+        let image: Vec<RGBColor> = (0..32 * width)
+            .map(|i| {
+                let lin = LinearRGBColor::from_srgb(black)
+                    .mix(LinearRGBColor::from_srgb(end), (i % width) as f64 / 31.);
+                lin.to_srgb()
+            })
+            .collect();
+        let mut dithered = image.clone();
+        dither_floyd_steinberg(&mut dithered, width, &palette);
+        let codes: Vec<String> = palette.iter().map(|c| c.to_string()).collect();
+        for pixel in dithered.iter() {
+            assert!(codes.contains(&pixel.to_string()));
+        }
+        // the average amount of light is about the same
+        let linear_mean = |colors: &[RGBColor]| {
+            let n = colors.len() as f64;
+            colors
+                .iter()
+                .map(|&c| LinearRGBColor::from_srgb(c))
+                .fold([0.; 3], |acc, c| {
+                    [acc[0] + c.r / n, acc[1] + c.g / n, acc[2] + c.b / n]
+                })
+        };
+        let before = linear_mean(&image);
+        let after = linear_mean(&dithered);
+        for c in 0..3 {
+            assert!((before[c] - after[c]).abs() <= 0.02);
+        }
+        // colors already in the palette are left alone
+        let mut exact = palette.clone();
+        dither_floyd_steinberg(&mut exact, 2, &palette);
+        for (a, b) in exact.iter().zip(palette.iter()) {
+            assert_eq!(a.to_string(), b.to_string());
+        }
+        dither_floyd_steinberg(&mut [], 3, &palette);
+    }
+    #[test]
+    fn test_gray_world_balance() {
+        // a varied scene with a blue cast
+        let scene: Vec<RGBColor> = ["#5070a0", "#a0b0d0", "#304870", "#8090c0", "#c0d0f0"]
+            .iter()
+            .map(|x| RGBColor::from_hex_code(x).unwrap())
+            .collect();
+        let linear_mean = |colors: &[RGBColor]| {
+            let xyz = colors
+                .iter()
+                .map(|c| c.to_xyz(Illuminant::D65))
+                .fold([0.; 3], |acc, c| [acc[0] + c.x, acc[1] + c.y, acc[2] + c.z]);
+            let n = colors.len() as f64;
+            RGBColor::from_xyz(XYZColor {
+                x: xyz[0] / n,
+                y: xyz[1] / n,
+                z: xyz[2] / n,
+                illuminant: Illuminant::D65,
+            })
+        };
+        let before = linear_mean(&scene);
+        assert!(before.chroma() > 10.);
+        let balanced = gray_world_balance(&scene);
+        assert_eq!(balanced.len(), scene.len());
+        let after = linear_mean(&balanced);
+        // the average is now neutral, and about as bright as before
+        assert!(after.chroma() < 0.5);
+        assert!((after.lightness() - before.lightness()).abs() < 0.5);
+        // a scene that is already neutral is left alone
+        let grays: Vec<RGBColor> = ["#202020", "#808080", "#d0d0d0"]
+            .iter()
+            .map(|x| RGBColor::from_hex_code(x).unwrap())
+            .collect();
+        for (old, new) in grays.iter().zip(gray_world_balance(&grays).iter()) {
+            assert!(old.distance(new) <= 1e-8);
+        }
+        assert!(gray_world_balance(&[]).is_empty());
+    }
+    #[test]
+    fn test_rotate_hue() {
+        let circular_diff = |a: f64, b: f64| {
+            let diff = (a - b).rem_euclid(360.);
+            diff.min(360. - diff)
+        };
+        for code in ["#2A7AB0", "#C04020", "#466223", "#7A8A9A"].iter() {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            // three thirds of a turn is a full turn
+            let around = rgb.rotate_hue(120.).rotate_hue(120.).rotate_hue(120.);
+            assert!(around.distance(&rgb) <= 1e-8);
+            assert_eq!(around.to_string(), String::from(*code));
+            // rotations compose, in both directions
+            assert!(rgb.rotate_hue(50.).rotate_hue(-50.).distance(&rgb) <= 1e-8);
+            assert!(rgb.rotate_hue(30.).distance(&rgb.rotate_hue(-330.)) <= 1e-8);
+            assert!(rgb.rotate_hue(720.).distance(&rgb) <= 1e-8);
+            // the hue always wraps into 0-360
+            for deg in [-400., -90., 200., 350., 1000.].iter() {
+                let h = rgb.rotate_hue(*deg).convert::<CIELCHColor>().h;
+                assert!((0. ..360.).contains(&h));
+                assert!(circular_diff(h, rgb.hue() + deg) <= 1e-8);
+            }
+            // complementary colors are opposite and in gamut
+            let comp = rgb.complementary();
+            assert!((circular_diff(comp.hue(), rgb.hue()) - 180.).abs() <= 1e-6);
+            assert!(comp.is_in_gamut());
+            assert!(comp.chroma() <= rgb.chroma() + 1e-8);
+            // if no chroma had to be taken away, twice is the identity
+            if rgb.rotate_hue(180.).is_in_gamut() {
+                assert!((comp.chroma() - rgb.chroma()).abs() <= 1e-8);
+                assert!(comp.complementary().distance(&rgb) <= 1e-8);
+            }
+        }
+    }
+    #[test]
+    fn test_color_schemes() {
+        let circular_diff = |a: f64, b: f64| {
+            let diff = (a - b).rem_euclid(360.);
+            diff.min(360. - diff)
+        };
+        for code in ["#2A7AB0", "#FF0000", "#00FF00", "#466223", "#FFCC00"].iter() {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            let triadic = rgb.triadic();
+            // the hues are evenly spaced, even when chroma has to be reduced
+            for pair in [(0, 1), (1, 2), (2, 0)].iter() {
+                let diff = circular_diff(triadic[pair.0].hue(), triadic[pair.1].hue());
+                assert!((diff - 120.).abs() <= 1e-6);
+            }
+            let split = rgb.split_complementary();
+            assert!((circular_diff(split[1].hue(), rgb.hue()) - 150.).abs() <= 1e-6);
+            assert!((circular_diff(split[2].hue(), rgb.hue()) - 150.).abs() <= 1e-6);
+            let tetradic = rgb.tetradic();
+            for i in 0..4 {
+                let diff = circular_diff(tetradic[i].hue(), tetradic[(i + 1) % 4].hue());
+                assert!((diff - 90.).abs() <= 1e-6);
+            }
+            let analogous = rgb.analogous(90., 4);
+            assert!((circular_diff(analogous[0].hue(), rgb.hue()) - 45.).abs() <= 1e-6);
+            assert!((circular_diff(analogous[1].hue(), rgb.hue()) - 15.).abs() <= 1e-6);
+            assert!((circular_diff(analogous[3].hue(), rgb.hue()) - 45.).abs() <= 1e-6);
+            // everything is displayable and keeps the same lightness
+            let comp = [rgb.complementary()];
+            assert!((circular_diff(comp[0].hue(), rgb.hue()) - 180.).abs() <= 1e-6);
+            let all = triadic
+                .iter()
+                .chain(comp.iter())
+                .chain(split.iter())
+                .chain(tetradic.iter())
+                .chain(analogous.iter());
+            for color in all {
+                assert!(color.is_in_gamut());
+                assert!((color.lightness() - rgb.lightness()).abs() <= 1e-4);
+            }
+        }
+        let rgb = RGBColor::from_hex_code("#2A7AB0").unwrap();
+        assert!(rgb.analogous(30., 0).is_empty());
+        assert_eq!(rgb.analogous(30., 1)[0], rgb);
+        assert_eq!(rgb.analogous(30., 3)[1], rgb);
+    }
+    #[test]
+    fn test_distinct_palette() {
+        let min_pairwise = |palette: &[RGBColor]| {
+            let matrix = similarity_matrix(palette);
+            let mut min = f64::INFINITY;
+            for (i, row) in matrix.iter().enumerate() {
+                for dist in row.iter().skip(i + 1) {
+                    min = min.min(*dist);
+                }
+            }
+            min
+        };
+        let palette = distinct_palette(8, None);
+        assert_eq!(palette.len(), 8);
+        assert!(min_pairwise(&palette) > 25.);
+        assert!(palette.iter().all(|c| c.is_in_gamut()));
+        // with a background, the colors avoid it too
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let on_white = distinct_palette(8, Some(white));
+        assert!(min_pairwise(&on_white) > 25.);
+        assert!(on_white.iter().all(|c| c.distance(&white) > 25.));
+        // asking for more colors keeps the first ones the same, and gets less distinct
+        let more = distinct_palette(16, None);
+        for (a, b) in palette.iter().zip(more.iter()) {
+            assert_eq!(a, b);
+        }
+        assert!(min_pairwise(&more) <= min_pairwise(&palette));
+        assert!(distinct_palette(0, None).is_empty());
+    }
+    #[test]
+    fn test_simulate_cvd() {
+        let kinds = [
+            CvdKind::Protanopia,
+            CvdKind::Deuteranopia,
+            CvdKind::Tritanopia,
+        ];
+        for code in ["#FF0000", "#2266AA", "#FAFA22", "#466223", "#FFFFFF"].iter() {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            for kind in kinds.iter() {
+                // normal vision sees the color as it is
+                assert!(rgb.simulate_cvd(*kind, 0.0).distance(&rgb) <= 1e-10);
+                // partial deficiency is in between normal vision and dichromacy
+                let full = rgb.simulate_cvd(*kind, 1.0);
+                let half = rgb.simulate_cvd(*kind, 0.5);
+                assert!(half.distance(&rgb) <= full.distance(&rgb) + 1e-10);
+                assert_eq!(rgb.simulate_cvd(*kind, 2.0).to_string(), full.to_string());
+            }
+        }
+        // white and gray are unaffected by any deficiency
+        let gray = RGBColor::from_hex_code("#777777").unwrap();
+        for kind in kinds.iter() {
+            assert_eq!(gray.simulate_cvd(*kind, 1.0).to_string(), "#777777");
+        }
+        // red and green collapse together under deuteranopia, more so as it gets more severe
+        let red = RGBColor::from_hex_code("#DD2222").unwrap();
+        let green = RGBColor::from_hex_code("#22AA22").unwrap();
+        let mut last = red.distance(&green);
+        for severity in [0.25, 0.5, 0.75, 1.0].iter() {
+            let red_seen = red.simulate_cvd(CvdKind::Deuteranopia, *severity);
+            let green_seen = green.simulate_cvd(CvdKind::Deuteranopia, *severity);
+            let dist = red_seen.distance(&green_seen);
+            assert!(dist < last);
+            last = dist;
+        }
+        assert!(last < red.distance(&green) / 2.);
+        // but tritanopes can still tell them apart easily
+        let red_seen = red.simulate_cvd(CvdKind::Tritanopia, 1.0);
+        let green_seen = green.simulate_cvd(CvdKind::Tritanopia, 1.0);
+        assert!(red_seen.distance(&green_seen) > red.distance(&green) / 2.);
+    }
+    #[test]
     #[ignore]
     fn color_scheme() {
         let mut colors: Vec<RGBColor> = vec![];
@@ -1760,7 +4673,7 @@ mod tests {
         }
         println!();
         for color in colors {
-            println!("{}", color.to_string());
+            println!("{}", color);
         }
     }
 }
@@ -3,17 +3,43 @@
 //! provides some common ones used in programs like MATLAB and in data
 //! visualization everywhere.
 
-use color::{Color, RGBColor};
+use color::{srgb_max_chroma, Color, RGBColor, RGBParseError};
 use colorpoint::ColorPoint;
+use colors::cielabcolor::CIELABColor;
+use colors::cielchcolor::CIELCHColor;
+use colors::cmykcolor::CMYKColor;
+use colors::linearrgbcolor::{linear_to_srgb, srgb_to_linear};
 use coord::Coord;
+use csv;
 use matplotlib_cmaps;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use std::any::TypeId;
+use std::error::Error;
+use std::fmt;
+use std::io;
 use std::iter::Iterator;
+use std::marker::PhantomData;
 
 /// A trait that models a colormap, a continuous mapping of the numbers between 0 and 1 to
 /// colors. Any color output format is supported, but it must be consistent.
 pub trait ColorMap<T: Color + Sized> {
     /// Maps a given number between 0 and 1 to a given output `Color`. This should never fail or panic
-    /// except for NaN and similar: there should be some Color that marks out-of-range data.
+    /// except for NaN and similar: there should be some Color that marks out-of-range data. Most
+    /// colormaps clamp out-of-range data to the nearest end: wrap them in [`OverUnderColorMap`] to
+    /// mark it with a different color instead.
+    ///
+    /// NaN marks missing data, and it fails every comparison, so it has to be handled explicitly.
+    /// The colormaps in this module that compute colors themselves treat NaN exactly like 0, giving
+    /// the color at the start of the colormap. Those that wrap another colormap, like
+    /// [`ReversedColorMap`] or [`DomainColorMap`], pass NaN through to it unchanged, so that an
+    /// [`OverUnderColorMap`] anywhere inside still gives its `bad` color. New implementations should
+    /// do the same.
+    ///
+    /// [`ReversedColorMap`]: struct.ReversedColorMap.html
+    /// [`DomainColorMap`]: struct.DomainColorMap.html
+    ///
+    /// [`OverUnderColorMap`]: struct.OverUnderColorMap.html
     fn transform_single(&self, color: f64) -> T;
     /// Maps a given collection of numbers between 0 and 1 to an iterator of `Color`s. Does not evaluate
     /// lazily, because the colormap could have some sort of state that changes between iterations otherwise.
@@ -24,11 +50,452 @@ pub trait ColorMap<T: Color + Sized> {
             .map(|x| self.transform_single(x))
             .collect()
     }
+    /// Maps a given collection of numbers between 0 and 1 to `Color`s lazily, without collecting
+    /// them: each color is computed only when the returned iterator reaches it. This is only safe
+    /// for stateless colormaps, like [`GradientColorMap`] and [`ListedColorMap`], whose output for a
+    /// number doesn't depend on what was transformed before it: for colormaps with state, use
+    /// [`ColorMap::transform`] instead. This is useful for streaming large amounts of data through a
+    /// colormap without buffering all of it.
+    ///
+    /// [`GradientColorMap`]: struct.GradientColorMap.html
+    /// [`ListedColorMap`]: struct.ListedColorMap.html
+    /// [`ColorMap::transform`]: #method.transform
+    fn transform_iter<U: IntoIterator<Item = f64>>(&self, inputs: U) -> impl Iterator<Item = T> {
+        inputs.into_iter().map(move |x| self.transform_single(x))
+    }
+    /// Maps a given collection of numbers between 0 and 1 to `Color`s like [`ColorMap::transform`],
+    /// but splits the work across rayon's thread pool, which gives an easy speedup for large inputs
+    /// like the pixels of an image. The output is in the same order as the input and is identical
+    /// to what [`ColorMap::transform`] gives. Like [`ColorMap::transform_iter`], this is only
+    /// correct for stateless colormaps. For small inputs, the cost of splitting up the work
+    /// outweighs the benefit: prefer [`ColorMap::transform`] unless there are many thousands of
+    /// values. Requires the `rayon` feature.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// # #[cfg(feature = "rayon")]
+    /// # {
+    /// let cmap = ListedColorMap::viridis();
+    /// let data: Vec<f64> = (0..100_000).map(|i| (i % 1000) as f64 / 1000.).collect();
+    /// let colors: Vec<RGBColor> = cmap.transform_par(data.clone());
+    /// assert_eq!(colors.len(), data.len());
+    /// # }
+    /// ```
+    ///
+    /// [`ColorMap::transform`]: #method.transform
+    /// [`ColorMap::transform_iter`]: #method.transform_iter
+    #[cfg(feature = "rayon")]
+    fn transform_par<U: IntoParallelIterator<Item = f64>>(&self, inputs: U) -> Vec<T>
+    where
+        Self: Sync,
+        T: Send,
+    {
+        inputs
+            .into_par_iter()
+            .map(|x| self.transform_single(x))
+            .collect()
+    }
+    /// Returns a closure that maps a number to a color just like [`ColorMap::transform_single`],
+    /// for use in tight rendering loops or anywhere that wants a plain function. The closure only
+    /// borrows the colormap, so the colormap can't be moved or mutated while the closure is alive:
+    /// if it needs to outlive the colormap, move the colormap into a closure of your own instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, GradientColorMap};
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// let cmap = GradientColorMap::new_linear(black, white);
+    /// let shade = cmap.as_fn();
+    /// let pixels: Vec<String> = [0., 1.].iter().map(|&x| shade(x).to_string()).collect();
+    /// assert_eq!(pixels, vec!["#000000", "#FFFFFF"]);
+    /// ```
+    ///
+    /// [`ColorMap::transform_single`]: #tymethod.transform_single
+    fn as_fn(&self) -> impl Fn(f64) -> T + '_ {
+        move |x| self.transform_single(x)
+    }
+    /// Samples the colormap at `n` evenly-spaced points, including both 0 and 1: that is, at
+    /// `i / (n - 1)` for `i` from 0 to `n - 1`. If `n` is 1, the single sample is taken at 0.5, and
+    /// if `n` is 0 the result is empty. This is useful for getting a set of discrete colors for
+    /// something like a legend.
+    fn sample(&self, n: usize) -> Vec<T> {
+        self.transform(sample_points(n))
+    }
+    /// Finds the input whose color is closest to `color`, by CIEDE2000: the inverse of
+    /// [`ColorMap::transform_single`], for reading data values back off of colors. Only the inputs
+    /// that [`ColorMap::sample`] would use for `n` samples are checked, so the result is always one
+    /// of them: more samples give a finer answer. An `n` of 0 is treated as 1, which always gives
+    /// 0.5. Ties are resolved in favor of the smallest input.
+    ///
+    /// [`ColorMap::transform_single`]: #tymethod.transform_single
+    /// [`ColorMap::sample`]: #method.sample
+    fn closest_input<U: Color>(&self, color: &U, n: usize) -> f64 {
+        let n = n.max(1);
+        let mut best_x = 0.5;
+        let mut best_dist = f64::INFINITY;
+        for (x, sample) in sample_points(n).into_iter().zip(self.sample(n)) {
+            let dist = sample.distance(color);
+            if dist < best_dist {
+                best_x = x;
+                best_dist = dist;
+            }
+        }
+        best_x
+    }
+    /// Flips the colormap, so that 0 maps to what 1 used to and vice versa, like the `_r` suffix
+    /// in matplotlib. See [`ReversedColorMap`] for more.
+    ///
+    /// [`ReversedColorMap`]: struct.ReversedColorMap.html
+    fn reversed(self) -> ReversedColorMap<Self>
+    where
+        Self: Sized,
+    {
+        ReversedColorMap::new(self)
+    }
+    /// Makes the colormap take raw data values from `vmin` to `vmax` instead of numbers from 0 to 1,
+    /// rescaling them linearly before they're mapped: `vmin` maps to the color for 0 and `vmax` to
+    /// the color for 1. Values outside of that range are clamped to it: see [`Normalizer`] to let
+    /// them extrapolate instead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, GradientColorMap};
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// // temperatures from 10 to 50 degrees
+    /// let cmap = GradientColorMap::new_linear(black, white).with_domain(10., 50.);
+    /// assert_eq!(cmap.transform_single(30.).to_string(), "#808080");
+    /// assert_eq!(cmap.transform_single(60.).to_string(), "#FFFFFF");
+    /// ```
+    ///
+    /// [`Normalizer`]: struct.Normalizer.html
+    fn with_domain(self, vmin: f64, vmax: f64) -> DomainColorMap<Self>
+    where
+        Self: Sized,
+    {
+        DomainColorMap::new(self, Normalizer::new(vmin, vmax))
+    }
+    /// Like [`ColorMap::with_domain`], but rescales logarithmically: see [`LogNormalizer`]. This is
+    /// the right choice for data that spans several orders of magnitude, where a linear scale
+    /// would squash everything but the largest values into the low end of the colormap.
+    /// # Errors
+    /// Returns `ColorMapError::NonPositiveDomain` if `vmin` or `vmax` is not positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, GradientColorMap};
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// let cmap = GradientColorMap::new_linear(black, white).with_log_domain(1., 100.).unwrap();
+    /// assert_eq!(cmap.transform_single(10.).to_string(), "#808080");
+    /// ```
+    ///
+    /// [`ColorMap::with_domain`]: #method.with_domain
+    /// [`LogNormalizer`]: struct.LogNormalizer.html
+    fn with_log_domain(
+        self,
+        vmin: f64,
+        vmax: f64,
+    ) -> Result<DomainColorMap<Self, LogNormalizer>, ColorMapError>
+    where
+        Self: Sized,
+    {
+        Ok(DomainColorMap::new(self, LogNormalizer::new(vmin, vmax)?))
+    }
+    /// Passes every input through the function `f` before it reaches the colormap, clamping the
+    /// result to 0 to 1. This warps the colormap in any way at all, unlike the fixed choices in
+    /// [`NormalizeMapping`], and it works for any colormap. See [`WarpedColorMap`] for more.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, GradientColorMap};
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// // spend more of the colormap on the low end, like a gamma curve
+    /// let cmap = GradientColorMap::new_linear(black, white).with_input_transform(|x| x.sqrt());
+    /// assert_eq!(cmap.transform_single(0.25).to_string(), "#808080");
+    /// ```
+    ///
+    /// [`NormalizeMapping`]: enum.NormalizeMapping.html
+    /// [`WarpedColorMap`]: struct.WarpedColorMap.html
+    fn with_input_transform<F: Fn(f64) -> f64>(self, f: F) -> WarpedColorMap<Self, F>
+    where
+        Self: Sized,
+    {
+        WarpedColorMap::new(self, f)
+    }
+    /// Finds the perceptual center of the colormap: the input between 0 and 1 whose output has the
+    /// lowest CIELCH chroma among `n` samples, taken as by [`ColorMap::sample`]. For a diverging
+    /// colormap, this is the neutral color where data representing zero should be mapped, which
+    /// may not sit exactly at 0.5. If `n` is less than 2, only the endpoints are checked. Ties are
+    /// resolved in favor of the smallest input.
+    ///
+    /// [`ColorMap::sample`]: #method.sample
+    fn neutral_point(&self, n: usize) -> f64 {
+        let n = n.max(2);
+        let mut best_x = 0.;
+        let mut best_chroma = f64::INFINITY;
+        for (x, sample) in sample_points(n).into_iter().zip(self.sample(n)) {
+            let chroma = sample.chroma();
+            if chroma < best_chroma {
+                best_x = x;
+                best_chroma = chroma;
+            }
+        }
+        best_x
+    }
+    /// Scores how well the colormap suits the given data, which should already be normalized to
+    /// inputs between 0 and 1 (values outside of that are clamped, and NaN values are ignored). The
+    /// data is binned into a histogram with 20 equal-width bins, the colormap is sampled at the
+    /// center of each bin that contains data, and the result is the mean CIEDE2000 difference
+    /// between each pair of consecutive occupied bins. Higher is better: the score rewards
+    /// colormaps that put their perceptual contrast where the data actually is, rather than in
+    /// ranges where there is no data to show. If the data occupies fewer than 2 bins, returns 0.
+    fn fit_score(&self, data: &[f64]) -> f64 {
+        const BINS: usize = 20;
+        let mut occupied = [false; BINS];
+        for &x in data.iter().filter(|x| !x.is_nan()) {
+            let bin = (x.clamp(0., 1.) * BINS as f64) as usize;
+            occupied[bin.min(BINS - 1)] = true;
+        }
+        let centers: Vec<T> = (0..BINS)
+            .filter(|&i| occupied[i])
+            .map(|i| self.transform_single((i as f64 + 0.5) / BINS as f64))
+            .collect();
+        if centers.len() < 2 {
+            return 0.;
+        }
+        let total: f64 = centers.windows(2).map(|w| w[0].distance(&w[1])).sum();
+        total / (centers.len() - 1) as f64
+    }
+    /// Finds the inputs where the colormap's CIELCH hue turns around: that is, where it stops
+    /// increasing and starts decreasing or vice versa. The colormap is sampled with
+    /// [`ColorMap::sample`], using at least 3 samples, and hue is tracked continuously across the
+    /// 0/360 boundary, so a map that passes through red isn't seen as turning there.
+    /// The endpoints are never reported. For a cyclic colormap that sweeps back and forth in hue,
+    /// these are natural places for labels, like the cardinal directions on a phase wheel. The
+    /// result is sorted in increasing order, and is empty if the hue only moves in one direction.
+    ///
+    /// [`ColorMap::sample`]: #method.sample
+    fn hue_extrema(&self, n: usize) -> Vec<f64> {
+        let steps = n.max(3) - 1;
+        let hues: Vec<f64> = self.sample(steps + 1).iter().map(|c| c.hue()).collect();
+        let mut extrema = vec![];
+        // the direction the hue was last moving in, and the sample where it last moved: if the hue
+        // holds still for a while before turning, the turn is placed in the middle of that stretch
+        let mut last_dir = 0.;
+        let mut last_end = 0;
+        for i in 1..=steps {
+            // the shortest way around the hue circle
+            let diff = (hues[i] - hues[i - 1] + 540.).rem_euclid(360.) - 180.;
+            if diff == 0. {
+                continue;
+            }
+            let dir = diff.signum();
+            if dir != last_dir {
+                if last_dir != 0. {
+                    extrema.push((last_end + i - 1) as f64 / 2. / steps as f64);
+                }
+                last_dir = dir;
+            }
+            last_end = i;
+        }
+        extrema
+    }
+    /// Takes `n` samples of the colormap with [`ColorMap::sample`], and quantizes each one to an
+    /// sRGB color with `bits` bits per channel (at least 1, at most 16), for displays that can only
+    /// show a few levels. Rounding each sample on its own gives visible bands: instead, this uses
+    /// error diffusion along the strip, carrying the rounding error of each sample into the next so
+    /// that the strip averages out to the smooth colormap. Out-of-gamut colors are clamped first.
+    ///
+    /// [`ColorMap::sample`]: #method.sample
+    fn dither_to_bits(&self, bits: u8, n: usize) -> Vec<RGBColor> {
+        let levels = ((1u32 << bits.clamp(1, 16)) - 1) as f64;
+        let mut err = [0.; 3];
+        self.sample(n)
+            .into_iter()
+            .map(|color| {
+                let rgb: RGBColor = color.convert();
+                let mut quantized = [0.; 3];
+                for (i, &val) in [rgb.r, rgb.g, rgb.b].iter().enumerate() {
+                    let target = val.clamp(0., 1.) + err[i];
+                    quantized[i] = ((target * levels).round() / levels).clamp(0., 1.);
+                    err[i] = target - quantized[i];
+                }
+                RGBColor {
+                    r: quantized[0],
+                    g: quantized[1],
+                    b: quantized[2],
+                }
+            })
+            .collect()
+    }
+    /// Writes the colormap to a PNG file at `path`, as an 8-bit RGB image `width` pixels wide and 1
+    /// pixel tall: each pixel is one of `width` samples from [`ColorMap::sample`], rounded to the
+    /// nearest sRGB color. This is the format shaders expect for a gradient lookup texture. Returns
+    /// an error if `width` is 0 or the file can't be written. Requires the `image` feature.
+    ///
+    /// [`ColorMap::sample`]: #method.sample
+    #[cfg(feature = "image")]
+    fn save_lut_png(&self, path: &str, width: usize) -> io::Result<()> {
+        if width == 0 || width > u32::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "lookup table width must be between 1 and 2^32 - 1",
+            ));
+        }
+        let pixels: Vec<u8> = self
+            .sample(width)
+            .into_iter()
+            .flat_map(|color| {
+                let (r, g, b) = color.convert::<RGBColor>().int_rgb_tup();
+                [r, g, b]
+            })
+            .collect();
+        let img = image::RgbImage::from_raw(width as u32, 1, pixels)
+            .expect("There is one pixel for each sample.");
+        img.save(path).map_err(|err| match err {
+            image::ImageError::IoError(err) => err,
+            err => io::Error::other(err),
+        })
+    }
+    /// Measures how fast the colormap changes perceptually across its range. The inputs from 0 to 1
+    /// are split into `bins` equal-width bins, and for each one this returns the CIEDE2000 distance
+    /// traveled within that bin divided by the bin's width: the average rate of perceptual change
+    /// per unit of input. A perceptually uniform colormap has about the same speed everywhere,
+    /// while peaks and dips show where a colormap stretches or compresses contrast. Each bin is
+    /// measured with several steps so that curved paths aren't undercounted.
+    fn perceptual_speed_histogram(&self, bins: usize) -> Vec<f64> {
+        const STEPS_PER_BIN: usize = 8;
+        let total_steps = bins * STEPS_PER_BIN;
+        let samples: Vec<T> = (0..=total_steps)
+            .map(|i| self.transform_single(i as f64 / total_steps as f64))
+            .collect();
+        samples
+            .windows(2)
+            .map(|pair| pair[0].distance(&pair[1]))
+            .collect::<Vec<f64>>()
+            .chunks(STEPS_PER_BIN)
+            .map(|steps| steps.iter().sum::<f64>() * bins as f64)
+            .collect()
+    }
+    /// Counts how many perceptually distinct colors the colormap really has once it's stored as an
+    /// 8-bit lookup table, such as a texture for a shader. The table holds 256 samples from
+    /// [`ColorMap::sample`], each rounded to the nearest 8-bit sRGB color. Walking from one end to
+    /// the other, a color counts as a new level if its CIEDE2000 distance from the last counted
+    /// level is more than 1, the just-noticeable difference. The result is the number of levels a
+    /// viewer can actually tell apart, which is always at least 1 and at most 256: maps that change
+    /// slowly in perceptual terms have far fewer than their 256 entries suggest.
+    ///
+    /// [`ColorMap::sample`]: #method.sample
+    fn effective_levels_8bit(&self) -> usize {
+        let quantized: Vec<RGBColor> = self
+            .sample(256)
+            .into_iter()
+            .map(|color| RGBColor::from(color.convert::<RGBColor>().int_rgb_tup()))
+            .collect();
+        let mut last_level = quantized[0];
+        let mut levels = 1;
+        for color in quantized.into_iter().skip(1) {
+            if color.distance(&last_level) > 1.0 {
+                levels += 1;
+                last_level = color;
+            }
+        }
+        levels
+    }
+    /// Builds a lookup table for classed data, where the range from 0 to 1 is split into intervals
+    /// at the given `thresholds`, which should be sorted and between 0 and 1. The result has one
+    /// color for each interval, so one more than there are thresholds: the first covers 0 up to the
+    /// first threshold, and the last covers the last threshold up to 1. Each interval's color is
+    /// the colormap's value at its midpoint. Because the thresholds are sorted, the color for a
+    /// value can be found with a binary search, as in the example: this makes it cheap to color
+    /// large amounts of data with non-uniform class breaks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// let breaks = [0.1, 0.25, 0.5];
+    /// let lut = ColorMap::<RGBColor>::sorted_lut(&ListedColorMap::viridis(), &breaks);
+    /// assert_eq!(lut.len(), 4);
+    /// // 0.3 is in the third interval, from 0.25 to 0.5
+    /// let class = breaks.partition_point(|&t| t <= 0.3);
+    /// assert_eq!(class, 2);
+    /// let mid: RGBColor = ListedColorMap::viridis().transform_single(0.375);
+    /// assert_eq!(lut[class].to_string(), mid.to_string());
+    /// ```
+    fn sorted_lut(&self, thresholds: &[f64]) -> Vec<RGBColor> {
+        let bounds: Vec<f64> = Some(0.0)
+            .into_iter()
+            .chain(thresholds.iter().cloned())
+            .chain(Some(1.0))
+            .collect();
+        bounds
+            .windows(2)
+            .map(|pair| self.transform_single((pair[0] + pair[1]) / 2.0).convert())
+            .collect()
+    }
+    /// Makes a recolored copy of the colormap by rotating its hue, for example to get a warm variant
+    /// of a cool map. The colormap is sampled as by [`ColorMap::sample`], with at least 2 samples,
+    /// and each sample's CIELCH hue is rotated by `degrees`, keeping its lightness
+    /// and chroma. Where the rotated color falls outside of the sRGB gamut, its chroma is reduced
+    /// until it fits, so the lightness profile of the original map is always preserved. The
+    /// samples are returned as a [`ListedColorMap`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// // a warm variant of viridis, going from purple through red to yellow-green
+    /// let warm = ColorMap::<RGBColor>::rotate_hue(&ListedColorMap::viridis(), 120., 256);
+    /// assert_eq!(warm.vals.len(), 256);
+    /// ```
+    ///
+    /// [`ListedColorMap`]: struct.ListedColorMap.html
+    /// [`ColorMap::sample`]: #method.sample
+    fn rotate_hue(&self, degrees: f64, n: usize) -> ListedColorMap {
+        ListedColorMap::new(self.sample(n.max(2)).into_iter().map(|color| {
+            let lch: CIELCHColor = color.convert();
+            let h = (lch.h + degrees).rem_euclid(360.0);
+            let c = srgb_max_chroma(lch.l, h, lch.c);
+            let rgb: RGBColor = CIELCHColor { l: lch.l, c, h }.convert();
+            [rgb.r, rgb.g, rgb.b]
+        }))
+    }
+    /// Checks whether the colormap can be faithfully printed, by taking at least 2 samples with
+    /// [`ColorMap::sample`] and converting each one to [`CMYKColor`] and back. If the CIEDE2000
+    /// difference caused by the round trip stays within `tol` for every sample, returns `true`:
+    /// otherwise, some part of the map is outside of the typical print gamut, and returns `false`.
+    /// A `tol` of 1 requires the printed map to be visually indistinguishable from the original.
+    ///
+    /// [`CMYKColor`]: ../colors/cmykcolor/struct.CMYKColor.html
+    /// [`ColorMap::sample`]: #method.sample
+    fn is_cmyk_printable(&self, n: usize, tol: f64) -> bool {
+        self.sample(n.max(2)).into_iter().all(|color| {
+            let cmyk: CMYKColor = color.convert();
+            color.distance(&cmyk) <= tol
+        })
+    }
 }
 
 /// A struct that describes different transformations of the numbers between 0 and 1 to themselves,
 /// used for controlling the linearity or nonlinearity of gradients.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum NormalizeMapping {
     /// A normal linear mapping: each number maps to itself.
     Linear,
@@ -36,8 +503,26 @@ pub enum NormalizeMapping {
     /// differences in the low end of the range, which is useful for some data like sound intensity
     /// that isn't perceived linearly.
     Cbrt,
+    /// A square root mapping: 1/4 would map to 1/2, for example. This emphasizes the low end of the
+    /// range like [`NormalizeMapping::Cbrt`], but less strongly.
+    ///
+    /// [`NormalizeMapping::Cbrt`]: #variant.Cbrt
+    Sqrt,
+    /// A power (gamma) mapping, raising each number to the given exponent. Exponents below 1
+    /// emphasize differences in the low end of the range, and exponents above 1 emphasize
+    /// differences in the high end. The exponent must be positive for 0 to map to 0: zero,
+    /// negative, and NaN exponents are treated as the smallest positive exponent instead, which
+    /// maps 0 to 0 and everything else to very nearly 1.
+    Power(f64),
+    /// A logarithmic mapping, for data spanning several orders of magnitude. Because the log of 0
+    /// is undefined, this takes the log of `x + 0.001` and rescales the result so that 0 still maps
+    /// to 0 and 1 to 1: this gives an even spread to the three decades from 0.001 to 1, and values
+    /// smaller than that all map close to 0.
+    Log,
     /// A generic mapping, taking as a value any function or closure that maps the integers from 0-1
-    /// to the same range. This should never fail.
+    /// to the same range. This should never fail. Functions can't be serialized, so trying to
+    /// serialize this variant gives an error, and it never results from deserialization.
+    #[serde(skip)]
     Generic(fn(f64) -> f64),
 }
 
@@ -49,18 +534,84 @@ impl NormalizeMapping {
         match *self {
             NormalizeMapping::Linear => x,
             NormalizeMapping::Cbrt => x.cbrt(),
+            NormalizeMapping::Sqrt => x.sqrt(),
+            // f64::max ignores NaN, so this also catches that
+            NormalizeMapping::Power(exp) => x.powf(exp.max(f64::MIN_POSITIVE)),
+            NormalizeMapping::Log => {
+                const EPS: f64 = 1e-3;
+                ((x + EPS) / EPS).ln() / ((1. + EPS) / EPS).ln()
+            }
             NormalizeMapping::Generic(func) => func(x),
         }
     }
 }
 
+/// The ways of measuring the perceptual distance between colors along a colormap, used for example
+/// by [`GradientColorMap::arc_length`] to see how much a colormap changes from start to finish.
+///
+/// [`GradientColorMap::arc_length`]: struct.GradientColorMap.html#method.arc_length
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InterpSpace {
+    /// The Euclidean distance between colors in CIELAB. This is simple and fast, and is the
+    /// distance that a straight line in CIELAB covers exactly.
+    CIELAB,
+    /// The CIEDE2000 difference between colors, which more closely follows human perception. See
+    /// [`Color::distance`](../color/trait.Color.html#method.distance) for more.
+    CIEDE2000,
+}
+
+impl InterpSpace {
+    /// Gets the distance between two colors, of any type, as measured in this space.
+    pub fn distance<T: Color, U: Color>(self, c1: &T, c2: &U) -> f64 {
+        match self {
+            InterpSpace::CIELAB => {
+                let lab1: CIELABColor = c1.convert();
+                let lab2: CIELABColor = c2.convert();
+                lab1.euclidean_distance(lab2)
+            }
+            InterpSpace::CIEDE2000 => c1.distance(c2),
+        }
+    }
+}
+
+/// Replaces NaN with 0, leaving everything else as is. NaN fails every comparison, so it slips past
+/// clamping and poisons interpolation: colormaps call this on their input first.
+fn nan_as_zero(x: f64) -> f64 {
+    if x.is_nan() {
+        0.
+    } else {
+        x
+    }
+}
+
+/// The inputs [`ColorMap::sample`] uses for `n` samples: `i / (n - 1)` for `i` from 0 to `n - 1`,
+/// or just 0.5 if `n` is 1.
+fn sample_points(n: usize) -> Vec<f64> {
+    match n {
+        0 => vec![],
+        1 => vec![0.5],
+        _ => (0..n).map(|i| i as f64 / (n - 1) as f64).collect(),
+    }
+}
+
+/// Adds up the distances between consecutive samples of a colormap, taking at least 2.
+fn arc_length<T: Color, M: ColorMap<T>>(map: &M, space: InterpSpace, n: usize) -> f64 {
+    map.sample(n.max(2))
+        .windows(2)
+        .map(|pair| space.distance(&pair[0], &pair[1]))
+        .sum()
+}
+
 /// A gradient colormap: a continuous, evenly-spaced shift between two colors A and B such that 0 maps
 /// to A, 1 maps to B, and any number in between maps to a weighted mix of them in a given
 /// coordinate space. Uses the gradient functions in the [`ColorPoint`] trait to complete this.
 /// Out-of-range values are simply clamped to the correct range: calling this on negative numbers
-/// will return A, and calling this on numbers larger than 1 will return B.
-#[derive(Debug, Clone)]
-pub struct GradientColorMap<T: ColorPoint> {
+/// will return A, and calling this on numbers larger than 1 will return B. The colors are mixed in
+/// the space `S`, which is `T` itself unless [`GradientColorMap::in_space`] picks another.
+///
+/// [`GradientColorMap::in_space`]: #method.in_space
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GradientColorMap<T: ColorPoint, S: ColorPoint = T> {
     /// The start of the gradient. Calling this colormap on 0 or any negative number returns this color.
     pub start: T,
     /// The end of the gradient. Calling this colormap on 1 or any larger number returns this color.
@@ -74,6 +625,29 @@ pub struct GradientColorMap<T: ColorPoint> {
     /// keeping the overall map smooth and continuous. Padding of `(0., 1.)` is the default and normal
     /// behavior.
     pub padding: (f64, f64),
+    /// Marks the color space `S` the start and end colors are mixed in. This holds no data, and is
+    /// not serialized: the space is part of the type.
+    #[serde(skip)]
+    pub space: PhantomData<S>,
+}
+
+/// Mixes two colors by their coordinates in their own space, going `t` of the way from `start` to
+/// `end`.
+fn interpolate_native<T: ColorPoint>(start: T, end: T, t: f64) -> T {
+    let c1: Coord = start.into();
+    let c2: Coord = end.into();
+    T::from(c2.weighted_midpoint(&c1, t))
+}
+
+/// Mixes two colors by their coordinates in the space `S`, going `t` of the way from `start` to
+/// `end`, and converts the result back. If `S` is `T`, the colors are mixed as they are, without a
+/// round trip through XYZ that could lose information like the hue of a gray.
+fn interpolate_in<T: ColorPoint + 'static, S: ColorPoint + 'static>(start: T, end: T, t: f64) -> T {
+    if TypeId::of::<S>() == TypeId::of::<T>() {
+        interpolate_native(start, end, t)
+    } else {
+        interpolate_native(start.convert::<S>(), end.convert::<S>(), t).convert()
+    }
 }
 
 impl<T: ColorPoint> GradientColorMap<T> {
@@ -84,6 +658,7 @@ impl<T: ColorPoint> GradientColorMap<T> {
             end,
             normalization: NormalizeMapping::Linear,
             padding: (0., 1.),
+            space: PhantomData,
         }
     }
     /// Constructs a new cube root [`GradientColorMap`], without padding, from two colors.
@@ -93,12 +668,115 @@ impl<T: ColorPoint> GradientColorMap<T> {
             end,
             normalization: NormalizeMapping::Cbrt,
             padding: (0., 1.),
+            space: PhantomData,
+        }
+    }
+}
+
+impl<T: ColorPoint + 'static, S: ColorPoint + 'static> GradientColorMap<T, S> {
+    /// Makes the gradient mix its colors in the color space `U` instead: the start and end are
+    /// converted to `U`, mixed there, and the result is converted back to `T`. The path a gradient
+    /// takes depends a great deal on the space it's mixed in: mixing in CIELAB, for example,
+    /// changes lightness evenly, while mixing in sRGB is quicker but uneven.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, GradientColorMap};
+    /// # use scarlet::colors::CIELABColor;
+    /// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// let rgb_mix = GradientColorMap::new_linear(red, white);
+    /// let lab_mix = GradientColorMap::new_linear(red, white).in_space::<CIELABColor>();
+    /// // the endpoints are the same, but the paths between them are not
+    /// assert_eq!(lab_mix.transform_single(1.).to_string(), "#FFFFFF");
+    /// assert_eq!(rgb_mix.transform_single(0.5).to_string(), "#FF8080");
+    /// assert_eq!(lab_mix.transform_single(0.5).to_string(), "#FF9F80");
+    /// ```
+    pub fn in_space<U: ColorPoint>(self) -> GradientColorMap<T, U> {
+        GradientColorMap {
+            start: self.start,
+            end: self.end,
+            normalization: self.normalization,
+            padding: self.padding,
+            space: PhantomData,
+        }
+    }
+    /// Computes the total perceptual distance the colormap travels from 0 to 1, measured in the
+    /// given space, by adding up the distances between `n` consecutive samples (at least 2) from
+    /// [`ColorMap::sample`]: more samples give a more accurate result for curved paths. This is
+    /// useful for pacing animations, so that equal amounts of time give equal amounts of perceived
+    /// change.
+    ///
+    /// [`ColorMap::sample`]: trait.ColorMap.html#method.sample
+    pub fn arc_length(&self, space: InterpSpace, n: usize) -> f64 {
+        arc_length(self, space, n)
+    }
+}
+
+impl GradientColorMap<CIELABColor> {
+    /// Constructs a single-hue sequential colormap, like matplotlib's `Blues` or `Greens`: a ramp
+    /// from a very light tint at 0 to a dark shade at 1, all at the given CIELCH hue in degrees.
+    /// Lightness falls steadily from 95 to 25, so the map reads correctly in grayscale, and the
+    /// chroma grows along with the darkness. The ramp is a straight line in CIELAB, so the hue is the
+    /// same throughout, and the chroma is chosen to be as high as possible while keeping the whole
+    /// ramp inside the sRGB gamut. Some hues can hold much more chroma than others at these
+    /// lightnesses: yellows, for example, turn olive as they darken.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, GradientColorMap};
+    /// let blues = GradientColorMap::sequential_from_hue(270.);
+    /// let light: RGBColor = blues.transform_single(0.).convert();
+    /// let dark: RGBColor = blues.transform_single(1.).convert();
+    /// assert!(light.lightness() > dark.lightness());
+    /// assert!(dark.b > dark.r && dark.b > dark.g);
+    /// ```
+    pub fn sequential_from_hue(hue_degrees: f64) -> GradientColorMap<CIELABColor> {
+        let h = hue_degrees.rem_euclid(360.0);
+        let ramp = |scale: f64| {
+            let start = CIELCHColor {
+                l: 95.0,
+                c: scale * srgb_max_chroma(95.0, h, 15.0),
+                h,
+            };
+            let end = CIELCHColor {
+                l: 25.0,
+                c: scale * srgb_max_chroma(25.0, h, 50.0),
+                h,
+            };
+            GradientColorMap::new_linear(start.convert(), end.convert())
+        };
+        // the gamut isn't convex, so the middle can leave it even if both ends are inside: scale
+        // the chroma down until it doesn't
+        let fits = |map: &GradientColorMap<CIELABColor>| {
+            map.sample(33)
+                .iter()
+                .all(|c| c.convert::<RGBColor>().is_in_gamut())
+        };
+        let full = ramp(1.0);
+        if fits(&full) {
+            return full;
+        }
+        let (mut lo, mut hi) = (0.0, 1.0);
+        for _i in 0..20 {
+            let mid = (lo + hi) / 2.0;
+            if fits(&ramp(mid)) {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
         }
+        ramp(lo)
     }
 }
 
-impl<T: ColorPoint> ColorMap<T> for GradientColorMap<T> {
+impl<T: ColorPoint + 'static, S: ColorPoint + 'static> ColorMap<T> for GradientColorMap<T, S> {
     fn transform_single(&self, x: f64) -> T {
+        let x = nan_as_zero(x);
         // clamp between 0 and 1 beforehand
         let clamped = if x < 0. {
             0.
@@ -107,18 +785,616 @@ impl<T: ColorPoint> ColorMap<T> for GradientColorMap<T> {
         } else {
             x
         };
-        self.start
-            .padded_gradient(&self.end, self.padding.0, self.padding.1)(
-            self.normalization.normalize(clamped),
-        )
+        let (lower_pad, upper_pad) = self.padding;
+        let t = (upper_pad - lower_pad) * self.normalization.normalize(clamped) + lower_pad;
+        interpolate_in::<T, S>(self.start, self.end, t)
+    }
+}
+
+/// A colormap that runs another colormap backwards: an input of `x` gives the color the inner
+/// colormap gives for `1 - x`. Because [`ListedColorMap`] and the like can output any color type,
+/// calling [`ColorMap::reversed`] on them needs the output type spelled out, as in
+/// `ColorMap::<RGBColor>::reversed(cmap)`: [`ReversedColorMap::new`] avoids this.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, ListedColorMap, ReversedColorMap};
+/// let viridis = ListedColorMap::viridis();
+/// let viridis_r = ReversedColorMap::new(ListedColorMap::viridis());
+/// let end: RGBColor = viridis.transform_single(1.);
+/// let start: RGBColor = viridis_r.transform_single(0.);
+/// assert_eq!(start.to_string(), end.to_string());
+/// ```
+///
+/// [`ColorMap::reversed`]: trait.ColorMap.html#method.reversed
+/// [`ReversedColorMap::new`]: #method.new
+#[derive(Debug, Clone)]
+pub struct ReversedColorMap<M> {
+    /// The colormap being reversed.
+    pub inner: M,
+}
+
+impl<M> ReversedColorMap<M> {
+    /// Wraps the given colormap so that it runs backwards.
+    pub fn new(inner: M) -> ReversedColorMap<M> {
+        ReversedColorMap { inner }
+    }
+}
+
+impl<T: Color, M: ColorMap<T>> ColorMap<T> for ReversedColorMap<M> {
+    fn transform_single(&self, x: f64) -> T {
+        self.inner.transform_single(1.0 - x)
+    }
+}
+
+/// A trait for rescaling raw data values onto the range 0 to 1 that colormaps expect, used by
+/// [`DomainColorMap`].
+///
+/// [`DomainColorMap`]: struct.DomainColorMap.html
+pub trait Normalize {
+    /// Rescales a data value to the range 0 to 1.
+    fn normalize(&self, value: f64) -> f64;
+}
+
+/// A linear rescaling of data values from `vmin` to `vmax` onto 0 to 1, like matplotlib's
+/// `Normalize`. If `vmin` and `vmax` are equal, every value maps to 0.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Normalizer {
+    /// The data value that maps to 0.
+    pub vmin: f64,
+    /// The data value that maps to 1.
+    pub vmax: f64,
+    /// Whether values outside of `vmin` to `vmax` are clamped to 0 to 1. If this is false, they
+    /// extrapolate past 0 and 1 instead, which leaves handling them to the colormap: most clamp
+    /// anyway, but [`CyclicColorMap`], for example, wraps them around.
+    ///
+    /// [`CyclicColorMap`]: struct.CyclicColorMap.html
+    pub clip: bool,
+}
+
+impl Normalizer {
+    /// Constructs a new [`Normalizer`] from `vmin` to `vmax` that clamps values outside of that
+    /// range.
+    pub fn new(vmin: f64, vmax: f64) -> Normalizer {
+        Normalizer {
+            vmin,
+            vmax,
+            clip: true,
+        }
+    }
+}
+
+impl Normalize for Normalizer {
+    /// Rescales a data value to the range 0 to 1, or past it if `clip` is false. NaN stays NaN.
+    fn normalize(&self, value: f64) -> f64 {
+        if self.vmin == self.vmax && !value.is_nan() {
+            return 0.;
+        }
+        let x = (value - self.vmin) / (self.vmax - self.vmin);
+        if self.clip {
+            x.clamp(0., 1.)
+        } else {
+            x
+        }
+    }
+}
+
+/// A logarithmic rescaling of data values from `vmin` to `vmax` onto 0 to 1, like matplotlib's
+/// `LogNorm`: a value `v` maps to `(log(v) - log(vmin)) / (log(vmax) - log(vmin))`, so that each
+/// order of magnitude takes up the same amount of the colormap. Values outside of `vmin` to `vmax`
+/// are clamped to 0 to 1. Zero and negative values have no logarithm, and map to 0 like other
+/// values below `vmin`; NaN maps to NaN.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogNormalizer {
+    /// The data value that maps to 0. Must be positive: use [`LogNormalizer::new`] to ensure this
+    /// holds.
+    ///
+    /// [`LogNormalizer::new`]: #method.new
+    pub vmin: f64,
+    /// The data value that maps to 1. Must be positive.
+    pub vmax: f64,
+}
+
+impl LogNormalizer {
+    /// Constructs a new [`LogNormalizer`] from `vmin` to `vmax`.
+    /// # Errors
+    /// Returns `ColorMapError::NonPositiveDomain` if `vmin` or `vmax` is not positive.
+    pub fn new(vmin: f64, vmax: f64) -> Result<LogNormalizer, ColorMapError> {
+        if vmin > 0. && vmax > 0. {
+            Ok(LogNormalizer { vmin, vmax })
+        } else {
+            Err(ColorMapError::NonPositiveDomain)
+        }
+    }
+}
+
+impl Normalize for LogNormalizer {
+    fn normalize(&self, value: f64) -> f64 {
+        if value <= 0. {
+            return 0.;
+        }
+        Normalizer::new(self.vmin.ln(), self.vmax.ln()).normalize(value.ln())
+    }
+}
+
+/// A colormap that takes raw data values, rescales them with a [`Normalize`] implementation such as
+/// [`Normalizer`] or [`LogNormalizer`], and passes them on to another colormap. Usually constructed
+/// with [`ColorMap::with_domain`] or [`ColorMap::with_log_domain`].
+///
+/// [`Normalize`]: trait.Normalize.html
+/// [`Normalizer`]: struct.Normalizer.html
+/// [`LogNormalizer`]: struct.LogNormalizer.html
+/// [`ColorMap::with_domain`]: trait.ColorMap.html#method.with_domain
+/// [`ColorMap::with_log_domain`]: trait.ColorMap.html#method.with_log_domain
+#[derive(Debug, Clone)]
+pub struct DomainColorMap<M, N = Normalizer> {
+    /// The colormap the rescaled values are given to.
+    pub inner: M,
+    /// How data values are rescaled.
+    pub normalizer: N,
+}
+
+impl<M, N> DomainColorMap<M, N> {
+    /// Wraps the given colormap so that it rescales its inputs with the given normalizer first.
+    pub fn new(inner: M, normalizer: N) -> DomainColorMap<M, N> {
+        DomainColorMap { inner, normalizer }
+    }
+}
+
+impl<T: Color, M: ColorMap<T>, N: Normalize> ColorMap<T> for DomainColorMap<M, N> {
+    fn transform_single(&self, x: f64) -> T {
+        self.inner.transform_single(self.normalizer.normalize(x))
+    }
+}
+
+/// A colormap that turns another colormap into a diverging one by reflecting it about the center:
+/// 0.5 maps to the inner colormap's 0, and both 0 and 1 map to its 1. The lower half runs the inner
+/// colormap backwards and the upper half runs it forwards, so the neutral end of a sequential map
+/// sits in the middle.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, GradientColorMap, MirroredColorMap};
+/// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+/// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+/// let cmap = MirroredColorMap::new(GradientColorMap::new_linear(white, red));
+/// assert_eq!(cmap.transform_single(0.5).to_string(), "#FFFFFF");
+/// assert_eq!(cmap.transform_single(0.).to_string(), "#FF0000");
+/// assert_eq!(cmap.transform_single(0.25).to_string(), cmap.transform_single(0.75).to_string());
+/// ```
+#[derive(Debug, Clone)]
+pub struct MirroredColorMap<M> {
+    /// The colormap being reflected. Its 0 end is placed at the center.
+    pub inner: M,
+}
+
+impl<M> MirroredColorMap<M> {
+    /// Wraps the given colormap so that it is reflected about the center.
+    pub fn new(inner: M) -> MirroredColorMap<M> {
+        MirroredColorMap { inner }
+    }
+}
+
+impl<T: Color, M: ColorMap<T>> ColorMap<T> for MirroredColorMap<M> {
+    fn transform_single(&self, x: f64) -> T {
+        if x < 0.5 {
+            self.inner.transform_single(1.0 - 2.0 * x)
+        } else {
+            self.inner.transform_single(2.0 * (x - 0.5))
+        }
+    }
+}
+
+/// A colormap that passes its input through a function before giving it to another colormap, with
+/// the result clamped to 0 to 1. This is the general form of [`NormalizeMapping`]: any scalar
+/// transfer function can go here, including one that samples another colormap's lightness, or a
+/// lookup table of measured values. As with [`ReversedColorMap`], [`WarpedColorMap::new`] avoids
+/// spelling out the output type of colormaps like [`ListedColorMap`] that can output any color,
+/// which [`ColorMap::with_input_transform`] would need.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, ListedColorMap, WarpedColorMap};
+/// let viridis = ListedColorMap::viridis();
+/// let warped = WarpedColorMap::new(ListedColorMap::viridis(), |x: f64| x.sqrt());
+/// let warped_color: RGBColor = warped.transform_single(0.25);
+/// let color: RGBColor = viridis.transform_single(0.5);
+/// assert_eq!(warped_color.to_string(), color.to_string());
+/// ```
+///
+/// [`ReversedColorMap`]: struct.ReversedColorMap.html
+/// [`WarpedColorMap::new`]: #method.new
+/// [`ListedColorMap`]: struct.ListedColorMap.html
+/// [`ColorMap::with_input_transform`]: trait.ColorMap.html#method.with_input_transform
+/// [`NormalizeMapping`]: enum.NormalizeMapping.html
+#[derive(Clone)]
+pub struct WarpedColorMap<M, F> {
+    /// The colormap the transformed inputs are given to.
+    pub inner: M,
+    /// The function inputs are passed through.
+    pub transform: F,
+}
+
+impl<M, F: Fn(f64) -> f64> WarpedColorMap<M, F> {
+    /// Wraps the given colormap so that it passes its inputs through `transform` first.
+    pub fn new(inner: M, transform: F) -> WarpedColorMap<M, F> {
+        WarpedColorMap { inner, transform }
+    }
+}
+
+impl<T: Color, M: ColorMap<T>, F: Fn(f64) -> f64> ColorMap<T> for WarpedColorMap<M, F> {
+    fn transform_single(&self, x: f64) -> T {
+        // missing data isn't a position to warp: the inner colormap handles it
+        if x.is_nan() {
+            return self.inner.transform_single(x);
+        }
+        self.inner
+            .transform_single((self.transform)(x).clamp(0., 1.))
+    }
+}
+
+/// A colormap that marks data outside of the range 0 to 1 with its own colors, like
+/// `set_under`, `set_over`, and `set_bad` in matplotlib: inputs below 0 give `under`, inputs above
+/// 1 give `over`, and NaN gives `bad`. Everything else goes to the inner colormap. This makes
+/// clipped or missing data visible instead of blending in with the ends of the colormap. To mark
+/// raw data values outside of a domain, wrap the result with [`ColorMap::with_domain`] and a
+/// [`Normalizer`] that doesn't clip.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, ListedColorMap, OverUnderColorMap};
+/// let gray = RGBColor::from_hex_code("#808080").unwrap();
+/// let black = RGBColor::from_hex_code("#000000").unwrap();
+/// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+/// let cmap = OverUnderColorMap::new(ListedColorMap::viridis(), black, white, gray);
+/// assert_eq!(cmap.transform_single(-0.5).to_string(), "#000000");
+/// assert_eq!(cmap.transform_single(1.5).to_string(), "#FFFFFF");
+/// assert_eq!(cmap.transform_single(std::f64::NAN).to_string(), "#808080");
+/// assert_eq!(cmap.transform_single(1.).to_string(), "#FDE725");
+/// ```
+///
+/// [`ColorMap::with_domain`]: trait.ColorMap.html#method.with_domain
+/// [`Normalizer`]: struct.Normalizer.html
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OverUnderColorMap<M, T> {
+    /// The colormap for inputs from 0 to 1.
+    pub inner: M,
+    /// The color for inputs below 0.
+    pub under: T,
+    /// The color for inputs above 1.
+    pub over: T,
+    /// The color for NaN inputs.
+    pub bad: T,
+}
+
+impl<M, T: Color> OverUnderColorMap<M, T> {
+    /// Wraps the given colormap so that inputs below 0, inputs above 1, and NaN give the `under`,
+    /// `over`, and `bad` colors respectively.
+    pub fn new(inner: M, under: T, over: T, bad: T) -> OverUnderColorMap<M, T> {
+        OverUnderColorMap {
+            inner,
+            under,
+            over,
+            bad,
+        }
+    }
+}
+
+impl<T: Color + Clone, M: ColorMap<T>> ColorMap<T> for OverUnderColorMap<M, T> {
+    fn transform_single(&self, x: f64) -> T {
+        if x.is_nan() {
+            self.bad.clone()
+        } else if x < 0. {
+            self.under.clone()
+        } else if x > 1. {
+            self.over.clone()
+        } else {
+            self.inner.transform_single(x)
+        }
+    }
+}
+
+/// A colormap that wraps its input around instead of clamping it, so that 1.25 maps to the same
+/// color as 0.25 and -0.25 maps to the same color as 0.75. Most colormaps clamp inputs outside of 0
+/// to 1, which is a problem for cyclic data like angles or phases: 370 degrees should look like 10
+/// degrees, not like 360. Wrapping a cyclic colormap such as [`ListedColorMap::circle`] in this is
+/// the correct way to use it for that kind of data.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, CyclicColorMap, ListedColorMap};
+/// let phase = CyclicColorMap::new(ListedColorMap::circle());
+/// let c1: RGBColor = phase.transform_single(1.2);
+/// let c2: RGBColor = phase.transform_single(0.2);
+/// assert_eq!(c1.to_string(), c2.to_string());
+/// ```
+///
+/// [`ListedColorMap::circle`]: struct.ListedColorMap.html#method.circle
+#[derive(Debug, Clone)]
+pub struct CyclicColorMap<M> {
+    /// The colormap being wrapped. It should have the same color at 0 and 1.
+    pub inner: M,
+}
+
+impl<M> CyclicColorMap<M> {
+    /// Wraps the given colormap so that inputs outside of 0 to 1 wrap around.
+    pub fn new(inner: M) -> CyclicColorMap<M> {
+        CyclicColorMap { inner }
+    }
+}
+
+impl<T: Color, M: ColorMap<T>> ColorMap<T> for CyclicColorMap<M> {
+    fn transform_single(&self, x: f64) -> T {
+        self.inner.transform_single(x.rem_euclid(1.0))
+    }
+}
+
+/// A colormap that fades another colormap into a fixed fog color, by an amount that depends on the
+/// input: an input of `x` gives the inner colormap's color for `x`, mixed `opacity_fn(x)` of the way
+/// toward `fog`. An opacity of 0 leaves the inner color alone, and an opacity of 1 gives the fog
+/// color. The mixing is done in linear sRGB, the way light actually combines, so the fog looks like
+/// a translucent layer over the colormap. This is useful for atmospheric effects, or for fading the
+/// low end of a heatmap into the background.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, FogColorMap, ListedColorMap};
+/// let background = RGBColor::from_hex_code("#ffffff").unwrap();
+/// // fade out toward 0
+/// let cmap = FogColorMap::new(ListedColorMap::viridis(), background, |x| 1. - x);
+/// let start: RGBColor = cmap.transform_single(0.);
+/// let end: RGBColor = cmap.transform_single(1.);
+/// assert_eq!(start.to_string(), "#FFFFFF");
+/// assert_eq!(end.to_string(), "#FDE725");
+/// ```
+#[derive(Debug, Clone)]
+pub struct FogColorMap<M> {
+    /// The colormap being fogged.
+    pub inner: M,
+    /// The color of the fog.
+    pub fog: RGBColor,
+    /// Gives the opacity of the fog, from 0 to 1, for each input. Values outside of that range are
+    /// clamped.
+    pub opacity_fn: fn(f64) -> f64,
+}
+
+impl<M> FogColorMap<M> {
+    /// Wraps the given colormap in fog of the given color, with the given opacity function.
+    pub fn new(inner: M, fog: RGBColor, opacity_fn: fn(f64) -> f64) -> FogColorMap<M> {
+        FogColorMap {
+            inner,
+            fog,
+            opacity_fn,
+        }
+    }
+}
+
+impl<T: Color, M: ColorMap<T>> ColorMap<T> for FogColorMap<M> {
+    fn transform_single(&self, x: f64) -> T {
+        // no fog over missing data, so whatever the inner colormap uses to mark it shows through
+        let opacity = if x.is_nan() {
+            0.
+        } else {
+            (self.opacity_fn)(x).clamp(0., 1.)
+        };
+        let color: RGBColor = self.inner.transform_single(x).convert();
+        let mix = |c: f64, f: f64| {
+            linear_to_srgb(srgb_to_linear(c) * (1. - opacity) + srgb_to_linear(f) * opacity)
+        };
+        RGBColor {
+            r: mix(color.r, self.fog.r),
+            g: mix(color.g, self.fog.g),
+            b: mix(color.b, self.fog.b),
+        }
+        .convert()
+    }
+}
+
+/// An error that results from an invalid attempt to construct a colormap.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ColorMapError {
+    /// This indicates that no colors were given, so there is nothing to map to.
+    Empty,
+    /// This indicates that a position was outside of the range 0 to 1.
+    PositionOutOfRange,
+    /// This indicates that the positions were not strictly increasing.
+    PositionsNotIncreasing,
+    /// This indicates that a logarithmic domain included zero or negative numbers.
+    NonPositiveDomain,
+}
+
+impl fmt::Display for ColorMapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ColorMap construction error")
+    }
+}
+
+impl Error for ColorMapError {
+    fn description(&self) -> &str {
+        match *self {
+            ColorMapError::Empty => "No colors given",
+            ColorMapError::PositionOutOfRange => "Position outside of the range 0 to 1",
+            ColorMapError::PositionsNotIncreasing => "Positions not strictly increasing",
+            ColorMapError::NonPositiveDomain => "Logarithmic domain not positive",
+        }
+    }
+}
+
+/// A gradient colormap with any number of colors, or *stops*, at arbitrary positions, like CSS's
+/// `linear-gradient`. Between two stops, colors are mixed linearly in the coordinate space of `T`,
+/// just like [`GradientColorMap`]. Inputs before the first stop or after the last stop map to the
+/// first or last color respectively.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, MultiGradientColorMap};
+/// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+/// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+/// let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+/// let cmap = MultiGradientColorMap::new(vec![(0., red), (0.25, white), (1., blue)]).unwrap();
+/// assert_eq!(cmap.transform_single(0.25).to_string(), "#FFFFFF");
+/// assert_eq!(cmap.transform_single(0.625).to_string(), "#8080FF");
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiGradientColorMap<T: ColorPoint> {
+    /// The stops, as pairs of (position, color), sorted by strictly increasing position between 0
+    /// and 1. Use [`MultiGradientColorMap::new`] to ensure these invariants hold.
+    ///
+    /// [`MultiGradientColorMap::new`]: #method.new
+    pub stops: Vec<(f64, T)>,
+}
+
+impl<T: ColorPoint> MultiGradientColorMap<T> {
+    /// Constructs a new [`MultiGradientColorMap`] from a list of (position, color) pairs.
+    /// # Errors
+    /// Returns `ColorMapError::Empty` if there are no stops, `ColorMapError::PositionOutOfRange` if
+    /// any position is not between 0 and 1, and `ColorMapError::PositionsNotIncreasing` if the
+    /// positions are not strictly increasing.
+    pub fn new(stops: Vec<(f64, T)>) -> Result<MultiGradientColorMap<T>, ColorMapError> {
+        if stops.is_empty() {
+            Err(ColorMapError::Empty)
+        } else if !stops.iter().all(|(pos, _)| (0.0..=1.0).contains(pos)) {
+            Err(ColorMapError::PositionOutOfRange)
+        } else if !stops.windows(2).all(|pair| pair[0].0 < pair[1].0) {
+            Err(ColorMapError::PositionsNotIncreasing)
+        } else {
+            Ok(MultiGradientColorMap { stops })
+        }
+    }
+}
+
+impl<T: ColorPoint> ColorMap<T> for MultiGradientColorMap<T> {
+    fn transform_single(&self, x: f64) -> T {
+        let x = nan_as_zero(x);
+        let (first_pos, first) = self.stops[0];
+        let (last_pos, last) = self.stops[self.stops.len() - 1];
+        if x <= first_pos {
+            return first;
+        } else if x >= last_pos {
+            return last;
+        }
+        // find the first stop past x: the one before it is guaranteed to exist
+        let ind = self
+            .stops
+            .iter()
+            .position(|(pos, _)| *pos > x)
+            .unwrap_or(self.stops.len() - 1);
+        let (pos1, color1) = self.stops[ind - 1];
+        let (pos2, color2) = self.stops[ind];
+        color2.weighted_midpoint(color1, (x - pos1) / (pos2 - pos1))
+    }
+}
+
+/// A diverging colormap: three colors, with a neutral `center` color between a `low` and a `high`
+/// one. Inputs below `center_position` are mixed between `low` and `center`, and inputs above it
+/// between `center` and `high`, in the coordinate space of `T`. Unlike a symmetric gradient, the
+/// neutral color can go anywhere, so it can line up with a meaningful value such as zero even when
+/// the data isn't symmetric about it. Inputs outside of 0 to 1 are clamped.
+///
+/// # Example
+/// Data from -3 to 7 should have white at 0, which is 0.3 of the way along.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::{ColorMap, DivergingColorMap};
+/// let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+/// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+/// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+/// let cmap = DivergingColorMap::new(blue, white, red, 0.3).unwrap();
+/// assert_eq!(cmap.transform_single(0.3).to_string(), "#FFFFFF");
+/// assert_eq!(cmap.transform_single(0.15).to_string(), "#8080FF");
+/// assert_eq!(cmap.transform_single(1.).to_string(), "#FF0000");
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DivergingColorMap<T: ColorPoint> {
+    /// The color at 0, and for any smaller input.
+    pub low: T,
+    /// The neutral color at `center_position`.
+    pub center: T,
+    /// The color at 1, and for any larger input.
+    pub high: T,
+    /// Where the neutral color goes, strictly between 0 and 1. Use [`DivergingColorMap::new`] to
+    /// ensure this holds.
+    ///
+    /// [`DivergingColorMap::new`]: #method.new
+    pub center_position: f64,
+}
+
+impl<T: ColorPoint> DivergingColorMap<T> {
+    /// Constructs a new [`DivergingColorMap`] from its three colors and the position of the center
+    /// one.
+    /// # Errors
+    /// Returns `ColorMapError::PositionOutOfRange` if `center_position` is not strictly between 0
+    /// and 1.
+    pub fn new(
+        low: T,
+        center: T,
+        high: T,
+        center_position: f64,
+    ) -> Result<DivergingColorMap<T>, ColorMapError> {
+        if center_position > 0. && center_position < 1. {
+            Ok(DivergingColorMap {
+                low,
+                center,
+                high,
+                center_position,
+            })
+        } else {
+            Err(ColorMapError::PositionOutOfRange)
+        }
+    }
+    /// Constructs a new [`DivergingColorMap`] whose center color is the data value `center` in data
+    /// that ranges from `min` to `max`, which is how the center is usually known.
+    /// # Errors
+    /// Returns `ColorMapError::PositionOutOfRange` if `center` is not strictly between `min` and
+    /// `max`.
+    pub fn centered_at(
+        low: T,
+        center_color: T,
+        high: T,
+        (min, center, max): (f64, f64, f64),
+    ) -> Result<DivergingColorMap<T>, ColorMapError> {
+        DivergingColorMap::new(low, center_color, high, (center - min) / (max - min))
+    }
+}
+
+impl<T: ColorPoint> ColorMap<T> for DivergingColorMap<T> {
+    fn transform_single(&self, x: f64) -> T {
+        let x = nan_as_zero(x);
+        let c = self.center_position;
+        if x <= 0. {
+            self.low
+        } else if x >= 1. {
+            self.high
+        } else if x < c {
+            self.center.weighted_midpoint(self.low, x / c)
+        } else {
+            self.high.weighted_midpoint(self.center, (x - c) / (1. - c))
+        }
     }
 }
 
 /// A colormap that linearly interpolates between a given series of values in an equally-spaced
 /// progression. This is modeled off of the `matplotlib` Python library's `ListedColormap`, and is
 /// only used to provide reference implementations of the standard matplotlib colormaps. Clamps values
-/// outside of 0 to 1.
-#[derive(Debug, Clone)]
+/// outside of 0 to 1. When serialized, this is just the list of values, each an array of three
+/// numbers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct ListedColorMap {
     /// The list of values, as a vector of `[f64]` arrays that provide equally-spaced RGB values.
     pub vals: Vec<[f64; 3]>,
@@ -127,8 +1403,14 @@ pub struct ListedColorMap {
 impl<T: ColorPoint> ColorMap<T> for ListedColorMap {
     /// Linearly interpolates by first finding the two colors on either boundary, and then using a
     /// simple linear gradient. There's no need to instantiate every single Color, because the vast
-    /// majority of them aren't important for one computation.
+    /// majority of them aren't important for one computation. The result is built in sRGB and
+    /// converted with [`Color::from_rgb`], so mapping to `RGBColor` doesn't go through XYZ at all.
+    /// For large-scale transformation, [`ListedColorMap::build_lut`] is faster still.
+    ///
+    /// [`Color::from_rgb`]: ../color/trait.Color.html#method.from_rgb
+    /// [`ListedColorMap::build_lut`]: #method.build_lut
     fn transform_single(&self, x: f64) -> T {
+        let x = nan_as_zero(x);
         let clamped = if x < 0. {
             0.
         } else if x > 1. {
@@ -136,9 +1418,6 @@ impl<T: ColorPoint> ColorMap<T> for ListedColorMap {
         } else {
             x
         };
-        // TODO: keeping every Color in memory might be more efficient for large-scale
-        // transformation; if it's a performance issue, try and fix
-
         // now find the two values that bound the clamped x
         // get the index as a floating point: the integers on either side bound it
         // we subtract 1 because 0-n is n+1 numbers, not n
@@ -149,12 +1428,11 @@ impl<T: ColorPoint> ColorMap<T> for ListedColorMap {
         if ind1 == ind2 {
             // x is exactly on the boundary, no interpolation needed
             let arr = self.vals[ind1]; // guaranteed to be in range
-            RGBColor::from(Coord {
-                x: arr[0],
-                y: arr[1],
-                z: arr[2],
+            T::from_rgb(RGBColor {
+                r: arr[0],
+                g: arr[1],
+                b: arr[2],
             })
-            .convert()
         } else {
             // interpolate
             let arr1 = self.vals[ind1];
@@ -170,14 +1448,57 @@ impl<T: ColorPoint> ColorMap<T> for ListedColorMap {
                 z: arr2[2],
             };
             // now interpolate and convert to the desired type
-            let rgb: RGBColor = coord2.weighted_midpoint(&coord1, clamped).into();
-            rgb.convert()
+            // the weight is the position between the two bounding values, not in the whole map
+            let weight = float_ind - ind1 as f64;
+            let rgb: RGBColor = coord2.weighted_midpoint(&coord1, weight).into();
+            T::from_rgb(rgb)
         }
     }
 }
 
 // now just constructors
 impl ListedColorMap {
+    /// Computes the total perceptual distance the colormap travels from 0 to 1, measured in the
+    /// given space, from `n` samples. See [`GradientColorMap::arc_length`] for more.
+    ///
+    /// [`GradientColorMap::arc_length`]: struct.GradientColorMap.html#method.arc_length
+    pub fn arc_length(&self, space: InterpSpace, n: usize) -> f64 {
+        arc_length::<RGBColor, _>(self, space, n)
+    }
+    /// Precomputes the colormap at `resolution` evenly-spaced points from 0 to 1, including both
+    /// endpoints, and returns a [`LutColorMap`] that looks colors up from that table in constant
+    /// time instead of interpolating the original list on every call. For 8-bit data, a resolution
+    /// of 256 gives every possible input its own entry. The returned map does nearest-entry lookup:
+    /// set its `interpolate` field to mix linearly between neighboring entries instead.
+    ///
+    /// # Panics
+    /// Panics if `resolution` is less than 2.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// let cmap = ListedColorMap::viridis();
+    /// let lut = cmap.build_lut(256);
+    /// let exact: RGBColor = cmap.transform_single(100. / 255.);
+    /// let fast: RGBColor = lut.transform_single(100. / 255.);
+    /// assert_eq!(exact.to_string(), fast.to_string());
+    /// ```
+    ///
+    /// [`LutColorMap`]: struct.LutColorMap.html
+    pub fn build_lut(&self, resolution: usize) -> LutColorMap {
+        assert!(resolution >= 2, "A LUT needs at least 2 entries.");
+        let colors = (0..resolution)
+            .map(|i| {
+                ColorMap::<RGBColor>::transform_single(self, i as f64 / (resolution - 1) as f64)
+            })
+            .collect();
+        LutColorMap {
+            colors,
+            interpolate: false,
+        }
+    }
     // TODO: In the future, I'd like to remove this weird array type bound if possible
     /// Initializes a ListedColorMap from an iterator of arrays [R, G, B].
     pub fn new<T: Iterator<Item = [f64; 3]>>(vals: T) -> ListedColorMap {
@@ -185,6 +1506,74 @@ impl ListedColorMap {
             vals: vals.collect(),
         }
     }
+    /// Initializes a ListedColorMap from a list of hex codes, parsed with
+    /// [`RGBColor::from_hex_code`]. If any of the codes is invalid, returns the first error
+    /// encountered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// let cmap = ListedColorMap::from_hex_codes(&["#000000", "#ff8000"]).unwrap();
+    /// let end: RGBColor = cmap.transform_single(1.);
+    /// assert_eq!(end.to_string(), "#FF8000");
+    /// assert!(ListedColorMap::from_hex_codes(&["#000000", "#ff80"]).is_err());
+    /// ```
+    ///
+    /// [`RGBColor::from_hex_code`]: ../color/struct.RGBColor.html#method.from_hex_code
+    pub fn from_hex_codes(codes: &[&str]) -> Result<ListedColorMap, RGBParseError> {
+        let vals = codes
+            .iter()
+            .map(|code| RGBColor::from_hex_code(code).map(|rgb| [rgb.r, rgb.g, rgb.b]))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ListedColorMap { vals })
+    }
+    /// Reads a ListedColorMap from CSV data with one color per row, as three comma-separated
+    /// floats between 0 and 1 for red, green, and blue, the format that matplotlib and colorcet
+    /// colormaps are usually dumped to. There should be no header row. Whitespace around values is
+    /// ignored, as are lines starting with `#`.
+    /// # Errors
+    /// Returns an error if reading fails, if a row doesn't have exactly three numbers, or if there
+    /// are no rows at all. Invalid data has the kind `io::ErrorKind::InvalidData`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colormap::{ColorMap, ListedColorMap};
+    /// let data = "0.0, 0.0, 0.0\n1.0, 0.5, 0.0\n";
+    /// let cmap = ListedColorMap::from_csv_reader(data.as_bytes()).unwrap();
+    /// let end: RGBColor = cmap.transform_single(1.);
+    /// assert_eq!(end.to_string(), "#FF8000");
+    /// ```
+    pub fn from_csv_reader<R: io::Read>(reader: R) -> io::Result<ListedColorMap> {
+        let vals = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .trim(csv::Trim::All)
+            .comment(Some(b'#'))
+            .from_reader(reader)
+            .deserialize()
+            .collect::<Result<Vec<[f64; 3]>, _>>()
+            .map_err(|err| {
+                if !err.is_io_error() {
+                    return io::Error::new(io::ErrorKind::InvalidData, err);
+                }
+                // pass errors from the reader itself through unchanged
+                match err.into_kind() {
+                    csv::ErrorKind::Io(err) => err,
+                    _ => unreachable!(),
+                }
+            })?;
+        if vals.is_empty() {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "colormap CSV has no colors",
+            ))
+        } else {
+            Ok(ListedColorMap { vals })
+        }
+    }
     /// Initializes a viridis colormap, a pleasing blue-green-yellow colormap that is perceptually
     /// uniform with respect to luminance, found in Python's `matplotlib` as the default
     /// colormap.
@@ -271,11 +1660,297 @@ impl ListedColorMap {
     }
 }
 
+/// A colormap backed by a precomputed lookup table of colors at evenly-spaced points from 0 to 1,
+/// usually made with [`ListedColorMap::build_lut`]. Each lookup is a single index computation, which
+/// makes this much faster than the map it came from when transforming lots of values, like every
+/// pixel of an image. Values outside of 0 to 1 are clamped.
+///
+/// [`ListedColorMap::build_lut`]: struct.ListedColorMap.html#method.build_lut
+#[derive(Debug, Clone)]
+pub struct LutColorMap {
+    /// The table of colors, with the first at 0 and the last at 1. This is never empty.
+    colors: Vec<RGBColor>,
+    /// If `true`, inputs between two entries are mixed linearly in sRGB from both of them, which
+    /// matches a [`ListedColorMap`] more closely. If `false`, the nearest entry is used as is.
+    ///
+    /// [`ListedColorMap`]: struct.ListedColorMap.html
+    pub interpolate: bool,
+}
+
+impl<T: Color> ColorMap<T> for LutColorMap {
+    fn transform_single(&self, x: f64) -> T {
+        let x = nan_as_zero(x);
+        let last = self.colors.len() - 1;
+        // a single entry covers the whole range
+        if last == 0 {
+            return T::from_rgb(self.colors[0]);
+        }
+        let float_ind = x.clamp(0., 1.) * last as f64;
+        if !self.interpolate {
+            return T::from_rgb(self.colors[float_ind.round() as usize]);
+        }
+        let ind = (float_ind.floor() as usize).min(last - 1);
+        let weight = float_ind - ind as f64;
+        let (c1, c2) = (self.colors[ind], self.colors[ind + 1]);
+        T::from_rgb(RGBColor {
+            r: c1.r + (c2.r - c1.r) * weight,
+            g: c1.g + (c2.g - c1.g) * weight,
+            b: c1.b + (c2.b - c1.b) * weight,
+        })
+    }
+}
+
+impl LutColorMap {
+    /// Initializes a LutColorMap from a table of colors, with the first at 0 and the last at 1. A
+    /// table with a single color maps every input to that color.
+    /// # Errors
+    /// Returns `ColorMapError::Empty` if there are no colors.
+    pub fn new(colors: Vec<RGBColor>, interpolate: bool) -> Result<LutColorMap, ColorMapError> {
+        if colors.is_empty() {
+            Err(ColorMapError::Empty)
+        } else {
+            Ok(LutColorMap {
+                colors,
+                interpolate,
+            })
+        }
+    }
+    /// The table of colors, with the first at 0 and the last at 1.
+    pub fn colors(&self) -> &[RGBColor] {
+        &self.colors
+    }
+}
+
+/// A colormap for categorical data: a fixed list of distinct colors, with no interpolation between
+/// them. The range from 0 to 1 is split into as many equal bins as there are colors, and each input
+/// maps to the color of the bin it falls in, so with four colors 0.3 maps to the second. Values
+/// outside of 0 to 1 are clamped. The list of colors must not be empty.
+#[derive(Debug, Clone)]
+pub struct CategoricalColorMap {
+    /// The list of colors, in the order they are assigned to bins.
+    pub colors: Vec<RGBColor>,
+}
+
+impl<T: Color> ColorMap<T> for CategoricalColorMap {
+    fn transform_single(&self, x: f64) -> T {
+        let x = nan_as_zero(x);
+        let clamped = x.clamp(0., 1.);
+        let n = self.colors.len();
+        // 1 would be its own bin otherwise
+        let ind = ((clamped * n as f64).floor() as usize).min(n - 1);
+        self.colors[ind].convert()
+    }
+}
+
+impl CategoricalColorMap {
+    /// Initializes a CategoricalColorMap from an iterator of colors.
+    /// # Errors
+    /// Returns `ColorMapError::Empty` if there are no colors.
+    pub fn new<T: Iterator<Item = RGBColor>>(
+        colors: T,
+    ) -> Result<CategoricalColorMap, ColorMapError> {
+        let colors: Vec<RGBColor> = colors.collect();
+        if colors.is_empty() {
+            Err(ColorMapError::Empty)
+        } else {
+            Ok(CategoricalColorMap { colors })
+        }
+    }
+    /// Initializes a CategoricalColorMap from integers packed as `0xRRGGBB`, with red in the
+    /// highest byte used and blue in the lowest, as used by many game and retro palettes. See
+    /// [`RGBColor::from_u32`] for more.
+    /// # Errors
+    /// Returns `ColorMapError::Empty` if there are no colors.
+    ///
+    /// [`RGBColor::from_u32`]: ../color/struct.RGBColor.html#method.from_u32
+    pub fn from_u32s(vals: &[u32]) -> Result<CategoricalColorMap, ColorMapError> {
+        CategoricalColorMap::new(vals.iter().map(|&val| RGBColor::from_u32(val)))
+    }
+}
+
+/// A colormap for two variables at once, such as income and population density on a choropleth
+/// map: each pair of inputs `(x, y)` between 0 and 1 maps to a color. The colors at the four
+/// corners are given, and everything in between is mixed bilinearly from them in the coordinate
+/// space of `T`, so a perceptually uniform space like CIELAB gives smooth results. Out-of-range
+/// inputs are clamped. Because it takes two inputs, this isn't a [`ColorMap`].
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colormap::BivariateColorMap;
+/// # use scarlet::colors::CIELABColor;
+/// let corner = |hex| RGBColor::from_hex_code(hex).unwrap().convert::<CIELABColor>();
+/// let map = BivariateColorMap::new(
+///     corner("#e8e8e8"),
+///     corner("#c85a5a"),
+///     corner("#64acbe"),
+///     corner("#574249"),
+/// );
+/// let legend = map.legend_grid(3);
+/// assert_eq!(legend[0][0].to_string(), "#E8E8E8");
+/// assert_eq!(legend[2][2].to_string(), "#574249");
+/// ```
+///
+/// [`ColorMap`]: trait.ColorMap.html
+#[derive(Debug, Clone)]
+pub struct BivariateColorMap<T: ColorPoint> {
+    /// The color where both inputs are 0.
+    pub low_low: T,
+    /// The color where `x` is 1 and `y` is 0.
+    pub high_low: T,
+    /// The color where `x` is 0 and `y` is 1.
+    pub low_high: T,
+    /// The color where both inputs are 1.
+    pub high_high: T,
+}
+
+impl<T: ColorPoint> BivariateColorMap<T> {
+    /// Constructs a new [`BivariateColorMap`] from the colors at its four corners.
+    pub fn new(low_low: T, high_low: T, low_high: T, high_high: T) -> BivariateColorMap<T> {
+        BivariateColorMap {
+            low_low,
+            high_low,
+            low_high,
+            high_high,
+        }
+    }
+    /// Gets the color for the given pair of inputs, clamping each to the range 0 to 1.
+    pub fn transform_pair(&self, x: f64, y: f64) -> T {
+        let (x, y) = (x.clamp(0., 1.), y.clamp(0., 1.));
+        let low = self.high_low.weighted_midpoint(self.low_low, x);
+        let high = self.high_high.weighted_midpoint(self.low_high, x);
+        high.weighted_midpoint(low, y)
+    }
+    /// Samples the map on an `n` by `n` grid of evenly-spaced points including the corners, as sRGB
+    /// colors, for drawing the square legend that usually goes with a bivariate map. Row `i` and
+    /// column `j` of the grid is the color for `x = j / (n - 1)` and `y = i / (n - 1)`, so the first
+    /// row has `y` at 0: reverse the rows to draw `y` increasing upwards. As with
+    /// [`ColorMap::sample`], an `n` of 1 gives the center of the map and an `n` of 0 gives an empty
+    /// grid.
+    ///
+    /// [`ColorMap::sample`]: trait.ColorMap.html#method.sample
+    pub fn legend_grid(&self, n: usize) -> Vec<Vec<RGBColor>> {
+        let coord = |i: usize| {
+            if n == 1 {
+                0.5
+            } else {
+                i as f64 / (n - 1) as f64
+            }
+        };
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| self.transform_pair(coord(j), coord(i)).convert())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Interpolates between two colormaps, producing a new [`ListedColorMap`] that is `t` of the way
+/// from `a` to `b`. Both maps are sampled with [`ColorMap::sample`], taking at least 2 samples, and
+/// each pair of samples is mixed in CIELAB so that the transition is perceptually
+/// smooth. A `t` of 0 reproduces `a` at the sampled points, and a `t` of 1 reproduces `b`. This is
+/// useful for animating a plot's colormap changing over time.
+///
+/// [`ColorMap::sample`]: trait.ColorMap.html#method.sample
+pub fn lerp_colormaps<A: ColorMap<RGBColor>, B: ColorMap<RGBColor>>(
+    a: &A,
+    b: &B,
+    t: f64,
+    n: usize,
+) -> ListedColorMap {
+    let n = n.max(2);
+    ListedColorMap::new(
+        a.sample(n)
+            .into_iter()
+            .zip(b.sample(n))
+            .map(|(col_a, col_b)| {
+                let lab_a: CIELABColor = col_a.convert();
+                let lab_b: CIELABColor = col_b.convert();
+                let rgb: RGBColor = lab_b.weighted_midpoint(lab_a, t).convert();
+                [rgb.r, rgb.g, rgb.b]
+            }),
+    )
+}
+
+/// Renders a grid of data values between 0 and 1 through a colormap, returning an 8-bit RGB image
+/// from the `image` crate that can be saved as a PNG or processed further. `data` holds
+/// `width * height` values, row by row from the top left, and each pixel is the colormap's color
+/// for the matching value, rounded as by [`RGBColor::int_rgb_tup`]. Requires the `image` feature.
+///
+/// # Panics
+///
+/// Panics if `data` doesn't have exactly `width * height` values.
+///
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "image")]
+/// # {
+/// # use scarlet::colormap::{colormap_to_rgb_image, ListedColorMap};
+/// let data = [0., 0.5, 0.5, 1.];
+/// let img = colormap_to_rgb_image(&ListedColorMap::viridis(), &data, 2, 2);
+/// assert_eq!(img.dimensions(), (2, 2));
+/// // viridis starts out dark purple
+/// assert_eq!(img.get_pixel(0, 0).0, [68, 1, 84]);
+/// # }
+/// ```
+///
+/// [`RGBColor::int_rgb_tup`]: ../color/struct.RGBColor.html#method.int_rgb_tup
+#[cfg(feature = "image")]
+pub fn colormap_to_rgb_image<M: ColorMap<RGBColor>>(
+    map: &M,
+    data: &[f64],
+    width: u32,
+    height: u32,
+) -> image::RgbImage {
+    assert_eq!(
+        data.len(),
+        width as usize * height as usize,
+        "data must have width * height values"
+    );
+    image::RgbImage::from_fn(width, height, |x, y| {
+        let (r, g, b) = map
+            .transform_single(data[(y * width + x) as usize])
+            .int_rgb_tup();
+        image::Rgb([r, g, b])
+    })
+}
+
+/// Writes a colormap as a CSS `linear-gradient`, running from left to right, for using it in
+/// stylesheets. The colormap is sampled with [`ColorMap::sample`], taking at least 2 samples since
+/// CSS needs a stop at each end, and each sample becomes a color stop with its hex code and its
+/// position as a percentage, rounded to two decimal places. Browsers interpolate between stops in
+/// gamma-encoded sRGB, so for colormaps that aren't straight lines in sRGB, more stops give a
+/// closer match.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::colormap::{to_css_gradient, ListedColorMap};
+/// let css = to_css_gradient(&ListedColorMap::viridis(), 3);
+/// assert_eq!(css, "linear-gradient(to right, #440154 0%, #21908C 50%, #FDE725 100%)");
+/// ```
+///
+/// [`ColorMap::sample`]: trait.ColorMap.html#method.sample
+pub fn to_css_gradient<M: ColorMap<RGBColor>>(map: &M, stops: usize) -> String {
+    let n = stops.max(2);
+    let stops: Vec<String> = sample_points(n)
+        .into_iter()
+        .zip(map.sample(n))
+        .map(|(x, color)| format!("{} {}%", color, (x * 10000.).round() / 100.))
+        .collect();
+    format!("linear-gradient(to right, {})", stops.join(", "))
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
     use color::RGBColor;
+    use colors::CIELCHColor;
 
     #[test]
     fn test_linear_gradient() {
@@ -306,6 +1981,65 @@ mod tests {
         }
     }
     #[test]
+    fn test_in_space() {
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let rgb_mix = GradientColorMap::new_linear(red, white);
+        let lab_mix = GradientColorMap::new_linear(red, white).in_space::<CIELABColor>();
+        for &x in [0., 1.].iter() {
+            let c1 = rgb_mix.transform_single(x);
+            let c2 = lab_mix.transform_single(x);
+            assert!(c1.distance(&c2) <= 1e-6);
+        }
+        // through CIELAB, the midpoint is exactly halfway in lightness, while the sRGB blend is
+        // noticeably darker
+        let red_lab: CIELABColor = red.convert();
+        let white_lab: CIELABColor = white.convert();
+        let rgb_mid: CIELABColor = rgb_mix.transform_single(0.5).convert();
+        let lab_mid: CIELABColor = lab_mix.transform_single(0.5).convert();
+        assert!((lab_mid.l - (red_lab.l + white_lab.l) / 2.).abs() <= 1e-6);
+        assert!(rgb_mid.l < lab_mid.l - 5.);
+        // mixing in a different space also moves the hue
+        assert!((rgb_mid.hue() - lab_mid.hue()).abs() > 10.);
+    }
+    #[test]
+    fn test_normalize_mappings() {
+        let mappings = [
+            NormalizeMapping::Sqrt,
+            NormalizeMapping::Power(0.5),
+            NormalizeMapping::Power(2.2),
+            NormalizeMapping::Log,
+        ];
+        for mapping in mappings.iter() {
+            assert!(mapping.normalize(0.).abs() <= 1e-12);
+            assert!((mapping.normalize(1.) - 1.).abs() <= 1e-12);
+            let mut prev = mapping.normalize(0.);
+            for i in 1..=100 {
+                let curr = mapping.normalize(i as f64 / 100.);
+                assert!(curr > prev);
+                assert!((0. ..=1.).contains(&curr));
+                prev = curr;
+            }
+        }
+        assert!((NormalizeMapping::Sqrt.normalize(0.25) - 0.5).abs() <= 1e-12);
+        assert!((NormalizeMapping::Power(2.).normalize(0.5) - 0.25).abs() <= 1e-12);
+        // bad exponents still keep the endpoints where they belong
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        for &exp in [0., -1., -0.5, f64::NAN, f64::NEG_INFINITY].iter() {
+            let mapping = NormalizeMapping::Power(exp);
+            assert_eq!(mapping.normalize(0.), 0.);
+            assert_eq!(mapping.normalize(1.), 1.);
+            assert!((0. ..=1.).contains(&mapping.normalize(0.5)));
+            let mut cmap = GradientColorMap::new_linear(black, white);
+            cmap.normalization = mapping;
+            assert_eq!(cmap.transform_single(0.).to_string(), "#000000");
+            assert_eq!(cmap.transform_single(1.).to_string(), "#FFFFFF");
+        }
+        // each decade gets about a third of the range
+        assert!((NormalizeMapping::Log.normalize(0.1) - 2. / 3.).abs() <= 0.01);
+    }
+    #[test]
     fn test_padding() {
         let red = RGBColor::from_hex_code("#CC0000").unwrap();
         let blue = RGBColor::from_hex_code("#0000CC").unwrap();
@@ -322,6 +2056,986 @@ mod tests {
         }
     }
     #[test]
+    fn test_neutral_point() {
+        let bwr = ListedColorMap::new(vec![[0., 0., 1.], [1., 1., 1.], [1., 0., 0.]].into_iter());
+        let neutral = ColorMap::<RGBColor>::neutral_point(&bwr, 101);
+        assert!((neutral - 0.5).abs() <= 1e-10);
+        // the white center has been pushed to a quarter of the way through
+        let skewed = ListedColorMap::new(
+            vec![
+                [0., 0., 1.],
+                [1., 1., 1.],
+                [1., 0., 0.],
+                [1., 0., 0.],
+                [1., 0., 0.],
+            ]
+            .into_iter(),
+        );
+        let neutral = ColorMap::<RGBColor>::neutral_point(&skewed, 101);
+        assert!((neutral - 0.25).abs() <= 1e-10);
+    }
+    #[test]
+    fn test_effective_levels_8bit() {
+        let gray = |hex: &str| RGBColor::from_hex_code(hex).unwrap();
+        // black to white spans 100 units of lightness, so there are dozens of usable levels
+        let contrast = GradientColorMap::new_linear(gray("#000000"), gray("#ffffff"));
+        let contrast_levels = ColorMap::<RGBColor>::effective_levels_8bit(&contrast);
+        assert!(contrast_levels > 30);
+        assert!(contrast_levels <= 256);
+        // two nearby blue-grays of the same lightness can barely be told apart
+        let flat = GradientColorMap::new_linear(gray("#7a7a88"), gray("#7c7a84"));
+        let flat_levels = ColorMap::<RGBColor>::effective_levels_8bit(&flat);
+        assert!(flat_levels < 5);
+        assert!(contrast_levels > flat_levels);
+        // a constant map has exactly one level
+        let constant = GradientColorMap::new_linear(gray("#336699"), gray("#336699"));
+        assert_eq!(ColorMap::<RGBColor>::effective_levels_8bit(&constant), 1);
+        // perceptually uniform maps do well
+        assert!(ColorMap::<RGBColor>::effective_levels_8bit(&ListedColorMap::viridis()) > 30);
+    }
+    #[test]
+    fn test_rotate_hue() {
+        let blues = GradientColorMap::new_linear(
+            RGBColor::from_hex_code("#005a80").unwrap(),
+            RGBColor::from_hex_code("#90d0f0").unwrap(),
+        );
+        let rotated = ColorMap::<RGBColor>::rotate_hue(&blues, 180., 32);
+        assert_eq!(rotated.vals.len(), 32);
+        for (i, orig) in ColorMap::<RGBColor>::sample(&blues, 32).iter().enumerate() {
+            let new: RGBColor = ColorMap::<RGBColor>::transform_single(&rotated, i as f64 / 31.);
+            // lightness is unchanged...
+            assert!((new.lightness() - orig.lightness()).abs() <= 1e-6);
+            // ...but the hue is now orange
+            let h = new.convert::<CIELCHColor>().h;
+            assert!((20. ..=90.).contains(&h), "hue {} is not orange", h);
+            let expected = (orig.convert::<CIELCHColor>().h + 180.).rem_euclid(360.);
+            assert!((h - expected).abs() <= 1e-6);
+            // every sample fits in sRGB
+            for x in [new.r, new.g, new.b].iter() {
+                assert!((-1e-9..=1. + 1e-9).contains(x));
+            }
+        }
+        // rotating all the way around gives back the same map
+        let same = ColorMap::<RGBColor>::rotate_hue(&ListedColorMap::viridis(), 360., 16);
+        let orig = ColorMap::<RGBColor>::sample(&ListedColorMap::viridis(), 16);
+        for (i, color) in orig.iter().enumerate() {
+            let new: RGBColor = ColorMap::<RGBColor>::transform_single(&same, i as f64 / 15.);
+            assert!(new.distance(color) <= 1e-6);
+        }
+    }
+    #[test]
+    fn test_bivariate_legend_grid() {
+        let corner = |hex: &str| RGBColor::from_hex_code(hex).unwrap();
+        let corners = [
+            corner("#e8e8e8"),
+            corner("#be64ac"),
+            corner("#5ac8c8"),
+            corner("#3b4994"),
+        ];
+        let map = BivariateColorMap::new(corners[0], corners[1], corners[2], corners[3]);
+        let grid = map.legend_grid(4);
+        assert_eq!(grid.len(), 4);
+        assert!(grid.iter().all(|row| row.len() == 4));
+        // the corners of the grid are the corners of the map
+        assert_eq!(grid[0][0].to_string(), corners[0].to_string());
+        assert_eq!(grid[0][3].to_string(), corners[1].to_string());
+        assert_eq!(grid[3][0].to_string(), corners[2].to_string());
+        assert_eq!(grid[3][3].to_string(), corners[3].to_string());
+        // the middle mixes all four equally
+        let center = &map.legend_grid(1)[0][0];
+        let mean = |f: fn(&RGBColor) -> f64| corners.iter().map(f).sum::<f64>() / 4.;
+        assert!((center.r - mean(|c| c.r)).abs() <= 1e-10);
+        assert!((center.g - mean(|c| c.g)).abs() <= 1e-10);
+        assert!((center.b - mean(|c| c.b)).abs() <= 1e-10);
+        assert!(map.legend_grid(0).is_empty());
+        // inputs are clamped
+        assert_eq!(
+            map.transform_pair(-1., 2.).to_string(),
+            corners[2].to_string()
+        );
+    }
+    #[test]
+    fn test_sorted_lut() {
+        let cmap = ListedColorMap::viridis();
+        let breaks = [0.1, 0.2, 0.6, 0.9];
+        let lut = ColorMap::<RGBColor>::sorted_lut(&cmap, &breaks);
+        assert_eq!(lut.len(), breaks.len() + 1);
+        // each class is colored by its midpoint
+        let mids = [0.05, 0.15, 0.4, 0.75, 0.95];
+        for (color, &mid) in lut.iter().zip(mids.iter()) {
+            let expected: RGBColor = cmap.transform_single(mid);
+            assert!(color.distance(&expected) <= 1e-10);
+        }
+        // binary searching finds the right class
+        for &(x, class) in [(0., 0), (0.1, 1), (0.15, 1), (0.59, 2), (0.6, 3), (1., 4)].iter() {
+            assert_eq!(breaks.partition_point(|&t| t <= x), class);
+        }
+        // no breaks means a single class colored by the middle of the map
+        let single = ColorMap::<RGBColor>::sorted_lut(&cmap, &[]);
+        assert_eq!(single.len(), 1);
+        let middle: RGBColor = cmap.transform_single(0.5);
+        assert!(single[0].distance(&middle) <= 1e-10);
+    }
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_transform_par() {
+        fn check<M: ColorMap<RGBColor> + Sync>(cmap: &M, data: &[f64]) {
+            let serial = cmap.transform(data.iter().cloned());
+            let parallel = cmap.transform_par(data.par_iter().cloned());
+            assert_eq!(serial.len(), parallel.len());
+            for (a, b) in serial.iter().zip(parallel.iter()) {
+                assert_eq!((a.r, a.g, a.b), (b.r, b.g, b.b));
+            }
+        }
+        let data: Vec<f64> = (0..10_007).map(|i| (i as f64 * 0.618).fract()).collect();
+        let listed = ListedColorMap::viridis();
+        let gradient = GradientColorMap::new_linear(
+            RGBColor::from_hex_code("#102040").unwrap(),
+            RGBColor::from_hex_code("#f0e0a0").unwrap(),
+        );
+        check(&listed, &data);
+        check(&gradient, &data);
+        // small and empty inputs work too
+        check(&listed, &data[..1]);
+        check(&listed, &data[..3]);
+        check(&listed, &[]);
+    }
+    #[test]
+    fn test_lut_colormap() {
+        let cmap = ListedColorMap::magma();
+        let nearest = cmap.build_lut(256);
+        let mut interpolated = cmap.build_lut(64);
+        interpolated.interpolate = true;
+        // largest difference in any channel, in 8-bit units
+        let diff = |a: RGBColor, b: RGBColor| {
+            [a.r - b.r, a.g - b.g, a.b - b.b]
+                .iter()
+                .fold(0.0f64, |m, d| m.max(d.abs() * 255.))
+        };
+        for i in 0..=1000 {
+            let x = i as f64 / 1000.;
+            let exact: RGBColor = cmap.transform_single(x);
+            assert!(diff(exact, nearest.transform_single(x)) <= 2.);
+            assert!(diff(exact, interpolated.transform_single(x)) <= 1.);
+        }
+        // 8-bit inputs land exactly on entries
+        for i in 0..256 {
+            let x = i as f64 / 255.;
+            let exact: RGBColor = cmap.transform_single(x);
+            let near: RGBColor = nearest.transform_single(x);
+            assert_eq!(exact.to_string(), near.to_string());
+        }
+        // clamping
+        let low: RGBColor = interpolated.transform_single(-1.);
+        let high: RGBColor = interpolated.transform_single(2.);
+        assert_eq!(low.to_string(), "#000004");
+        assert_eq!(high.to_string(), "#FCFDBF");
+    }
+    #[test]
+    fn test_lut_colormap_short_tables() {
+        assert_eq!(
+            LutColorMap::new(vec![], false).unwrap_err(),
+            ColorMapError::Empty
+        );
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        for &interpolate in [false, true].iter() {
+            let lut = LutColorMap::new(vec![red], interpolate).unwrap();
+            assert_eq!(lut.colors().len(), 1);
+            for &x in [-1., 0., 0.5, 1., 2., f64::NAN].iter() {
+                let color: RGBColor = lut.transform_single(x);
+                assert_eq!(color.to_string(), "#FF0000");
+            }
+        }
+    }
+    #[test]
+    #[ignore]
+    fn lut_benchmark() {
+        use std::time::Instant;
+        let cmap = ListedColorMap::viridis();
+        let lut = cmap.build_lut(256);
+        let data: Vec<f64> = (0..1_000_000).map(|i| (i % 256) as f64 / 255.).collect();
+        let start = Instant::now();
+        let exact: Vec<RGBColor> = cmap.transform(data.iter().cloned());
+        let exact_time = start.elapsed();
+        let start = Instant::now();
+        let fast: Vec<RGBColor> = lut.transform(data.iter().cloned());
+        let lut_time = start.elapsed();
+        println!(
+            "ListedColorMap: {:?}\nLutColorMap: {:?}",
+            exact_time, lut_time
+        );
+        assert_eq!(exact.len(), fast.len());
+    }
+    #[test]
+    fn test_listed_rgb_fast_path() {
+        // the generic path: build the color in sRGB, then convert through XYZ
+        let generic = |cmap: &ListedColorMap, x: f64| -> RGBColor {
+            let rgb: RGBColor = cmap.transform_single(x);
+            rgb.convert()
+        };
+        for cmap in [
+            ListedColorMap::viridis(),
+            ListedColorMap::turbo(),
+            ListedColorMap::hell(),
+        ]
+        .iter()
+        {
+            for i in 0..=1000 {
+                let x = i as f64 / 1000.;
+                let fast: RGBColor = cmap.transform_single(x);
+                let slow = generic(cmap, x);
+                assert_eq!(fast.int_rgb_tup(), slow.int_rgb_tup());
+                assert!((fast.r - slow.r).abs() <= 1e-12);
+                assert!((fast.g - slow.g).abs() <= 1e-12);
+                assert!((fast.b - slow.b).abs() <= 1e-12);
+            }
+        }
+    }
+    #[test]
+    fn test_fit_score() {
+        // two clusters, one around 0.3 and one around 0.7
+        let data: Vec<f64> = (0..50)
+            .flat_map(|i| {
+                let offset = (i as f64 / 50. - 0.5) * 0.1;
+                vec![0.3 + offset, 0.7 + offset]
+            })
+            .collect();
+        // a diverging map puts contrast on both sides of the center, where the data is
+        let diverging = ColorMap::<RGBColor>::fit_score(&ListedColorMap::bluered(), &data);
+        let sequential = ColorMap::<RGBColor>::fit_score(&ListedColorMap::viridis(), &data);
+        assert!(diverging > sequential);
+        // a single occupied bin has nothing to contrast against
+        let constant = ColorMap::<RGBColor>::fit_score(&ListedColorMap::viridis(), &[0.5; 10]);
+        assert_eq!(constant, 0.);
+    }
+    #[test]
+    fn test_transform_iter() {
+        // yields the numbers from 0 to 1 in steps of 0.1, and panics if asked for more than that
+        struct Limited(u32);
+        impl Iterator for Limited {
+            type Item = f64;
+            fn next(&mut self) -> Option<f64> {
+                if self.0 > 10 {
+                    panic!("iterator was consumed past its limit");
+                }
+                self.0 += 1;
+                Some((self.0 - 1) as f64 / 10.)
+            }
+        }
+        let viridis = ListedColorMap::viridis();
+        let lazy: Vec<RGBColor> = viridis.transform_iter(Limited(0)).take(5).collect();
+        // borrowed inputs work too
+        let inputs = [0.5, 1.];
+        let borrowed: Vec<RGBColor> = viridis.transform_iter(inputs.iter().cloned()).collect();
+        assert_eq!(borrowed, viridis.transform(inputs.iter().cloned()));
+        let eager: Vec<RGBColor> = viridis.transform(vec![0., 0.1, 0.2, 0.3, 0.4]);
+        assert_eq!(lazy, eager);
+    }
+    #[test]
+    fn test_as_fn() {
+        let viridis = ListedColorMap::viridis();
+        let f = ColorMap::<RGBColor>::as_fn(&viridis);
+        for i in -10..=110 {
+            let x = i as f64 / 100.;
+            let direct: RGBColor = viridis.transform_single(x);
+            assert_eq!(f(x), direct);
+        }
+    }
+    #[test]
+    fn test_hue_extrema() {
+        // sweeps back and forth through reds twice, crossing hue 0 each time
+        let vals = (0..=256).map(|i| {
+            let x = i as f64 / 256.;
+            let hue = 360. + 40. * (4. * std::f64::consts::PI * x).sin();
+            let rgb: RGBColor = CIELCHColor {
+                l: 55.,
+                c: 40.,
+                h: hue.rem_euclid(360.),
+            }
+            .convert();
+            [rgb.r, rgb.g, rgb.b]
+        });
+        let cmap = ListedColorMap::new(vals);
+        let extrema = ColorMap::<RGBColor>::hue_extrema(&cmap, 257);
+        assert_eq!(extrema.len(), 4);
+        for (x, expected) in extrema.iter().zip([0.125, 0.375, 0.625, 0.875].iter()) {
+            assert!((x - expected).abs() <= 0.01);
+        }
+        // viridis only moves one way in hue
+        assert!(ColorMap::<RGBColor>::hue_extrema(&ListedColorMap::viridis(), 100).is_empty());
+    }
+    #[test]
+    fn test_dither_to_bits() {
+        let viridis = ListedColorMap::viridis();
+        let n = 256;
+        let truth: Vec<RGBColor> = viridis.sample(n);
+        let dithered = ColorMap::<RGBColor>::dither_to_bits(&viridis, 2, n);
+        assert_eq!(dithered.len(), n);
+        // every channel is one of the 4 levels 2 bits allow
+        for c in dithered.iter() {
+            for &val in [c.r, c.g, c.b].iter() {
+                assert!(((val * 3.).round() - val * 3.).abs() <= 1e-10);
+            }
+        }
+        let naive: Vec<RGBColor> = truth
+            .iter()
+            .map(|c| RGBColor {
+                r: (c.r * 3.).round() / 3.,
+                g: (c.g * 3.).round() / 3.,
+                b: (c.b * 3.).round() / 3.,
+            })
+            .collect();
+        // compare averages over short stretches of the strip, the way the eye blurs them
+        let window_error = |strip: &[RGBColor]| -> f64 {
+            let mut total = 0.;
+            for (win, true_win) in strip.chunks(8).zip(truth.chunks(8)) {
+                let mean = |cs: &[RGBColor], f: fn(&RGBColor) -> f64| {
+                    cs.iter().map(f).sum::<f64>() / cs.len() as f64
+                };
+                for &f in [|c: &RGBColor| c.r, |c: &RGBColor| c.g, |c: &RGBColor| c.b].iter() {
+                    total += (mean(win, f) - mean(true_win, f)).abs();
+                }
+            }
+            total
+        };
+        assert!(window_error(&dithered) < window_error(&naive) / 2.);
+    }
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_save_lut_png() {
+        let viridis = ListedColorMap::viridis();
+        let path = std::env::temp_dir().join("scarlet_test_viridis_lut.png");
+        let path = path.to_str().unwrap();
+        ColorMap::<RGBColor>::save_lut_png(&viridis, path, 16).unwrap();
+        let img = image::open(path).unwrap().to_rgb8();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(img.dimensions(), (16, 1));
+        let pixels = img.into_raw();
+        let start: RGBColor = viridis.transform_single(0.);
+        let end: RGBColor = viridis.transform_single(1.);
+        assert_eq!(pixels[..3], [start.int_r(), start.int_g(), start.int_b()]);
+        assert_eq!(pixels[45..], [end.int_r(), end.int_g(), end.int_b()]);
+        assert!(ColorMap::<RGBColor>::save_lut_png(&viridis, path, 0).is_err());
+    }
+    #[test]
+    fn test_perceptual_speed_histogram() {
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let cbrt = GradientColorMap::new_cbrt(black, white);
+        let speeds = cbrt.perceptual_speed_histogram(10);
+        assert_eq!(speeds.len(), 10);
+        // the cube root races through the low end and slows down after
+        assert!(speeds[0] > 2. * speeds[9]);
+        for pair in speeds.windows(2) {
+            assert!(pair[0] > pair[1]);
+        }
+        // the speeds of the bins add up to the total distance traveled, times the number of bins
+        let total: f64 = speeds.iter().sum::<f64>() / 10.;
+        assert!((total - cbrt.arc_length(InterpSpace::CIEDE2000, 81)).abs() <= 1e-6);
+        assert!(cbrt.perceptual_speed_histogram(0).is_empty());
+    }
+    #[test]
+    fn test_closest_input() {
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let gray = GradientColorMap::new_linear(black, white);
+        let mid = RGBColor::from_hex_code("#808080").unwrap();
+        assert!((gray.closest_input(&mid, 101) - 0.5).abs() <= 0.01);
+        assert_eq!(gray.closest_input(&white, 101), 1.);
+        // with only the endpoints, the answer is one of them
+        let dark = RGBColor::from_hex_code("#202020").unwrap();
+        assert_eq!(gray.closest_input(&dark, 2), 0.);
+        // a single sample is taken at 0.5, just like ColorMap::sample
+        assert_eq!(gray.closest_input(&dark, 1), 0.5);
+        assert_eq!(gray.closest_input(&dark, 0), 0.5);
+    }
+    #[test]
+    fn test_sample() {
+        let viridis = ListedColorMap::viridis();
+        let samples: Vec<RGBColor> = viridis.sample(5);
+        assert_eq!(samples.len(), 5);
+        let expected: Vec<RGBColor> = viridis.transform(vec![0., 0.25, 0.5, 0.75, 1.]);
+        assert_eq!(samples, expected);
+        let first: RGBColor = viridis.transform_single(0.);
+        let last: RGBColor = viridis.transform_single(1.);
+        assert_eq!(samples[0], first);
+        assert_eq!(samples[4], last);
+
+        let single: Vec<RGBColor> = viridis.sample(1);
+        let middle: RGBColor = viridis.transform_single(0.5);
+        assert_eq!(single, vec![middle]);
+        assert!(ColorMap::<RGBColor>::sample(&viridis, 0).is_empty());
+    }
+    #[test]
+    fn test_reversed() {
+        let viridis = ListedColorMap::viridis();
+        let viridis_r = ColorMap::<RGBColor>::reversed(ListedColorMap::viridis());
+        let vals = vec![-0.5, 0., 0.3, 0.5, 0.9, 1., 1.5];
+        for x in vals {
+            let col: RGBColor = viridis_r.transform_single(x);
+            let flipped: RGBColor = viridis.transform_single(1. - x);
+            assert_eq!(col, flipped);
+        }
+        let start: RGBColor = viridis_r.transform_single(0.);
+        let end: RGBColor = viridis.transform_single(1.);
+        assert_eq!(start, end);
+
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        let cmap = GradientColorMap::new_linear(red, blue).reversed();
+        assert_eq!(cmap.transform_single(0.).to_string(), "#0000FF");
+        assert_eq!(cmap.transform_single(0.8).to_string(), "#CC0033");
+        // reversing twice gets back the original
+        let cmap = cmap.reversed();
+        assert_eq!(cmap.transform_single(0.).to_string(), "#FF0000");
+    }
+    #[test]
+    fn test_mirrored() {
+        let viridis = ListedColorMap::viridis();
+        let mirrored = MirroredColorMap::new(ListedColorMap::viridis());
+        let center: RGBColor = mirrored.transform_single(0.5);
+        let start: RGBColor = viridis.transform_single(0.);
+        assert_eq!(center, start);
+        let end: RGBColor = viridis.transform_single(1.);
+        let low: RGBColor = mirrored.transform_single(0.);
+        let high: RGBColor = mirrored.transform_single(1.);
+        assert_eq!(low, end);
+        assert_eq!(high, end);
+        // each half covers the whole inner map
+        let quarter: RGBColor = mirrored.transform_single(0.25);
+        let three_quarters: RGBColor = mirrored.transform_single(0.75);
+        let half: RGBColor = viridis.transform_single(0.5);
+        assert_eq!(quarter, half);
+        assert_eq!(three_quarters, half);
+    }
+    #[test]
+    fn test_input_transform() {
+        use std::f64::consts::PI;
+        let viridis = ListedColorMap::viridis();
+        let warped = ColorMap::<RGBColor>::with_input_transform(ListedColorMap::viridis(), |x| {
+            (x * PI / 2.).sin()
+        });
+        for &x in [0., 0.1, 1. / 3., 0.5, 0.9, 1.].iter() {
+            let expected: RGBColor = viridis.transform_single((x * PI / 2.).sin());
+            let actual: RGBColor = warped.transform_single(x);
+            assert!(expected.distance(&actual) <= 1e-10);
+        }
+        // the sine warp runs ahead of the plain map everywhere in between
+        let plain: RGBColor = viridis.transform_single(0.5);
+        let fast: RGBColor = warped.transform_single(0.5);
+        assert!(fast.lightness() > plain.lightness());
+        // the transformed input is clamped
+        let doubled = WarpedColorMap::new(ListedColorMap::viridis(), |x: f64| 2. * x - 0.5);
+        let end: RGBColor = viridis.transform_single(1.);
+        let start: RGBColor = viridis.transform_single(0.);
+        assert_eq!(
+            ColorMap::<RGBColor>::transform_single(&doubled, 0.9).to_string(),
+            end.to_string()
+        );
+        assert_eq!(
+            ColorMap::<RGBColor>::transform_single(&doubled, 0.1).to_string(),
+            start.to_string()
+        );
+        // and it works with other combinators
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let cmap = GradientColorMap::new_linear(black, white)
+            .with_input_transform(|x| x * x)
+            .with_domain(0., 10.);
+        assert_eq!(cmap.transform_single(5.).to_string(), "#404040");
+    }
+    #[test]
+    fn test_nan_inputs() {
+        let nan = f64::NAN;
+        let same = |a: RGBColor, b: RGBColor| a.to_string() == b.to_string() && !a.r.is_nan();
+        let listed = [
+            ListedColorMap::viridis(),
+            ListedColorMap::magma(),
+            ListedColorMap::inferno(),
+            ListedColorMap::plasma(),
+            ListedColorMap::cividis(),
+            ListedColorMap::turbo(),
+            ListedColorMap::circle(),
+            ListedColorMap::bluered(),
+            ListedColorMap::breeze(),
+            ListedColorMap::mist(),
+            ListedColorMap::earth(),
+            ListedColorMap::hell(),
+        ];
+        for cmap in listed.iter() {
+            assert!(same(cmap.transform_single(nan), cmap.transform_single(0.)));
+            let mut lut = cmap.build_lut(16);
+            assert!(same(lut.transform_single(nan), lut.transform_single(0.)));
+            lut.interpolate = true;
+            assert!(same(lut.transform_single(nan), lut.transform_single(0.)));
+        }
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        let mut gradient = GradientColorMap::new_cbrt(red, blue);
+        gradient.padding = (0.2, 0.9);
+        assert!(same(
+            gradient.transform_single(nan),
+            gradient.transform_single(0.)
+        ));
+        let lab = GradientColorMap::new_linear(red, blue).in_space::<CIELABColor>();
+        assert!(same(lab.transform_single(nan), red));
+        let multi = MultiGradientColorMap::new(vec![(0.2, red), (0.5, white), (1., blue)]).unwrap();
+        assert!(same(multi.transform_single(nan), red));
+        let diverging = DivergingColorMap::new(red, white, blue, 0.3).unwrap();
+        assert!(same(diverging.transform_single(nan), red));
+        let categorical = CategoricalColorMap::new(vec![blue, white, red].into_iter()).unwrap();
+        assert!(same(categorical.transform_single(nan), blue));
+        let hue = GradientColorMap::sequential_from_hue(200.);
+        let hue_nan: RGBColor = hue.transform_single(nan).convert();
+        let hue_start: RGBColor = hue.transform_single(0.).convert();
+        assert!(same(hue_nan, hue_start));
+        // wrappers pass NaN on, so the inner colormap decides what it looks like
+        let reversed = GradientColorMap::new_linear(red, blue).reversed();
+        assert!(same(reversed.transform_single(nan), red));
+        let mirrored = MirroredColorMap::new(GradientColorMap::new_linear(white, red));
+        assert!(same(mirrored.transform_single(nan), white));
+        let cyclic = CyclicColorMap::new(GradientColorMap::new_linear(red, blue));
+        assert!(same(cyclic.transform_single(nan), red));
+        let warped = GradientColorMap::new_linear(red, blue).with_input_transform(|_| 1.);
+        assert!(same(warped.transform_single(nan), red));
+        let log = GradientColorMap::new_linear(red, blue)
+            .with_log_domain(1., 10.)
+            .unwrap();
+        assert!(same(log.transform_single(nan), red));
+        let fog = FogColorMap::new(GradientColorMap::new_linear(red, blue), white, |_| 0.5);
+        assert!(same(fog.transform_single(nan), red));
+        // so a bad color survives being wrapped, even in a degenerate domain
+        let marked = OverUnderColorMap::new(ListedColorMap::viridis(), blue, red, white);
+        let domain = marked.clone().with_domain(5., 5.).reversed();
+        assert!(same(domain.transform_single(nan), white));
+        let fogged = FogColorMap::new(marked, blue, |_| 1.);
+        assert!(same(fogged.transform_single(nan), white));
+    }
+    #[test]
+    fn test_over_under() {
+        let under = RGBColor::from_hex_code("#0000ff").unwrap();
+        let over = RGBColor::from_hex_code("#ff0000").unwrap();
+        let bad = RGBColor::from_hex_code("#808080").unwrap();
+        let viridis = ListedColorMap::viridis();
+        let cmap = OverUnderColorMap::new(ListedColorMap::viridis(), under, over, bad);
+        assert_eq!(cmap.transform_single(-0.1).to_string(), "#0000FF");
+        assert_eq!(cmap.transform_single(1.1).to_string(), "#FF0000");
+        assert_eq!(cmap.transform_single(f64::NAN).to_string(), "#808080");
+        assert_eq!(
+            cmap.transform_single(f64::NEG_INFINITY).to_string(),
+            "#0000FF"
+        );
+        assert_eq!(cmap.transform_single(f64::INFINITY).to_string(), "#FF0000");
+        // the ends themselves are in range
+        for &x in [0., 0.3, 1.].iter() {
+            let expected: RGBColor = viridis.transform_single(x);
+            assert_eq!(cmap.transform_single(x).to_string(), expected.to_string());
+        }
+        // with an unclipped domain, raw data outside of it is marked
+        let mut domain = cmap.with_domain(10., 20.);
+        domain.normalizer.clip = false;
+        assert_eq!(domain.transform_single(5.).to_string(), "#0000FF");
+        assert_eq!(domain.transform_single(25.).to_string(), "#FF0000");
+    }
+    #[test]
+    fn test_cyclic() {
+        let circle = ListedColorMap::circle();
+        let cyclic = CyclicColorMap::new(ListedColorMap::circle());
+        let expected: RGBColor = circle.transform_single(0.25);
+        let wrapped: RGBColor = cyclic.transform_single(1.25);
+        assert_eq!(wrapped, expected);
+        let expected: RGBColor = circle.transform_single(0.75);
+        let wrapped: RGBColor = cyclic.transform_single(-0.25);
+        assert_eq!(wrapped, expected);
+        let wrapped: RGBColor = cyclic.transform_single(-2.25);
+        assert_eq!(wrapped, expected);
+        // in range, it's the same as the inner map
+        let expected: RGBColor = circle.transform_single(0.6);
+        let inside: RGBColor = cyclic.transform_single(0.6);
+        assert_eq!(inside, expected);
+    }
+    #[test]
+    fn test_fog() {
+        let fog = RGBColor::from_hex_code("#8090a0").unwrap();
+        let clear = FogColorMap::new(ListedColorMap::viridis(), fog, |_x| 0.);
+        let opaque = FogColorMap::new(ListedColorMap::viridis(), fog, |_x| 1.);
+        let viridis = ListedColorMap::viridis();
+        for i in 0..=10 {
+            let x = i as f64 / 10.;
+            let expected: RGBColor = viridis.transform_single(x);
+            let unchanged: RGBColor = clear.transform_single(x);
+            let fogged: RGBColor = opaque.transform_single(x);
+            assert!(unchanged.distance(&expected) <= 1e-8);
+            assert!(fogged.distance(&fog) <= 1e-8);
+        }
+        // half black and half white light is much brighter than a 50% sRGB value
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let half = FogColorMap::new(GradientColorMap::new_linear(black, black), white, |_x| 0.5);
+        assert_eq!(half.transform_single(0.3).to_string(), "#BCBCBC");
+    }
+    #[test]
+    fn test_multi_gradient() {
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        let cmap = MultiGradientColorMap::new(vec![(0., red), (0.5, white), (1., blue)]).unwrap();
+        let vals = vec![-0.5, 0., 0.25, 0.5, 0.75, 1., 1.5];
+        let strs = [
+            "#FF0000", "#FF0000", "#FF8080", "#FFFFFF", "#8080FF", "#0000FF", "#0000FF",
+        ];
+        for (col, hex) in cmap.transform(vals).iter().zip(strs.iter()) {
+            assert_eq!(col.to_string(), *hex);
+        }
+        // stops not at the ends clamp
+        let cmap = MultiGradientColorMap::new(vec![(0.2, red), (0.8, blue)]).unwrap();
+        assert_eq!(cmap.transform_single(0.1).to_string(), "#FF0000");
+        assert_eq!(cmap.transform_single(0.9).to_string(), "#0000FF");
+
+        assert_eq!(
+            MultiGradientColorMap::<RGBColor>::new(vec![]).unwrap_err(),
+            ColorMapError::Empty
+        );
+        assert_eq!(
+            MultiGradientColorMap::new(vec![(0., red), (1.2, blue)]).unwrap_err(),
+            ColorMapError::PositionOutOfRange
+        );
+        assert_eq!(
+            MultiGradientColorMap::new(vec![(0., red), (0.5, white), (0.5, blue)]).unwrap_err(),
+            ColorMapError::PositionsNotIncreasing
+        );
+    }
+    #[test]
+    fn test_with_domain() {
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let cmap = GradientColorMap::new_linear(black, white).with_domain(10., 50.);
+        let vals = vec![0., 10., 20., 30., 50., 70.];
+        let strs = [
+            "#000000", "#000000", "#404040", "#808080", "#FFFFFF", "#FFFFFF",
+        ];
+        for (col, hex) in cmap.transform(vals).iter().zip(strs.iter()) {
+            assert_eq!(col.to_string(), *hex);
+        }
+
+        let norm = Normalizer::new(10., 50.);
+        assert!((norm.normalize(30.) - 0.5).abs() <= 1e-15);
+        assert_eq!(norm.normalize(60.), 1.);
+        // without clipping, values extrapolate
+        let norm = Normalizer {
+            clip: false,
+            ..norm
+        };
+        assert!((norm.normalize(60.) - 1.25).abs() <= 1e-15);
+        assert!((norm.normalize(0.) + 0.25).abs() <= 1e-15);
+        // which a cyclic colormap wraps around
+        let cyclic = DomainColorMap::new(CyclicColorMap::new(ListedColorMap::circle()), norm);
+        let c1: RGBColor = cyclic.transform_single(60.);
+        let c2: RGBColor = cyclic.transform_single(20.);
+        assert_eq!(c1.to_string(), c2.to_string());
+        // reversed domains and empty ones work too
+        assert!((Normalizer::new(50., 10.).normalize(40.) - 0.25).abs() <= 1e-15);
+        assert_eq!(Normalizer::new(5., 5.).normalize(7.), 0.);
+    }
+    #[test]
+    fn test_log_domain() {
+        let norm = LogNormalizer::new(1., 1000.).unwrap();
+        // the geometric mean is in the middle
+        assert!((norm.normalize(1000f64.sqrt()) - 0.5).abs() <= 1e-15);
+        assert!((norm.normalize(31.6) - 0.5).abs() <= 1e-3);
+        assert!((norm.normalize(10.) - 1. / 3.).abs() <= 1e-15);
+        assert_eq!(norm.normalize(0.5), 0.);
+        assert_eq!(norm.normalize(1e6), 1.);
+        assert_eq!(norm.normalize(0.), 0.);
+        assert_eq!(norm.normalize(-5.), 0.);
+        assert!(norm.normalize(f64::NAN).is_nan());
+
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let cmap = GradientColorMap::new_linear(black, white)
+            .with_log_domain(1., 1000.)
+            .unwrap();
+        let mid: RGBColor = cmap.transform_single(31.6);
+        assert!((mid.r - 0.5).abs() <= 1e-3);
+
+        for &(vmin, vmax) in [(0., 10.), (-1., 10.), (1., -10.), (f64::NAN, 10.)].iter() {
+            assert_eq!(
+                LogNormalizer::new(vmin, vmax).unwrap_err(),
+                ColorMapError::NonPositiveDomain
+            );
+        }
+    }
+    #[test]
+    fn test_diverging() {
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        // skewed data: the neutral color is well left of the middle
+        let cmap = DivergingColorMap::new(blue, white, red, 0.3).unwrap();
+        let vals = vec![-0.5, 0., 0.15, 0.3, 0.5, 1., 1.5];
+        let strs = [
+            "#0000FF", "#0000FF", "#8080FF", "#FFFFFF", "#FFB6B6", "#FF0000", "#FF0000",
+        ];
+        for (col, hex) in cmap.transform(vals).iter().zip(strs.iter()) {
+            assert_eq!(col.to_string(), *hex);
+        }
+        // halfway along the upper part is halfway between white and red
+        let mid: RGBColor = cmap.transform_single(0.65);
+        assert!((mid.g - 0.5).abs() <= 1e-12);
+        // the neutral color is only at the center
+        let near: RGBColor = cmap.transform_single(0.29);
+        assert!(near.r < 1.);
+        let near: RGBColor = cmap.transform_single(0.31);
+        assert!(near.b < 1.);
+        // giving the center in data units does the same thing
+        let cmap2 = DivergingColorMap::centered_at(blue, white, red, (-3., 0., 7.)).unwrap();
+        assert!((cmap2.center_position - 0.3).abs() <= 1e-12);
+
+        for &pos in [0., 1., -0.2, 1.2, f64::NAN].iter() {
+            assert_eq!(
+                DivergingColorMap::new(blue, white, red, pos).unwrap_err(),
+                ColorMapError::PositionOutOfRange
+            );
+        }
+        assert!(DivergingColorMap::centered_at(blue, white, red, (0., 5., 5.)).is_err());
+    }
+    #[test]
+    fn test_sequential_from_hue() {
+        for &hue in [0., 60., 135., 210., 270., 330., -90.].iter() {
+            let map = GradientColorMap::sequential_from_hue(hue);
+            let samples = map.sample(50);
+            let expected = hue.rem_euclid(360.);
+            let mut last_l = 100.;
+            for color in &samples {
+                let lch: CIELCHColor = color.convert();
+                // lightness only goes down, from light to dark
+                assert!(lch.l < last_l);
+                last_l = lch.l;
+                let diff = (lch.h - expected + 180.).rem_euclid(360.) - 180.;
+                assert!(diff.abs() <= 1.0);
+                assert!(color.convert::<RGBColor>().is_in_gamut());
+            }
+            assert!(samples[0].l >= 90. && samples[49].l <= 30.);
+            // and it has some color to it, even at the ends
+            assert!(samples[49].convert::<CIELCHColor>().c >= 10.);
+        }
+    }
+    #[test]
+    fn test_arc_length() {
+        let start = CIELABColor {
+            l: 20.,
+            a: -30.,
+            b: 10.,
+        };
+        let end = CIELABColor {
+            l: 80.,
+            a: 40.,
+            b: -20.,
+        };
+        let cmap = GradientColorMap::new_linear(start, end);
+        let straight = start.euclidean_distance(end);
+        assert!((cmap.arc_length(InterpSpace::CIELAB, 2) - straight).abs() <= 1e-8);
+        assert!((cmap.arc_length(InterpSpace::CIELAB, 50) - straight).abs() <= 1e-8);
+
+        // a curved path covers more ground than a straight one between its endpoints
+        let viridis = ListedColorMap::viridis();
+        let ends: Vec<RGBColor> = viridis.transform(vec![0., 1.]);
+        let length = viridis.arc_length(InterpSpace::CIEDE2000, 100);
+        assert!(length > ends[0].distance(&ends[1]));
+        assert!(viridis.arc_length(InterpSpace::CIELAB, 100) > length);
+    }
+    #[test]
+    fn test_is_cmyk_printable() {
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        let green = RGBColor::from_hex_code("#00ff00").unwrap();
+        let saturated = GradientColorMap::new_linear(blue, green);
+        assert!(!ColorMap::<RGBColor>::is_cmyk_printable(&saturated, 11, 2.));
+        let brown = RGBColor::from_hex_code("#8a7a6a").unwrap();
+        let tan = RGBColor::from_hex_code("#d0c0a0").unwrap();
+        let muted = GradientColorMap::new_linear(brown, tan);
+        assert!(ColorMap::<RGBColor>::is_cmyk_printable(&muted, 11, 1.));
+    }
+    #[test]
+    fn test_lerp_colormaps() {
+        let viridis = ListedColorMap::viridis();
+        let magma = ListedColorMap::magma();
+        let start = lerp_colormaps(&viridis, &magma, 0., 11);
+        let end = lerp_colormaps(&viridis, &magma, 1., 11);
+        let middle = lerp_colormaps(&viridis, &magma, 0.5, 11);
+        assert_eq!(start.vals.len(), 11);
+        for i in 0..11 {
+            let x = i as f64 / 10.;
+            let from_a: RGBColor = viridis.transform_single(x);
+            let from_b: RGBColor = magma.transform_single(x);
+            let from_start: RGBColor = start.transform_single(x);
+            let from_end: RGBColor = end.transform_single(x);
+            let from_middle: RGBColor = middle.transform_single(x);
+            assert!(from_start.distance(&from_a) <= 1e-6);
+            assert!(from_end.distance(&from_b) <= 1e-6);
+            // the halfway point is equally far from both in CIELAB
+            let lab: CIELABColor = from_middle.convert();
+            let lab_a: CIELABColor = from_a.convert();
+            let lab_b: CIELABColor = from_b.convert();
+            assert!((lab.euclidean_distance(lab_a) - lab.euclidean_distance(lab_b)).abs() <= 1e-6);
+        }
+    }
+    #[test]
+    fn test_categorical_from_u32s() {
+        let cmap = CategoricalColorMap::from_u32s(&[0xff0000, 0x00ff00]).unwrap();
+        assert_eq!(cmap.colors.len(), 2);
+        assert_eq!(cmap.colors[0].to_string(), "#FF0000");
+        assert_eq!(cmap.colors[1].to_string(), "#00FF00");
+        let cols: Vec<RGBColor> = cmap.transform(vec![-1., 0., 0.49, 0.5, 1., 2.]);
+        let strs = [
+            "#FF0000", "#FF0000", "#FF0000", "#00FF00", "#00FF00", "#00FF00",
+        ];
+        for (col, hex) in cols.iter().zip(strs.iter()) {
+            assert_eq!(col.to_string(), *hex);
+        }
+        // with no colors, there's nothing to map to
+        assert_eq!(
+            CategoricalColorMap::from_u32s(&[]).unwrap_err(),
+            ColorMapError::Empty
+        );
+        assert_eq!(
+            CategoricalColorMap::new(vec![].into_iter()).unwrap_err(),
+            ColorMapError::Empty
+        );
+    }
+    #[test]
+    fn test_listed_from_hex_codes() {
+        let cmap = ListedColorMap::from_hex_codes(&["#ff0000", "#00ff00", "#0000ff"]).unwrap();
+        let start: RGBColor = cmap.transform_single(0.);
+        let mid: RGBColor = cmap.transform_single(0.5);
+        let end: RGBColor = cmap.transform_single(1.);
+        assert_eq!(start.to_string(), "#FF0000");
+        assert_eq!(mid.to_string(), "#00FF00");
+        assert_eq!(end.to_string(), "#0000FF");
+        assert_eq!(
+            ListedColorMap::from_hex_codes(&["#ff0000", "nope"]).unwrap_err(),
+            RGBParseError::InvalidHexSyntax
+        );
+    }
+    #[test]
+    fn test_from_csv_reader() {
+        let data = "# a custom map\n0,0,0\n0.25, 0.5, 0\n0.5,1,0.5\n1.0,1.0,1.0\n";
+        let cmap = ListedColorMap::from_csv_reader(data.as_bytes()).unwrap();
+        assert_eq!(cmap.vals.len(), 4);
+        let start: RGBColor = cmap.transform_single(0.);
+        let end: RGBColor = cmap.transform_single(1.);
+        assert_eq!(start.to_string(), "#000000");
+        assert_eq!(end.to_string(), "#FFFFFF");
+        assert_eq!(cmap.vals[1], [0.25, 0.5, 0.]);
+
+        // rows with the wrong length, non-numbers, and no data at all are all errors
+        for bad in ["0,0,0\n1,1\n", "0,0,0\nr,g,b\n", "", "# nothing\n"].iter() {
+            let err = ListedColorMap::from_csv_reader(bad.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        }
+    }
+    #[test]
+    fn test_serde() {
+        // a flat list of RGB values, three per color, like a config file might hold
+        let data = "1,0,0,0,1,0,0,0,1\n";
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(data.as_bytes());
+        let cmap: ListedColorMap = reader.deserialize().next().unwrap().unwrap();
+        let mid: RGBColor = cmap.transform_single(0.5);
+        assert_eq!(mid.to_string(), "#00FF00");
+
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let gradient = GradientColorMap::new_cbrt(red, white);
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_writer(vec![]);
+        writer.serialize(&gradient).unwrap();
+        let data = writer.into_inner().unwrap();
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(data.as_slice());
+        let gradient2: GradientColorMap<RGBColor> = reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(gradient2.normalization, NormalizeMapping::Cbrt);
+        for &x in [0., 0.3, 1.].iter() {
+            assert_eq!(gradient.transform_single(x), gradient2.transform_single(x));
+        }
+        // the mixing space survives the round trip
+        let lab_gradient = GradientColorMap::new_linear(red, white).in_space::<CIELABColor>();
+        let mut writer = csv::WriterBuilder::new()
+            .has_headers(false)
+            .from_writer(vec![]);
+        writer.serialize(&lab_gradient).unwrap();
+        let data = writer.into_inner().unwrap();
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .from_reader(data.as_slice());
+        let lab_gradient2: GradientColorMap<RGBColor, CIELABColor> =
+            reader.deserialize().next().unwrap().unwrap();
+        assert_eq!(
+            lab_gradient.transform_single(0.5).to_string(),
+            lab_gradient2.transform_single(0.5).to_string()
+        );
+
+        // functions can't be written out
+        let mut writer = csv::Writer::from_writer(vec![]);
+        assert!(writer
+            .serialize(NormalizeMapping::Generic(|x| x * x))
+            .is_err());
+    }
+    #[test]
+    fn test_to_css_gradient() {
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        let cmap = GradientColorMap::new_linear(red, blue);
+        assert_eq!(
+            to_css_gradient(&cmap, 2),
+            "linear-gradient(to right, #FF0000 0%, #0000FF 100%)"
+        );
+        // too few stops still gives both ends
+        assert_eq!(to_css_gradient(&cmap, 0), to_css_gradient(&cmap, 2));
+        assert_eq!(
+            to_css_gradient(&cmap, 4),
+            "linear-gradient(to right, #FF0000 0%, #AA0055 33.33%, #5500AA 66.67%, #0000FF 100%)"
+        );
+    }
+    #[test]
+    #[cfg(feature = "image")]
+    fn test_colormap_to_rgb_image() {
+        // a 4x4 gradient running from the top left to the bottom right
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let gray = GradientColorMap::new_linear(black, white);
+        let data: Vec<f64> = (0..16).map(|i| ((i % 4) + (i / 4)) as f64 / 6.).collect();
+        let img = colormap_to_rgb_image(&gray, &data, 4, 4);
+        assert_eq!(img.dimensions(), (4, 4));
+        assert_eq!(img.get_pixel(0, 0).0, [0, 0, 0]);
+        assert_eq!(img.get_pixel(3, 3).0, [255, 255, 255]);
+        // top right and bottom left are both halfway
+        assert_eq!(img.get_pixel(3, 0).0, [128, 128, 128]);
+        assert_eq!(img.get_pixel(0, 3).0, [128, 128, 128]);
+    }
+    #[test]
+    fn test_listed_interpolation() {
+        let cmap = ListedColorMap::new(vec![[0., 0., 0.], [1., 0.5, 0.]].into_iter());
+        let rgb: RGBColor = cmap.transform_single(0.25);
+        assert!((rgb.r - 0.25).abs() <= 1e-10);
+        assert!((rgb.g - 0.125).abs() <= 1e-10);
+        assert!(rgb.b.abs() <= 1e-10);
+
+        // the second segment runs from white to green: 0.75 is halfway along it
+        let cmap = ListedColorMap::new(vec![[0., 0., 0.], [1., 1., 1.], [0., 1., 0.]].into_iter());
+        let rgb: RGBColor = cmap.transform_single(0.75);
+        assert!((rgb.r - 0.5).abs() <= 1e-10);
+        assert!((rgb.g - 1.).abs() <= 1e-10);
+        assert!((rgb.b - 0.5).abs() <= 1e-10);
+        let rgb: RGBColor = cmap.transform_single(0.1);
+        assert!((rgb.r - 0.2).abs() <= 1e-10);
+        assert!((rgb.g - 0.2).abs() <= 1e-10);
+        assert!((rgb.b - 0.2).abs() <= 1e-10);
+    }
+    #[test]
     fn test_mpl_colormaps() {
         let viridis = ListedColorMap::viridis();
         let magma = ListedColorMap::magma();
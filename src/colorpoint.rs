@@ -6,9 +6,15 @@
 
 use super::geo::prelude::*;
 use super::geo::{Closest, LineString, Point};
-use color::{Color, XYZColor};
+use color::{srgb_max_chroma, Color, RGBColor, XYZColor};
+use colormap::ColorMap;
+use colors::cielabcolor::CIELABColor;
+use colors::cielchcolor::CIELCHColor;
 use colors::cieluvcolor::CIELUVColor;
 use coord::Coord;
+use illuminants::Illuminant;
+use std::error::Error;
+use std::fmt;
 use visual_gamut::read_cie_spectral_data;
 
 /// Some errors that might pop up when dealing with colors as coordinates.
@@ -16,48 +22,136 @@ use visual_gamut::read_cie_spectral_data;
 pub enum ColorCalcError {
     /// Returned when the number of weights given and the number of colors being averaged differ.
     MismatchedWeights,
+    /// Returned when one of the weights given is negative.
+    NegativeWeight,
+    /// Returned when one of the weights given is infinite or NaN.
+    NonFiniteWeight,
+    /// Returned when the weights given add up to 0, so they can't be normalized.
+    ZeroWeightSum,
+}
+
+impl fmt::Display for ColorCalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Color calculation error")
+    }
+}
+
+impl Error for ColorCalcError {
+    fn description(&self) -> &str {
+        match *self {
+            ColorCalcError::MismatchedWeights => "Number of weights and colors differ",
+            ColorCalcError::NegativeWeight => "Negative weight",
+            ColorCalcError::NonFiniteWeight => "Infinite or NaN weight",
+            ColorCalcError::ZeroWeightSum => "Weights add up to 0",
+        }
+    }
 }
 
 /// A trait that indicates that the current Color can be embedded in 3D space. This also requires
 /// `Clone` and `Copy`: there shouldn't be any necessary information outside of the coordinate data.
 pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
     /// Gets the Euclidean distance between these two points when embedded in 3D space. This should
-    /// **not** be used as an analog of color similarity: use the [`distance()`] function for
-    /// that.
+    /// **not** be used as an analog of color similarity: use the [`distance()`] method for
+    /// that, which every `ColorPoint` has through [`Color`] and which computes the CIEDE2000
+    /// difference between the two colors in CIELAB.
     ///
     /// [`distance()`]: ../color/trait.Color.html#method.distance
+    /// [`Color`]: ../color/trait.Color.html
     fn euclidean_distance(self, other: Self) -> f64 {
         let c1: Coord = self.into();
         let c2: Coord = other.into();
         c1.euclidean_distance(&c2)
     }
 
+    /// Returns `true` if each of the coordinates of these two colors is within `eps` of the other's,
+    /// in the space `Self` is in. This is useful for testing conversions, where floating-point error
+    /// means colors that should be equal aren't quite. Pick `eps` with the scale of the space in
+    /// mind: CIELAB lightness runs from 0 to 100, but sRGB components only from 0 to 1. Like
+    /// [`XYZColor::approx_equal`], this doesn't consider whether the colors look the same: use
+    /// [`Color::visually_indistinguishable`] for that.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// let lab = CIELABColor{l: 50., a: 20., b: -30.};
+    /// let rgb: RGBColor = lab.convert();
+    /// let lab2: CIELABColor = rgb.convert();
+    /// assert!(lab.approx_equal_eps(lab2, 1e-10));
+    /// ```
+    ///
+    /// [`XYZColor::approx_equal`]: ../color/struct.XYZColor.html#method.approx_equal
+    /// [`Color::visually_indistinguishable`]: ../color/trait.Color.html#method.visually_indistinguishable
+    fn approx_equal_eps(self, other: Self, eps: f64) -> bool {
+        let c1: Coord = self.into();
+        let c2: Coord = other.into();
+        (c1.x - c2.x).abs() <= eps && (c1.y - c2.y).abs() <= eps && (c1.z - c2.z).abs() <= eps
+    }
+
     /// Gets the *weighted midpoint* of two colors in a space as a new
     /// [`Color`](../color/trait.Color.html). This is defined as the color corresponding to the point
     /// along the line segment connecting the two points such that the distance to the second point
     /// is the weight, which for most applications needs to be between 0 and 1. For example, a
     /// weight of 0.9 would make the midpoint one-tenth as much affected by the second points as the
     /// first.
+    ///
+    /// This interpolates the coordinates of whatever space `Self` is. For [`RGBColor`], which is
+    /// gamma-encoded, that is technically incorrect as a model of mixing light and gives mixes that
+    /// are too dark: convert to [`LinearRGBColor`] first to blend in linear light. Hue angles in
+    /// cylindrical spaces like CIELCH are the exception: they go the short way around the hue
+    /// circle, so mixing hues of 350 and 10 degrees gives 0, not 180.
+    ///
+    /// [`RGBColor`]: ../color/struct.RGBColor.html
+    /// [`LinearRGBColor`]: ../colors/linearrgbcolor/struct.LinearRGBColor.html
     fn weighted_midpoint(self, other: Self, weight: f64) -> Self {
         let c1: Coord = self.into();
         let c2: Coord = other.into();
-        Self::from(c1.weighted_midpoint(&c2, weight))
+        let mut mid = c1.weighted_midpoint(&c2, weight);
+        if let Some(axis) = Self::hue_axis() {
+            let hue = interpolate_hue(get_axis(c1, axis), get_axis(c2, axis), 1. - weight);
+            set_axis(&mut mid, axis, hue);
+        }
+        Self::from(mid)
     }
 
     /// Like `weighted_midpoint`, but with `weight = 0.5`: essentially, the
     /// [`Color`](../color/trait.Color.html) representing the midpoint of the two inputs in 3D space.
     fn midpoint(self, other: Self) -> Self {
-        let c1: Coord = self.into();
-        let c2: Coord = other.into();
-        Self::from(c1.midpoint(&c2))
+        self.weighted_midpoint(other, 0.5)
+    }
+
+    /// Mixes two colors by moving a fraction `t` of the way from `self` to `other` in a straight
+    /// line, so `t = 0` gives `self`, `t = 1` gives `other`, and `t = 0.25` is a quarter of the way
+    /// there. This is the usual convention for interpolation and the easiest way to blend two
+    /// colors. Note that it's the opposite of `weighted_midpoint`, where the weight is how much of
+    /// `self` to keep: `a.mix(b, t)` is the same as `a.weighted_midpoint(b, 1. - t)`. Values of `t`
+    /// outside of 0 to 1 extrapolate past the endpoints.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// let black = RGBColor::from_hex_code("#000000").unwrap();
+    /// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// assert_eq!(black.mix(white, 0.).to_string(), "#000000");
+    /// assert_eq!(black.mix(white, 0.25).to_string(), "#404040");
+    /// assert_eq!(black.mix(white, 1.).to_string(), "#FFFFFF");
+    /// ```
+    fn mix(self, other: Self, t: f64) -> Self {
+        self.weighted_midpoint(other, 1. - t)
     }
 
     /// Returns the weighted average of a given set of colors. Weights will be normalized so that they
     /// sum to 1. Each component of the final value will be calculated by summing the components of
-    /// each of the input colors multiplied by their given weight.
+    /// each of the input colors multiplied by their given weight, except for hue angles in
+    /// cylindrical spaces like CIELCH, which are averaged around the circle: see
+    /// [`circular_hue_mean`].
     /// # Errors
     /// Returns `ColorCalcError::MismatchedWeights` if the number of colors (`self` and anything in
-    /// `others`) and the number of weights mismatch.
+    /// `others`) and the number of weights mismatch, `ColorCalcError::NonFiniteWeight` if any weight
+    /// is infinite or NaN, `ColorCalcError::NegativeWeight` if any weight is negative, and
+    /// `ColorCalcError::ZeroWeightSum` if the weights are all 0.
     fn weighted_average(
         self,
         others: Vec<Self>,
@@ -65,6 +159,12 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
     ) -> Result<Self, ColorCalcError> {
         if others.len() + 1 != weights.len() {
             Err(ColorCalcError::MismatchedWeights)
+        } else if weights.iter().any(|w| !w.is_finite()) {
+            Err(ColorCalcError::NonFiniteWeight)
+        } else if weights.iter().any(|&w| w < 0.) {
+            Err(ColorCalcError::NegativeWeight)
+        } else if weights.iter().sum::<f64>() == 0. {
+            Err(ColorCalcError::ZeroWeightSum)
         } else {
             let c1: Coord = self.into();
             let norm: f64 = weights.iter().sum();
@@ -72,15 +172,51 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
             for i in 1..weights.len() {
                 coord = coord + others[i - 1].into() * weights[i] / norm;
             }
+            if let Some(axis) = Self::hue_axis() {
+                let hues: Vec<f64> = std::iter::once(self)
+                    .chain(others)
+                    .map(|color| get_axis(color.into(), axis))
+                    .collect();
+                set_axis(
+                    &mut coord,
+                    axis,
+                    weighted_circular_hue_mean(&hues, &weights),
+                );
+            }
             Ok(Self::from(coord))
         }
     }
     /// Returns the arithmetic mean of a given set of colors. Equivalent to `weighted_average` in the
-    /// case where each weight is the same.
+    /// case where each weight is the same, including in averaging hues around the circle. This
+    /// gives the raw coordinates: use `average_color` to get a color of the same type back instead.
     fn average(self, others: Vec<Self>) -> Coord {
         let c1: Coord = self.into();
         let other_cs: Vec<Coord> = others.iter().map(|x| (*x).into()).collect();
-        c1.average(&other_cs)
+        let mut avg = c1.average(&other_cs);
+        if let Some(axis) = Self::hue_axis() {
+            let hues: Vec<f64> = std::iter::once(c1)
+                .chain(other_cs)
+                .map(|c| get_axis(c, axis))
+                .collect();
+            set_axis(&mut avg, axis, circular_hue_mean(&hues));
+        }
+        avg
+    }
+    /// Returns the arithmetic mean of a given set of colors as a color of the same type, just like
+    /// `weighted_average` with every weight the same. Unlike that, this can't fail.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use scarlet::prelude::*;
+    /// # use scarlet::colors::CIELABColor;
+    /// let lab1 = CIELABColor{l: 40., a: 20., b: -10.};
+    /// let lab2 = CIELABColor{l: 60., a: 0., b: 30.};
+    /// let avg = lab1.average_color(vec![lab2]);
+    /// assert_eq!((avg.l, avg.a, avg.b), (50., 10., 10.));
+    /// ```
+    fn average_color(self, others: Vec<Self>) -> Self {
+        Self::from(self.average(others))
     }
 
     /// Returns `true` if the color is outside the range of human vision. Uses the CIE 1931 standard
@@ -146,6 +282,122 @@ pub trait ColorPoint: Color + Into<Coord> + From<Coord> + Clone + Copy {
         }
     }
 
+    /// Returns the color in `options` with the highest WCAG contrast ratio against this color, or
+    /// `None` if `options` is empty. This is useful for picking a callout or text color that stands
+    /// out against a background that isn't known ahead of time. If several options are tied, the
+    /// first one is returned.
+    ///
+    /// # Example
+    /// ```rust
+    /// use scarlet::color::RGBColor;
+    /// use scarlet::colorpoint::ColorPoint;
+    /// let background = RGBColor::from_hex_code("#3a3a3a").unwrap();
+    /// let options = [
+    ///     RGBColor::from_hex_code("#000000").unwrap(),
+    ///     RGBColor::from_hex_code("#ffff00").unwrap(),
+    /// ];
+    /// // yellow is far brighter than black is dark relative to the background
+    /// assert_eq!(background.most_contrasting_in(&options), Some(&options[1]));
+    /// ```
+    fn most_contrasting_in<'a>(self, options: &'a [Self]) -> Option<&'a Self> {
+        let rgb: RGBColor = self.convert();
+        let mut best: Option<(&'a Self, f64)> = None;
+        for option in options {
+            let ratio = rgb.contrast_ratio(&option.convert::<RGBColor>());
+            match best {
+                Some((_, best_ratio)) if best_ratio >= ratio => {}
+                _ => best = Some((option, ratio)),
+            }
+        }
+        best.map(|(option, _ratio)| option)
+    }
+
+    /// Returns the ink density of this color, the fraction of paper that would be covered in black
+    /// ink to reproduce it in a halftoned grayscale print like a newspaper. This is 1 minus the
+    /// relative luminance (the D65 Y value), clamped between 0 and 1, so that 0 is bare paper
+    /// white and 1 is solid black. This is the complement of luminance, so it can be used directly
+    /// as ink coverage.
+    ///
+    /// # Example
+    /// ```rust
+    /// use scarlet::color::RGBColor;
+    /// use scarlet::colorpoint::ColorPoint;
+    /// let gray = RGBColor::from_hex_code("#777777").unwrap();
+    /// // middle gray by eye takes much more than half coverage, because luminance isn't linear
+    /// assert!(gray.to_ink_density() > 0.75);
+    /// ```
+    fn to_ink_density(self) -> f64 {
+        (1.0 - self.to_xyz(Illuminant::D65).y).clamp(0., 1.)
+    }
+
+    /// Snaps this color to the closest of `samples` colors taken from `map`, and returns that color
+    /// along with the input that gives it. This reverses a colormap, for example to read data
+    /// values off of a screenshot of a colorbar. See [`ColorMap::closest_input`] for more.
+    ///
+    /// # Example
+    /// ```rust
+    /// use scarlet::color::RGBColor;
+    /// use scarlet::colormap::ListedColorMap;
+    /// use scarlet::colorpoint::ColorPoint;
+    /// let viridis = ListedColorMap::viridis();
+    /// // a color read off a plot, close to viridis at 0.25
+    /// let pixel = RGBColor::from_hex_code("#3b528b").unwrap();
+    /// let (_color, x) = pixel.snap_to_map(&viridis, 101);
+    /// assert_eq!(x, 0.25);
+    /// ```
+    ///
+    /// [`ColorMap::closest_input`]: ../colormap/trait.ColorMap.html#method.closest_input
+    fn snap_to_map<M: ColorMap<RGBColor>>(self, map: &M, samples: usize) -> (RGBColor, f64) {
+        let x = map.closest_input(&self, samples);
+        (map.transform_single(x), x)
+    }
+    /// Makes sure this color has a CIELCH chroma of at least `min_chroma`, for making a color stand
+    /// out more. Colors that already have enough chroma are returned as-is. Otherwise, the chroma
+    /// is raised to `min_chroma`, keeping the CIELCH lightness and hue the same, but never past the
+    /// edge of the sRGB gamut: if the requested chroma isn't displayable, this gives the most
+    /// chromatic displayable color instead. Grays (with a chroma below 0.01) have no hue to keep,
+    /// so they're given a hue of 0, a pinkish red.
+    ///
+    /// # Example
+    /// ```rust
+    /// use scarlet::color::{Color, RGBColor};
+    /// use scarlet::colorpoint::ColorPoint;
+    /// let muted = RGBColor::from_hex_code("#7f8a99").unwrap();
+    /// let vivid = muted.ensure_min_chroma(30.);
+    /// assert!((vivid.chroma() - 30.).abs() <= 1e-6);
+    /// assert!((vivid.hue() - muted.hue()).abs() <= 1e-6);
+    /// ```
+    fn ensure_min_chroma(self, min_chroma: f64) -> Self {
+        let lch: CIELCHColor = self.convert();
+        if lch.c >= min_chroma {
+            return self;
+        }
+        let h = if lch.c < 0.01 { 0. } else { lch.h };
+        let c = srgb_max_chroma(lch.l, h, min_chroma).max(lch.c);
+        CIELCHColor { l: lch.l, c, h }.convert()
+    }
+    /// Mixes this color `t` of the way toward the color `map` gives for `x`, in CIELAB so that the
+    /// mix is perceptually even. A `t` of 0 returns this color and a `t` of 1 returns the colormap's
+    /// color. This is useful for tinting a marker or highlight toward the color of the data it sits
+    /// on.
+    ///
+    /// # Example
+    /// ```rust
+    /// use scarlet::color::RGBColor;
+    /// use scarlet::colormap::ListedColorMap;
+    /// use scarlet::colorpoint::ColorPoint;
+    /// let marker = RGBColor::from_hex_code("#ffffff").unwrap();
+    /// let viridis = ListedColorMap::viridis();
+    /// let tinted = marker.toward_colormap(&viridis, 0.3, 0.2);
+    /// // still mostly white, with a hint of the blue-green viridis has at 0.3
+    /// assert!(tinted.r > 0.8 && tinted.b > tinted.r);
+    /// ```
+    fn toward_colormap<M: ColorMap<RGBColor>>(self, map: &M, x: f64, t: f64) -> Self {
+        let start: CIELABColor = self.convert();
+        let end: CIELABColor = map.transform_single(x).convert();
+        end.weighted_midpoint(start, t).convert()
+    }
+
     /// Returns a Vector of colors that starts with this color, ends with the given other color, and
     /// evenly transitions between colors. The given `n` is the number of additional colors to add.
     fn gradient_scale(&self, other: &Self, n: usize) -> Vec<Self> {
@@ -241,12 +493,123 @@ impl<T: Color + Into<Coord> + From<Coord> + Copy + Clone> ColorPoint for T {
     // nothing to do
 }
 
+// Gets the coordinate on the given axis, numbered as in Color::hue_axis.
+fn get_axis(coord: Coord, axis: usize) -> f64 {
+    match axis {
+        0 => coord.x,
+        1 => coord.y,
+        _ => coord.z,
+    }
+}
+
+// Sets the coordinate on the given axis, numbered as in Color::hue_axis.
+fn set_axis(coord: &mut Coord, axis: usize, value: f64) {
+    match axis {
+        0 => coord.x = value,
+        1 => coord.y = value,
+        _ => coord.z = value,
+    }
+}
+
+/// Finds the mean of a set of hue angles in degrees, treating them as directions around a circle
+/// instead of as numbers on a line. The naive mean of 350 and 10 is 180, the opposite hue of both,
+/// but their circular mean is 0: this is the average that makes sense for hues. The result is
+/// between 0 and 360. If the hues cancel out completely, like 0 and 180, there is no meaningful
+/// mean, and 0 is returned. This is what [`ColorPoint::average`] and
+/// [`ColorPoint::weighted_average`] use for the hues of cylindrical color types.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::colorpoint::circular_hue_mean;
+/// assert!(circular_hue_mean(&[350., 10.]).abs() <= 1e-10);
+/// assert!((circular_hue_mean(&[80., 100., 90.]) - 90.).abs() <= 1e-10);
+/// ```
+///
+/// [`ColorPoint::average`]: trait.ColorPoint.html#method.average
+/// [`ColorPoint::weighted_average`]: trait.ColorPoint.html#method.weighted_average
+pub fn circular_hue_mean(hues: &[f64]) -> f64 {
+    weighted_circular_hue_mean(hues, &vec![1.; hues.len()])
+}
+
+// The weighted version of circular_hue_mean: the direction of the weighted sum of unit vectors.
+fn weighted_circular_hue_mean(hues: &[f64], weights: &[f64]) -> f64 {
+    let (sin, cos) =
+        hues.iter()
+            .zip(weights.iter())
+            .fold((0., 0.), |(sin, cos), (&hue, &weight)| {
+                let rad = hue.to_radians();
+                (sin + weight * rad.sin(), cos + weight * rad.cos())
+            });
+    let mean = sin.atan2(cos).to_degrees().rem_euclid(360.);
+    // tiny negative angles round up to 360 exactly
+    if mean >= 360. {
+        0.
+    } else {
+        mean
+    }
+}
+
+// Moves a fraction t of the way from hue h1 to hue h2, in degrees, the short way around the circle.
+fn interpolate_hue(h1: f64, h2: f64, t: f64) -> f64 {
+    let diff = (h2 - h1 + 180.).rem_euclid(360.) - 180.;
+    let hue = (h1 + t * diff).rem_euclid(360.);
+    // tiny negative angles round up to 360 exactly
+    if hue >= 360. {
+        0.
+    } else {
+        hue
+    }
+}
+
+/// Mixes any number of colors by their weighted average in the space `S`, instead of in the space
+/// they're given in: the colors are converted to `S`, averaged as in
+/// [`ColorPoint::weighted_average`], and the result is converted back. The space makes a big
+/// difference. Averaging red and green in sRGB gives a dark, muddy olive, because sRGB
+/// components aren't proportional to how light a color looks. Averaging them in CIELAB gives a
+/// yellow as light as the average of the two. The weights are normalized, so they don't have to
+/// sum to 1.
+/// # Errors
+/// Returns the same errors as [`ColorPoint::weighted_average`]: `ColorCalcError::MismatchedWeights`
+/// if there aren't as many weights as colors, `ColorCalcError::NonFiniteWeight` if any weight is
+/// infinite or NaN, `ColorCalcError::NegativeWeight` if any weight is negative, and
+/// `ColorCalcError::ZeroWeightSum` if the weights add up to 0, including if there are no colors at
+/// all.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colorpoint::mix_in;
+/// # use scarlet::colors::CIELABColor;
+/// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+/// let green = RGBColor::from_hex_code("#00ff00").unwrap();
+/// let muddy = mix_in::<RGBColor, _>(&[red, green], &[1., 1.]).unwrap();
+/// let bright = mix_in::<CIELABColor, _>(&[red, green], &[1., 1.]).unwrap();
+/// assert_eq!(muddy.to_string(), "#808000");
+/// assert!(bright.lightness() > muddy.lightness() + 10.);
+/// ```
+///
+/// [`ColorPoint::weighted_average`]: trait.ColorPoint.html#method.weighted_average
+pub fn mix_in<S: ColorPoint, T: Color>(colors: &[T], weights: &[f64]) -> Result<T, ColorCalcError> {
+    if colors.is_empty() {
+        return Err(if weights.is_empty() {
+            ColorCalcError::ZeroWeightSum
+        } else {
+            ColorCalcError::MismatchedWeights
+        });
+    }
+    let converted: Vec<S> = colors.iter().map(|color| color.convert()).collect();
+    let mixed = converted[0].weighted_average(converted[1..].to_vec(), weights.to_vec())?;
+    Ok(mixed.convert())
+}
+
 #[cfg(test)]
 mod tests {
     #[allow(unused_imports)]
     use super::*;
-    use color::RGBColor;
-    use colors::cielabcolor::CIELABColor;
+    use colormap::ListedColorMap;
+    use colors::{HSLColor, HSVColor};
 
     #[test]
     fn test_cielab_distance() {
@@ -264,6 +627,330 @@ mod tests {
         assert!((lab1.euclidean_distance(lab2) - 132.70150715).abs() <= 1e-7);
     }
     #[test]
+    fn test_approx_equal_eps() {
+        // a round trip through XYZ and back accumulates some error
+        let lab = CIELABColor {
+            l: 62.3,
+            a: -14.1,
+            b: 27.9,
+        };
+        let xyz = lab.to_xyz(Illuminant::D50);
+        let lab2 = CIELABColor::from_xyz(xyz);
+        let luv = CIELUVColor::from_xyz(xyz);
+        let luv2 = CIELUVColor::from_xyz(luv.to_xyz(Illuminant::D65));
+        // offset by more than the default XYZ tolerance
+        let xyz2 = XYZColor {
+            x: xyz.x + 1e-9,
+            ..xyz
+        };
+        assert!(!xyz.approx_equal(&xyz2));
+        assert!(xyz.approx_equal_eps(&xyz2, 1e-8));
+        assert!(!xyz.approx_equal_eps(&xyz2, 1e-10));
+        assert!(lab.approx_equal_eps(lab2, 1e-10));
+        assert!(luv.approx_equal_eps(luv2, 1e-10));
+        let lab3 = CIELABColor { l: 62.4, ..lab };
+        assert!(!lab.approx_equal_eps(lab3, 0.05));
+        assert!(lab.approx_equal_eps(lab3, 0.2));
+    }
+    #[test]
+    fn test_average_color() {
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        let avg = red.average_color(vec![blue]);
+        let weighted = red.weighted_average(vec![blue], vec![0.5, 0.5]).unwrap();
+        assert_eq!(avg, weighted);
+        assert_eq!(avg.to_string(), "#800080");
+        // a single color averages to itself
+        assert_eq!(red.average_color(vec![]), red);
+    }
+    #[test]
+    fn test_weighted_average_errors() {
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        assert_eq!(
+            red.weighted_average(vec![blue], vec![1.]),
+            Err(ColorCalcError::MismatchedWeights)
+        );
+        assert_eq!(
+            red.weighted_average(vec![blue], vec![0., 0.]),
+            Err(ColorCalcError::ZeroWeightSum)
+        );
+        assert_eq!(
+            red.weighted_average(vec![blue], vec![2., -1.]),
+            Err(ColorCalcError::NegativeWeight)
+        );
+        // NaN isn't negative, but it can't be used as a weight either
+        for &bad in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY].iter() {
+            assert_eq!(
+                red.weighted_average(vec![blue], vec![1., bad]),
+                Err(ColorCalcError::NonFiniteWeight)
+            );
+            assert_eq!(
+                mix_in::<CIELABColor, _>(&[red, blue], &[bad, 1.]).unwrap_err(),
+                ColorCalcError::NonFiniteWeight
+            );
+        }
+        // zero weights are fine as long as some aren't
+        assert_eq!(red.weighted_average(vec![blue], vec![0., 3.]), Ok(blue));
+    }
+    #[test]
+    fn test_circular_hue_mean() {
+        // the angle between two hues, going the short way around
+        let hue_diff = |a: f64, b: f64| {
+            let diff = (a - b).rem_euclid(360.);
+            diff.min(360. - diff)
+        };
+        assert!(hue_diff(circular_hue_mean(&[350.0, 10.0]), 0.) <= 1e-10);
+        assert!(hue_diff(circular_hue_mean(&[340., 0., 20.]), 0.) <= 1e-10);
+        assert!((circular_hue_mean(&[100., 200.]) - 150.).abs() <= 1e-10);
+        assert!((circular_hue_mean(&[270.]) - 270.).abs() <= 1e-10);
+        let mean = circular_hue_mean(&[-10., 370.]);
+        assert!((0.0..360.).contains(&mean) && hue_diff(mean, 0.) <= 1e-10);
+        assert!((weighted_circular_hue_mean(&[0., 90.], &[1., 3.]) - 71.565).abs() <= 1e-3);
+
+        // averaging cylindrical colors uses it
+        let lch1 = CIELCHColor {
+            l: 50.,
+            c: 40.,
+            h: 350.,
+        };
+        let lch2 = CIELCHColor {
+            l: 60.,
+            c: 40.,
+            h: 10.,
+        };
+        let avg = lch1.average_color(vec![lch2]);
+        assert!((avg.l - 55.).abs() <= 1e-10);
+        assert!(hue_diff(avg.h, 0.) <= 1e-10);
+        let weighted = lch1.weighted_average(vec![lch2], vec![1., 1.]).unwrap();
+        assert!(hue_diff(weighted.h, 0.) <= 1e-10);
+        // the hue of a mix of a red and a magenta is a reddish magenta, not a green
+        let red = RGBColor::from_hex_code("#ff0020").unwrap();
+        let magenta = RGBColor::from_hex_code("#ff0080").unwrap();
+        let hsv_mix = mix_in::<HSVColor, _>(&[red, magenta], &[1., 1.]).unwrap();
+        assert!(hsv_mix.r > 0.9 && hsv_mix.g < 0.1);
+        let hsl = HSLColor {
+            h: 355.,
+            s: 1.,
+            l: 0.5,
+        }
+        .average(vec![HSLColor {
+            h: 15.,
+            s: 1.,
+            l: 0.5,
+        }]);
+        assert!((hsl.x - 5.).abs() <= 1e-10);
+        // and other spaces don't
+        let lab = CIELABColor {
+            l: 50.,
+            a: 350.,
+            b: 0.,
+        }
+        .average_color(vec![CIELABColor {
+            l: 50.,
+            a: 10.,
+            b: 0.,
+        }]);
+        assert_eq!(lab.a, 180.);
+    }
+    #[test]
+    fn test_mix_across_zero_hue() {
+        let hue_diff = |a: f64, b: f64| {
+            let diff = (a - b).rem_euclid(360.);
+            diff.min(360. - diff)
+        };
+        let lch1 = CIELCHColor {
+            l: 50.,
+            c: 40.,
+            h: 350.,
+        };
+        let lch2 = CIELCHColor {
+            l: 60.,
+            c: 40.,
+            h: 10.,
+        };
+        // mixing two colors agrees with averaging them
+        let mixed = lch1.mix(lch2, 0.5);
+        assert!(hue_diff(mixed.h, 0.) <= 1e-10);
+        assert!((0.0..360.).contains(&mixed.h));
+        assert!((mixed.l - 55.).abs() <= 1e-10);
+        assert!(hue_diff(mixed.h, lch1.average_color(vec![lch2]).h) <= 1e-10);
+        assert!(hue_diff(lch1.midpoint(lch2).h, 0.) <= 1e-10);
+        // and moves evenly along the short arc in either direction
+        assert!((lch1.mix(lch2, 0.25).h - 355.).abs() <= 1e-10);
+        assert!((lch2.mix(lch1, 0.25).h - 5.).abs() <= 1e-10);
+        assert!((lch1.weighted_midpoint(lch2, 0.75).h - 355.).abs() <= 1e-10);
+        // the ends are the colors themselves
+        assert_eq!(lch1.mix(lch2, 0.).h, 350.);
+        assert!(hue_diff(lch1.mix(lch2, 1.).h, 10.) <= 1e-10);
+        let hsv = HSVColor {
+            h: 340.,
+            s: 1.,
+            v: 1.,
+        }
+        .mix(
+            HSVColor {
+                h: 20.,
+                s: 1.,
+                v: 1.,
+            },
+            0.5,
+        );
+        assert!(hue_diff(hsv.h, 0.) <= 1e-10);
+        // hues that don't cross 0 mix as before
+        let lch3 = CIELCHColor {
+            l: 50.,
+            c: 40.,
+            h: 100.,
+        };
+        let lch4 = CIELCHColor { h: 200., ..lch3 };
+        assert!((lch3.mix(lch4, 0.5).h - 150.).abs() <= 1e-10);
+    }
+    #[test]
+    fn test_mix_in() {
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let green = RGBColor::from_hex_code("#00ff00").unwrap();
+        let rgb_mix = mix_in::<RGBColor, _>(&[red, green], &[1., 1.]).unwrap();
+        let lab_mix = mix_in::<CIELABColor, _>(&[red, green], &[1., 1.]).unwrap();
+        assert_eq!(rgb_mix.to_string(), "#808000");
+        // the CIELAB mix has the average lightness, so it's much lighter than the sRGB one
+        let (red_lab, green_lab): (CIELABColor, CIELABColor) = (red.convert(), green.convert());
+        assert!((lab_mix.lightness() - (red_lab.l + green_lab.l) / 2.).abs() <= 1e-8);
+        assert!(lab_mix.lightness() > rgb_mix.lightness() + 10.);
+        // and is still a strong yellow, not an olive
+        assert!(lab_mix.chroma() > rgb_mix.chroma());
+        // weights work like weighted_average
+        let mostly_red = mix_in::<CIELABColor, _>(&[red, green], &[3., 1.]).unwrap();
+        assert!(mostly_red.distance(&red) < mostly_red.distance(&green));
+        let one = mix_in::<CIELABColor, _>(&[red], &[2.]).unwrap();
+        assert_eq!(one.to_string(), "#FF0000");
+
+        assert_eq!(
+            mix_in::<CIELABColor, RGBColor>(&[], &[]).unwrap_err(),
+            ColorCalcError::ZeroWeightSum
+        );
+        assert_eq!(
+            mix_in::<CIELABColor, _>(&[red], &[1., 1.]).unwrap_err(),
+            ColorCalcError::MismatchedWeights
+        );
+        assert_eq!(
+            mix_in::<CIELABColor, _>(&[red, green], &[1., -1.]).unwrap_err(),
+            ColorCalcError::NegativeWeight
+        );
+    }
+    #[test]
+    fn test_mix() {
+        let lab1 = CIELABColor {
+            l: 30.,
+            a: 40.,
+            b: -20.,
+        };
+        let lab2 = CIELABColor {
+            l: 80.,
+            a: -10.,
+            b: 60.,
+        };
+        // the endpoints are exact
+        let start = lab1.mix(lab2, 0.);
+        let end = lab1.mix(lab2, 1.);
+        assert_eq!((start.l, start.a, start.b), (lab1.l, lab1.a, lab1.b));
+        assert_eq!((end.l, end.a, end.b), (lab2.l, lab2.a, lab2.b));
+        // and t moves toward other, unlike the weight in weighted_midpoint
+        let quarter = lab1.mix(lab2, 0.25);
+        assert!((quarter.l - 42.5).abs() <= 1e-10);
+        let midpoint = lab1.weighted_midpoint(lab2, 0.75);
+        assert_eq!(
+            (quarter.l, quarter.a, quarter.b),
+            (midpoint.l, midpoint.a, midpoint.b)
+        );
+    }
+    #[test]
+    fn test_most_contrasting_in() {
+        let background = RGBColor::from_hex_code("#f4f0e8").unwrap();
+        let options: Vec<RGBColor> = ["#888888", "#1b2a49", "#e03030", "#c0c0c0"]
+            .iter()
+            .map(|x| RGBColor::from_hex_code(x).unwrap())
+            .collect();
+        // the darkest option is navy
+        let best = background.most_contrasting_in(&options).unwrap();
+        assert_eq!(best.to_string(), "#1B2A49");
+        assert_eq!(background.most_contrasting_in(&[]), None);
+    }
+    #[test]
+    fn test_ink_density() {
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        // the sRGB matrix is rounded, so white isn't exactly Y = 1
+        assert!(white.to_ink_density().abs() <= 1e-4);
+        assert!((black.to_ink_density() - 1.).abs() <= 1e-10);
+        // lighter colors need less ink
+        let yellow = RGBColor::from_hex_code("#ffff00").unwrap();
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        assert!(yellow.to_ink_density() < blue.to_ink_density());
+    }
+    #[test]
+    fn test_toward_colormap() {
+        let marker = RGBColor::from_hex_code("#cc3366").unwrap();
+        let viridis = ListedColorMap::viridis();
+        let target: RGBColor = viridis.transform_single(0.7);
+        assert!(marker.toward_colormap(&viridis, 0.7, 0.).distance(&marker) <= 1e-6);
+        assert!(marker.toward_colormap(&viridis, 0.7, 1.).distance(&target) <= 1e-6);
+        // halfway is halfway in CIELAB
+        let mid: CIELABColor = marker.toward_colormap(&viridis, 0.7, 0.5).convert();
+        let lab1: CIELABColor = marker.convert();
+        let lab2: CIELABColor = target.convert();
+        assert!((mid.l - (lab1.l + lab2.l) / 2.).abs() <= 1e-6);
+    }
+    #[test]
+    fn test_ensure_min_chroma() {
+        let muted = RGBColor::from_hex_code("#998877").unwrap();
+        let boosted = muted.ensure_min_chroma(25.);
+        assert!(muted.chroma() < 25.);
+        assert!((boosted.chroma() - 25.).abs() <= 1e-6);
+        assert!((boosted.hue() - muted.hue()).abs() <= 1e-6);
+        assert!((boosted.lightness() - muted.lightness()).abs() <= 1e-6);
+        // colors with enough chroma are left alone
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        assert_eq!(red.ensure_min_chroma(25.).to_string(), "#FF0000");
+        // gray gets the default hue
+        let gray = RGBColor::from_hex_code("#808080").unwrap();
+        let tinted = gray.ensure_min_chroma(20.);
+        assert!((tinted.chroma() - 20.).abs() <= 1e-6);
+        // 0 and 360 are the same hue
+        assert!(tinted.hue().min(360. - tinted.hue()) <= 1e-6);
+        // too much chroma to display is limited to the sRGB gamut
+        let limited = gray.ensure_min_chroma(200.);
+        assert!(limited.chroma() > 20. && limited.chroma() < 200.);
+        for &x in [limited.r, limited.g, limited.b].iter() {
+            assert!((-1e-6..=1. + 1e-6).contains(&x));
+        }
+    }
+    #[test]
+    fn test_snap_to_map() {
+        let viridis = ListedColorMap::viridis();
+        let x = 100. / 255.;
+        let color: RGBColor = viridis.transform_single(x);
+        let (snapped, snapped_x) = color.snap_to_map(&viridis, 256);
+        assert_eq!(snapped, color);
+        assert!((snapped_x - x).abs() <= 1e-12);
+        // works from any color space
+        let lab: CIELABColor = color.convert();
+        let (_snapped, snapped_x) = lab.snap_to_map(&viridis, 256);
+        assert!((snapped_x - x).abs() <= 1e-12);
+    }
+    #[test]
+    fn test_perceptual_distance() {
+        let red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let almost_red = RGBColor::from_hex_code("#fe0000").unwrap();
+        let green = RGBColor::from_hex_code("#00ff00").unwrap();
+        // distance is available on any ColorPoint, and uses CIEDE2000
+        assert!(red.distance(&almost_red) < 1.0);
+        assert!(red.distance(&green) > 50.0);
+        let lab1: CIELABColor = red.convert();
+        let lab2: CIELABColor = green.convert();
+        assert!((red.distance(&green) - lab1.ciede2000(&lab2)).abs() <= 1e-10);
+    }
+    #[test]
     fn test_grad_scale() {
         let start = RGBColor::from_hex_code("#11457c").unwrap();
         let end = RGBColor::from_hex_code("#774bdc").unwrap();
@@ -1,6 +1,12 @@
 //! A module that implements the Adobe RGB color space. The Adobe RGB space differs greatly from
 //! sRGB: its components are floating points that range between 0 and 1, and it has a set of
 //! primaries designed to give it a wider coverage (over half of CIE 1931).
+//!
+//! This follows the Adobe RGB (1998) specification: the primaries are red (0.64, 0.33), green
+//! (0.21, 0.71), and blue (0.15, 0.06) in CIE 1931 xy chromaticities, the white point is D65, and
+//! the gamma is exactly 563/256, or about 2.2, with no linear segment. Like every color in Scarlet,
+//! it converts to and from any other space through XYZ, so going between sRGB and Adobe RGB is just
+//! a call to `convert`.
 
 use bound::Bound;
 use color::{Color, XYZColor};
@@ -125,6 +131,7 @@ impl Bound for AdobeRGBColor {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use color::RGBColor;
     use consts::TEST_PRECISION;
 
     #[test]
@@ -140,6 +147,50 @@ mod tests {
         assert!(xyz1.distance(&xyz2) <= TEST_PRECISION);
     }
     #[test]
+    fn test_adobe_rgb_primaries() {
+        let chromaticity = |rgb: AdobeRGBColor| {
+            let xyz = rgb.to_xyz(Illuminant::D65);
+            let sum = xyz.x + xyz.y + xyz.z;
+            (xyz.x / sum, xyz.y / sum)
+        };
+        for (rgb, (x, y)) in [
+            ((1., 0., 0.), (0.64, 0.33)),
+            ((0., 1., 0.), (0.21, 0.71)),
+            ((0., 0., 1.), (0.15, 0.06)),
+        ]
+        .iter()
+        {
+            let (x2, y2) = chromaticity(AdobeRGBColor {
+                r: rgb.0,
+                g: rgb.1,
+                b: rgb.2,
+            });
+            // the matrix is only given to 5 digits
+            assert!((x - x2).abs() <= 1e-3);
+            assert!((y - y2).abs() <= 1e-3);
+        }
+    }
+    #[test]
+    fn test_adobe_rgb_green_outside_srgb() {
+        // Adobe RGB's main advantage is in greens and cyans
+        let green = AdobeRGBColor {
+            r: 0.,
+            g: 1.,
+            b: 0.,
+        };
+        let srgb_green: RGBColor = green.convert();
+        assert!(!srgb_green.is_in_gamut());
+        assert!(srgb_green.r < 0.);
+        // whereas sRGB greens fit inside Adobe RGB and come back unchanged
+        let srgb_green2 = RGBColor::from_hex_code("#20c040").unwrap();
+        let green2: AdobeRGBColor = srgb_green2.convert();
+        assert!(green2.r > 0. && green2.g < 1. && green2.b > 0.);
+        let srgb_green3: RGBColor = green2.convert();
+        assert!((srgb_green2.r - srgb_green3.r).abs() <= 1e-10);
+        assert!((srgb_green2.g - srgb_green3.g).abs() <= 1e-10);
+        assert!((srgb_green2.b - srgb_green3.b).abs() <= 1e-10);
+    }
+    #[test]
     fn test_adobe_rgb_clamping() {
         let argb = AdobeRGBColor {
             r: 1.1,
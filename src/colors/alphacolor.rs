@@ -0,0 +1,108 @@
+//! A module that implements [`AlphaColor`], which pairs any color with an alpha (opacity) value.
+//! Alpha isn't part of a color's appearance on its own, so it isn't tracked by [`Color`] itself:
+//! instead, this wrapper carries it alongside the color, mostly for exchanging colors with GUI
+//! toolkits and image formats that expect one.
+//!
+//! [`AlphaColor`]: struct.AlphaColor.html
+//! [`Color`]: ../../color/trait.Color.html
+
+use color::{Color, RGBColor};
+
+/// A color with an alpha value describing its opacity, from 0 (fully transparent) to 1 (fully
+/// opaque).
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::AlphaColor;
+/// // a half-transparent red, packed as 0xAARRGGBB the way egui and many other toolkits expect
+/// let red = AlphaColor::from_argb_u32(0x80ff0000);
+/// assert_eq!(red.color.to_string(), "#FF0000");
+/// assert!((red.alpha - 128. / 255.).abs() <= 1e-10);
+/// assert_eq!(red.to_rgba_u32(), 0xff000080);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlphaColor<T: Color> {
+    /// The underlying color.
+    pub color: T,
+    /// The opacity, from 0 (fully transparent) to 1 (fully opaque).
+    pub alpha: f64,
+}
+
+impl<T: Color> AlphaColor<T> {
+    /// Pairs a color with the given alpha value.
+    pub fn new(color: T, alpha: f64) -> AlphaColor<T> {
+        AlphaColor { color, alpha }
+    }
+}
+
+impl AlphaColor<RGBColor> {
+    /// Gets an 8-byte version of the alpha value, as a `u8`. Clamps values outside of the range 0-1
+    /// and discretizes, just like [`RGBColor::int_r`].
+    ///
+    /// [`RGBColor::int_r`]: ../../color/struct.RGBColor.html#method.int_r
+    pub fn int_alpha(&self) -> u8 {
+        (self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8
+    }
+    /// Constructs a color from an integer packed as `0xAARRGGBB`: alpha is in bits 24-31, red in
+    /// bits 16-23, green in bits 8-15, and blue in bits 0-7. This is the order used by egui,
+    /// Windows, and Android, among others.
+    pub fn from_argb_u32(val: u32) -> AlphaColor<RGBColor> {
+        AlphaColor {
+            color: RGBColor::from_u32(val),
+            alpha: f64::from((val >> 24) as u8) / 255.0,
+        }
+    }
+    /// Packs the color into an integer as `0xAARRGGBB`, the inverse of
+    /// [`AlphaColor::from_argb_u32`]. Components are clamped and rounded to 8 bits.
+    ///
+    /// [`AlphaColor::from_argb_u32`]: #method.from_argb_u32
+    pub fn to_argb_u32(&self) -> u32 {
+        let (r, g, b) = self.color.int_rgb_tup();
+        u32::from_be_bytes([self.int_alpha(), r, g, b])
+    }
+    /// Constructs a color from an integer packed as `0xRRGGBBAA`: red is in bits 24-31, green in
+    /// bits 16-23, blue in bits 8-15, and alpha in bits 0-7. This is the order used by CSS's
+    /// `#RRGGBBAA` hex codes and by iced, among others.
+    pub fn from_rgba_u32(val: u32) -> AlphaColor<RGBColor> {
+        AlphaColor {
+            color: RGBColor::from_u32(val >> 8),
+            alpha: f64::from(val as u8) / 255.0,
+        }
+    }
+    /// Packs the color into an integer as `0xRRGGBBAA`, the inverse of
+    /// [`AlphaColor::from_rgba_u32`]. Components are clamped and rounded to 8 bits.
+    ///
+    /// [`AlphaColor::from_rgba_u32`]: #method.from_rgba_u32
+    pub fn to_rgba_u32(&self) -> u32 {
+        let (r, g, b) = self.color.int_rgb_tup();
+        u32::from_be_bytes([r, g, b, self.int_alpha()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_argb_u32() {
+        let color = AlphaColor::from_argb_u32(0x401a2b3c);
+        assert_eq!(color.color.int_rgb_tup(), (0x1a, 0x2b, 0x3c));
+        assert_eq!(color.int_alpha(), 0x40);
+        assert_eq!(color.to_argb_u32(), 0x401a2b3c);
+        assert_eq!(color.to_rgba_u32(), 0x1a2b3c40);
+    }
+    #[test]
+    fn test_rgba_u32() {
+        let color = AlphaColor::from_rgba_u32(0x1a2b3c40);
+        assert_eq!(color.color.int_rgb_tup(), (0x1a, 0x2b, 0x3c));
+        assert_eq!(color.int_alpha(), 0x40);
+        assert_eq!(color.to_rgba_u32(), 0x1a2b3c40);
+        assert_eq!(color.to_argb_u32(), 0x401a2b3c);
+        // out-of-range alpha is clamped
+        let opaque = AlphaColor::new(color.color, 1.5);
+        assert_eq!(opaque.to_rgba_u32(), 0x1a2b3cff);
+    }
+}
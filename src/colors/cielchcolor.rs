@@ -80,6 +80,9 @@ impl Color for CIELCHColor {
         }
         .to_xyz(illuminant)
     }
+    fn hue_axis() -> Option<usize> {
+        Some(2)
+    }
 }
 
 impl From<Coord> for CIELCHColor {
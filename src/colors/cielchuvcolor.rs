@@ -72,6 +72,9 @@ impl Color for CIELCHuvColor {
         let v = self.c * rad_h.sin();
         CIELUVColor { l: self.l, u, v }.to_xyz(illuminant)
     }
+    fn hue_axis() -> Option<usize> {
+        Some(2)
+    }
 }
 
 impl From<Coord> for CIELCHuvColor {
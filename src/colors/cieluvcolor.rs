@@ -35,6 +35,30 @@ pub struct CIELUVColor {
     pub v: f64,
 }
 
+// The denominator of the u' and v' chromaticity coordinates. This is 0 for pure black, which has no
+// chromaticity: u' and v' are then taken to be 0, which CIELUV's scaling by L makes irrelevant.
+fn denom(color: XYZColor) -> f64 {
+    color.x + 15.0 * color.y + 3.0 * color.z
+}
+
+fn u_prime(color: XYZColor) -> f64 {
+    let d = denom(color);
+    if d == 0.0 {
+        0.0
+    } else {
+        4.0 * color.x / d
+    }
+}
+
+fn v_prime(color: XYZColor) -> f64 {
+    let d = denom(color);
+    if d == 0.0 {
+        0.0
+    } else {
+        9.0 * color.y / d
+    }
+}
+
 impl Color for CIELUVColor {
     /// Given an XYZ color, gets a new CIELUV color. This is CIELUV D50, so anything else is
     /// chromatically adapted before conversion.
@@ -42,19 +66,24 @@ impl Color for CIELUVColor {
         // this is not bad: LUV is meant to be easy from XYZ
         // https://en.wikipedia.org/wiki/CIELUV
 
+        // black has no chromaticity, and would otherwise give -0 for u and v
+        if xyz.x == 0.0 && xyz.y == 0.0 && xyz.z == 0.0 {
+            return CIELUVColor {
+                l: 0.0,
+                u: 0.0,
+                v: 0.0,
+            };
+        }
+
         // do u and v chromaticity conversions on whitepoint and on given color
         // because cieluv chromatic adaptation sucks, use the good one
         let xyz_c = xyz.color_adapt(Illuminant::D50);
         let wp = XYZColor::white_point(Illuminant::D50);
-        let denom = |color: XYZColor| color.x + 15.0 * color.y + 3.0 * color.z;
-        let u_func = |color: XYZColor| 4.0 * color.x / denom(color);
-        let v_func = |color: XYZColor| 9.0 * color.y / denom(color);
-
-        let u_prime_n = u_func(wp);
-        let v_prime_n = v_func(wp);
+        let u_prime_n = u_prime(wp);
+        let v_prime_n = v_prime(wp);
 
-        let u_prime = u_func(xyz_c);
-        let v_prime = v_func(xyz_c);
+        let u_prime = u_prime(xyz_c);
+        let v_prime = v_prime(xyz_c);
 
         let delta: f64 = 6.0 / 29.0; // like CIELAB
 
@@ -78,11 +107,17 @@ impl Color for CIELUVColor {
         // https://en.wikipedia.org/wiki/CIELUV literally has the equations in order
         // pretty straightforward
         let wp = XYZColor::white_point(Illuminant::D50);
-        let denom = |color: XYZColor| color.x + 15.0 * color.y + 3.0 * color.z;
-        let u_func = |color: XYZColor| 4.0 * color.x / denom(color);
-        let v_func = |color: XYZColor| 9.0 * color.y / denom(color);
-        let u_prime_n = u_func(wp);
-        let v_prime_n = v_func(wp);
+        // u and v are scaled by L, so black has no chromaticity to recover
+        if self.l == 0.0 {
+            return XYZColor {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+                illuminant,
+            };
+        }
+        let u_prime_n = u_prime(wp);
+        let v_prime_n = v_prime(wp);
 
         let u_prime = self.u / (13.0 * self.l) + u_prime_n;
         let v_prime = self.v / (13.0 * self.l) + v_prime_n;
@@ -131,6 +166,7 @@ impl From<CIELUVColor> for Coord {
 mod tests {
     #[allow(unused_imports)]
     use super::*;
+    use color::RGBColor;
     use consts::TEST_PRECISION;
 
     #[test]
@@ -160,4 +196,26 @@ mod tests {
         assert!(xyz2.approx_visually_equal(&xyz));
         assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
     }
+
+    #[test]
+    fn test_cieluv_black() {
+        let black = XYZColor {
+            x: 0.,
+            y: 0.,
+            z: 0.,
+            illuminant: Illuminant::D65,
+        };
+        let luv = CIELUVColor::from_xyz(black);
+        for val in [luv.l, luv.u, luv.v].iter() {
+            assert!(val.is_finite());
+            // exactly 0, not -0
+            assert_eq!(val.to_bits(), 0f64.to_bits());
+        }
+        let xyz = luv.to_xyz(Illuminant::D65);
+        assert_eq!(xyz, black);
+        let rgb: RGBColor = RGBColor::from((0, 0, 0)).convert::<CIELUVColor>().convert();
+        assert_eq!(rgb.r, 0.);
+        assert_eq!(rgb.g, 0.);
+        assert_eq!(rgb.b, 0.);
+    }
 }
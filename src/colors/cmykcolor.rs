@@ -0,0 +1,230 @@
+//! A module that implements CMYK, the subtractive color model used in printing. Unlike the RGB
+//! spaces in Scarlet, CMYK isn't a single standardized space: what a given set of ink amounts looks
+//! like depends on the inks, the paper, and the press. Scarlet models a typical commercial print
+//! on coated paper, so that conversions to CMYK give a realistic idea of which colors can and
+//! cannot be printed.
+
+use color::{Color, XYZColor};
+use colors::cielabcolor::CIELABColor;
+use illuminants::Illuminant;
+use nalgebra::{Matrix3, Vector3};
+
+lazy_static! {
+    // The CIELAB D50 values of the paper, the solid process inks and their overprints, and solid
+    // black, taken from the ISO 12647-2 aims for coated paper (the FOGRA39 characterization). The
+    // order is that of the bits of an index: C is 1, M is 2, and Y is 4, so e.g., index 3 is blue.
+    static ref PRIMARIES: [XYZColor; 8] = {
+        let lab = [
+            [95.0, 0.0, -2.0],
+            [55.0, -37.0, -50.0],
+            [48.0, 74.0, -3.0],
+            [24.0, 22.0, -46.0],
+            [89.0, -5.0, 93.0],
+            [50.0, -65.0, 27.0],
+            [47.0, 68.0, 48.0],
+            [23.0, 0.0, 0.0],
+        ];
+        let mut xyz = [XYZColor::white_point(Illuminant::D50); 8];
+        for (i, &[l, a, b]) in lab.iter().enumerate() {
+            xyz[i] = CIELABColor { l, a, b }.to_xyz(Illuminant::D50);
+        }
+        xyz
+    };
+    static ref BLACK: XYZColor = CIELABColor {
+        l: 16.0,
+        a: 0.0,
+        b: 0.0,
+    }
+    .to_xyz(Illuminant::D50);
+}
+
+/// Computes the XYZ coordinates of a print with the given ink coverages, relative to the paper so
+/// that blank paper is the D50 white point. This is the Neugebauer model with Demichel weights:
+/// the print is treated as a mix of the areas covered by each of the eight overprint combinations
+/// of C, M, and Y, which are assumed to be laid down independently. Black ink then filters the
+/// result.
+fn print_xyz(c: f64, m: f64, y: f64, k: f64) -> Vector3<f64> {
+    let paper = PRIMARIES[0];
+    let wp = Illuminant::D50.white_point();
+    let mut xyz = Vector3::zeros();
+    for (i, primary) in PRIMARIES.iter().enumerate() {
+        let cov = |bit: usize, amount: f64| {
+            if i & bit == 0 {
+                1.0 - amount
+            } else {
+                amount
+            }
+        };
+        let weight = cov(1, c) * cov(2, m) * cov(4, y);
+        xyz += Vector3::new(primary.x, primary.y, primary.z) * weight;
+    }
+    // relative colorimetry: scale so paper white is D50 white
+    let black = Vector3::new(BLACK.x / paper.x, BLACK.y / paper.y, BLACK.z / paper.z);
+    let filter = Vector3::repeat(1.0 - k) + black * k;
+    Vector3::new(
+        xyz[0] * filter[0] * wp[0] / paper.x,
+        xyz[1] * filter[1] * wp[1] / paper.y,
+        xyz[2] * filter[2] * wp[2] / paper.z,
+    )
+}
+
+/// A color as the amounts of cyan, magenta, yellow, and black (K) ink used to print it, each from 0
+/// (no ink) to 1 (solid ink). Scarlet models a typical offset print on coated paper, using the ISO
+/// 12647-2 colors for the inks and paper, and treats paper white as the white point. Many colors
+/// that can be displayed on a screen can't be printed: in particular, saturated blues, greens, and
+/// oranges are out of reach. Converting such a color to CMYK gives the closest printable color.
+///
+/// When converting to CMYK, black ink is only used for shadows too dark to be printed using
+/// cyan, magenta, and yellow alone.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::CMYKColor;
+/// let muted = RGBColor::from_hex_code("#8a7a6a").unwrap();
+/// let cmyk: CMYKColor = muted.convert();
+/// // this can be printed essentially exactly
+/// assert!(muted.distance(&cmyk) < 1.0);
+///
+/// let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+/// let cmyk: CMYKColor = blue.convert();
+/// // but bright blue is far outside what ink can do
+/// assert!(blue.distance(&cmyk) > 10.0);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CMYKColor {
+    /// The amount of cyan ink, from 0 to 1.
+    pub c: f64,
+    /// The amount of magenta ink, from 0 to 1.
+    pub m: f64,
+    /// The amount of yellow ink, from 0 to 1.
+    pub y: f64,
+    /// The amount of black ink, from 0 to 1.
+    pub k: f64,
+}
+
+impl Color for CMYKColor {
+    /// Finds the ink amounts that best reproduce the given color. Colors outside of the print gamut
+    /// are brought within it, so this conversion loses information for those colors.
+    fn from_xyz(xyz: XYZColor) -> CMYKColor {
+        let xyz_c = xyz.color_adapt(Illuminant::D50);
+        let target = Vector3::new(xyz_c.x, xyz_c.y, xyz_c.z);
+        let clamp = |x: f64| x.clamp(0.0, 1.0);
+
+        // the model can't be inverted directly, so use Newton's method, keeping the ink amounts in
+        // range at each step
+        let mut cmy = Vector3::repeat(0.5);
+        const STEP: f64 = 1e-6;
+        for _i in 0..50 {
+            let current = print_xyz(cmy[0], cmy[1], cmy[2], 0.0);
+            let residual = target - current;
+            if residual.norm() <= 1e-12 {
+                break;
+            }
+            // numerical Jacobian, using a one-sided difference that stays in range
+            let mut jacobian = Matrix3::zeros();
+            for j in 0..3 {
+                let mut shifted = cmy;
+                let h = if cmy[j] + STEP > 1.0 { -STEP } else { STEP };
+                shifted[j] += h;
+                let col = (print_xyz(shifted[0], shifted[1], shifted[2], 0.0) - current) / h;
+                jacobian.set_column(j, &col);
+            }
+            match jacobian.try_inverse() {
+                Some(inv) => {
+                    let next = (cmy + inv * residual).map(clamp);
+                    if (next - cmy).norm() <= 1e-12 {
+                        break;
+                    }
+                    cmy = next;
+                }
+                None => break,
+            }
+        }
+
+        // if the color is darker than cyan, magenta, and yellow can print, add black
+        let printed = print_xyz(cmy[0], cmy[1], cmy[2], 0.0);
+        // the tolerance keeps rounding error from adding tiny amounts of black
+        let k = if target[1] < printed[1] - 1e-9 {
+            let black_y = print_xyz(cmy[0], cmy[1], cmy[2], 1.0)[1];
+            clamp((printed[1] - target[1]) / (printed[1] - black_y))
+        } else {
+            0.0
+        };
+        CMYKColor {
+            c: cmy[0],
+            m: cmy[1],
+            y: cmy[2],
+            k,
+        }
+    }
+    /// Computes the color that printing with these ink amounts produces, relative to white
+    /// paper. Amounts outside of 0 to 1 are clamped.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let clamp = |x: f64| x.clamp(0.0, 1.0);
+        let xyz = print_xyz(clamp(self.c), clamp(self.m), clamp(self.y), clamp(self.k));
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D50,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+
+    #[test]
+    fn test_cmyk_paper_and_inks() {
+        // blank paper is white
+        let paper = CMYKColor {
+            c: 0.,
+            m: 0.,
+            y: 0.,
+            k: 0.,
+        };
+        assert!(paper.distance(&XYZColor::white_point(Illuminant::D50)) <= 1e-10);
+        // solid cyan has the hue of cyan ink, relative to the paper
+        let cyan: CIELABColor = CMYKColor {
+            c: 1.,
+            m: 0.,
+            y: 0.,
+            k: 0.,
+        }
+        .convert();
+        assert!(cyan.a < -30.0 && cyan.b < -40.0);
+    }
+    #[test]
+    fn test_cmyk_round_trip() {
+        for code in ["#8A7A6A", "#D0C0A0", "#406080", "#B05040", "#F0F0F0"].iter() {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            let cmyk: CMYKColor = rgb.convert();
+            assert!(rgb.distance(&cmyk) <= 1e-6);
+            assert_eq!(cmyk.k, 0.0);
+            let rgb2: RGBColor = cmyk.convert();
+            assert_eq!(rgb2.to_string(), *code);
+        }
+        // deep shadows need black ink, which only matches lightness, so they're close but not exact
+        let dark = RGBColor::from_hex_code("#202020").unwrap();
+        let cmyk: CMYKColor = dark.convert();
+        assert!(cmyk.k > 0.0);
+        assert!(dark.distance(&cmyk) <= 1.0);
+    }
+    #[test]
+    fn test_cmyk_out_of_gamut() {
+        for code in ["#0000FF", "#00FF00", "#FF6600"].iter() {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            let cmyk: CMYKColor = rgb.convert();
+            assert!(rgb.distance(&cmyk) > 3.0);
+            for ink in [cmyk.c, cmyk.m, cmyk.y, cmyk.k].iter() {
+                assert!((0.0..=1.0).contains(ink));
+            }
+        }
+    }
+}
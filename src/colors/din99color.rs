@@ -0,0 +1,224 @@
+//! This module implements the DIN99 color space, defined in the German standard DIN 6176. DIN99 is
+//! a reshaping of CIELAB: lightness is compressed logarithmically, the opponent axes are rotated and
+//! the new yellow-blue axis is shrunk, and chroma is compressed logarithmically too. The result is
+//! designed so that plain Euclidean distance between two DIN99 colors is a good estimate of how
+//! different they look, about as good as CIEDE2000 for small and medium differences but far
+//! cheaper to compute and, being a true distance, usable in things like k-d trees and clustering.
+
+use super::cielabcolor::CIELABColor;
+use color::{Color, XYZColor};
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// The angle, in degrees, by which the CIELAB opponent axes are rotated in DIN99.
+const DIN99_ROTATION: f64 = 16.0;
+/// The factor by which the second opponent axis is shrunk in DIN99.
+const DIN99_F_SCALE: f64 = 0.7;
+/// The coefficient of the logarithmic chroma compression in DIN99.
+const DIN99_CHROMA: f64 = 0.045;
+
+/// A color in the DIN99 color space, with a lightness and two opponent color axes that play the same
+/// roles as they do in CIELAB. Because the space is built so that distance matches perception,
+/// [`euclidean_distance`] between two `DIN99Color`s is a reasonable color difference: a difference
+/// of 1 is roughly a just-noticeable difference, the same as 1 unit of CIEDE2000.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::DIN99Color;
+/// let teal: DIN99Color = RGBColor::from_hex_code("#1a8080").unwrap().convert();
+/// let teal2: DIN99Color = RGBColor::from_hex_code("#1a8a80").unwrap().convert();
+/// let orange: DIN99Color = RGBColor::from_hex_code("#e08030").unwrap().convert();
+/// // close colors are close, and far colors are far
+/// assert!(teal.euclidean_distance(teal2) < 5.);
+/// assert!(teal.euclidean_distance(orange) > 30.);
+/// ```
+///
+/// [`euclidean_distance`]: ../colorpoint/trait.ColorPoint.html#method.euclidean_distance
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct DIN99Color {
+    /// The lightness, a logarithmic compression of CIELAB's: 0 is black and 100 is diffuse white.
+    pub l: f64,
+    /// The first opponent color axis, running roughly from green (negative) to red (positive).
+    pub a: f64,
+    /// The second opponent color axis, running roughly from blue (negative) to yellow (positive).
+    pub b: f64,
+}
+
+impl DIN99Color {
+    /// Converts from CIELAB to DIN99 directly, using the formulas of DIN 6176 with both weighting
+    /// factors set to 1, the standard for ordinary viewing conditions.
+    pub fn from_cielab(lab: CIELABColor) -> DIN99Color {
+        let (sin, cos) = DIN99_ROTATION.to_radians().sin_cos();
+        let l = 105.51 * (1.0 + 0.0158 * lab.l).ln();
+        // rotate the opponent axes and shrink the second one
+        let e = lab.a * cos + lab.b * sin;
+        let f = DIN99_F_SCALE * (lab.b * cos - lab.a * sin);
+        // compress chroma logarithmically, keeping the hue angle
+        let g = e.hypot(f);
+        let c = (1.0 + DIN99_CHROMA * g).ln() / DIN99_CHROMA;
+        let (a, b) = if g == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (c * e / g, c * f / g)
+        };
+        DIN99Color { l, a, b }
+    }
+    /// Converts from DIN99 back to CIELAB, exactly inverting [`DIN99Color::from_cielab`].
+    ///
+    /// [`DIN99Color::from_cielab`]: #method.from_cielab
+    pub fn to_cielab(&self) -> CIELABColor {
+        let (sin, cos) = DIN99_ROTATION.to_radians().sin_cos();
+        let l = ((self.l / 105.51).exp() - 1.0) / 0.0158;
+        // undo the chroma compression
+        let c = self.a.hypot(self.b);
+        let g = ((DIN99_CHROMA * c).exp() - 1.0) / DIN99_CHROMA;
+        let (e, f) = if c == 0.0 {
+            (0.0, 0.0)
+        } else {
+            (g * self.a / c, g * self.b / c)
+        };
+        // now undo the scaling and rotation
+        let f = f / DIN99_F_SCALE;
+        CIELABColor {
+            l,
+            a: e * cos - f * sin,
+            b: e * sin + f * cos,
+        }
+    }
+}
+
+impl Color for DIN99Color {
+    /// Converts from XYZ to DIN99 by way of CIELAB.
+    fn from_xyz(xyz: XYZColor) -> DIN99Color {
+        DIN99Color::from_cielab(CIELABColor::from_xyz(xyz))
+    }
+    /// Converts from DIN99 back to XYZ by way of CIELAB, chromatically adapting it as CIELAB does.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        self.to_cielab().to_xyz(illuminant)
+    }
+}
+
+impl From<Coord> for DIN99Color {
+    fn from(c: Coord) -> DIN99Color {
+        DIN99Color {
+            l: c.x,
+            a: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<DIN99Color> for Coord {
+    fn from(val: DIN99Color) -> Self {
+        Coord {
+            x: val.l,
+            y: val.a,
+            z: val.b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use colorpoint::ColorPoint;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_din99_cielab_round_trip() {
+        for lab in [
+            CIELABColor {
+                l: 50.,
+                a: 10.,
+                b: 10.,
+            },
+            CIELABColor {
+                l: 0.,
+                a: 0.,
+                b: 0.,
+            },
+            CIELABColor {
+                l: 100.,
+                a: 0.,
+                b: 0.,
+            },
+            CIELABColor {
+                l: 35.,
+                a: -60.,
+                b: 45.,
+            },
+            CIELABColor {
+                l: 80.,
+                a: 70.,
+                b: -90.,
+            },
+        ]
+        .iter()
+        {
+            let din = DIN99Color::from_cielab(*lab);
+            let lab2 = din.to_cielab();
+            assert!((lab.l - lab2.l).abs() <= 1e-10);
+            assert!((lab.a - lab2.a).abs() <= 1e-10);
+            assert!((lab.b - lab2.b).abs() <= 1e-10);
+        }
+        // and through XYZ too
+        let xyz = XYZColor {
+            x: 0.2,
+            y: 0.42,
+            z: 0.23,
+            illuminant: Illuminant::D50,
+        };
+        let din: DIN99Color = xyz.convert();
+        let xyz2: XYZColor = din.convert();
+        assert!(xyz2.approx_equal(&xyz));
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_din99_values() {
+        // L99 = 105.51 ln(1 + 0.0158 L): white stays at 100
+        let white = DIN99Color::from_cielab(CIELABColor {
+            l: 100.,
+            a: 0.,
+            b: 0.,
+        });
+        assert!((white.l - 100.).abs() <= 0.01);
+        let din = DIN99Color::from_cielab(CIELABColor {
+            l: 50.,
+            a: 10.,
+            b: 10.,
+        });
+        assert!((din.l - 61.4296).abs() <= 1e-3);
+        assert!((din.a - 9.6988).abs() <= 1e-3);
+        assert!((din.b - 3.7633).abs() <= 1e-3);
+    }
+    #[test]
+    fn test_din99_distance_ranking() {
+        // pairs of colors whose CIEDE2000 differences are well separated should be ranked the same
+        // way by DIN99 Euclidean distance
+        let pairs = [
+            ("#808080", "#828282"),
+            ("#3050a0", "#3a58a0"),
+            ("#c04040", "#d06050"),
+            ("#40a040", "#60c080"),
+            ("#f0d020", "#a040c0"),
+        ];
+        let mut de2000 = vec![];
+        let mut din99 = vec![];
+        for (i, (hex1, hex2)) in pairs.iter().enumerate() {
+            let c1 = RGBColor::from_hex_code(hex1).unwrap();
+            let c2 = RGBColor::from_hex_code(hex2).unwrap();
+            let (d1, d2): (DIN99Color, DIN99Color) = (c1.convert(), c2.convert());
+            de2000.push((c1.distance(&c2), i));
+            din99.push((d1.euclidean_distance(d2), i));
+        }
+        de2000.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+        din99.sort_by(|x, y| x.0.partial_cmp(&y.0).unwrap());
+        let rank = |v: &Vec<(f64, usize)>| v.iter().map(|x| x.1).collect::<Vec<usize>>();
+        assert_eq!(rank(&de2000), rank(&din99));
+        assert_eq!(rank(&din99), vec![0, 1, 2, 3, 4]);
+    }
+}
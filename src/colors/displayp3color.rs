@@ -0,0 +1,172 @@
+//! This module implements Display P3, the wide-gamut RGB space used by Apple devices and most
+//! recent phones and laptops, and supported by CSS as `color(display-p3 ...)`. It uses the
+//! primaries of DCI-P3, the digital cinema standard, with the D65 white point and the same transfer
+//! curve as sRGB, so it behaves just like sRGB but with more saturated reds and greens.
+//!
+//! The primaries, as CIE 1931 xy chromaticities, are red (0.680, 0.320), green (0.265, 0.690), and
+//! blue (0.150, 0.060). The last is identical to sRGB's blue.
+
+use bound::Bound;
+use color::{Color, XYZColor};
+use colors::linearrgbcolor::{linear_to_srgb, srgb_to_linear};
+use consts::DISPLAY_P3_TRANSFORM as P3;
+use consts::DISPLAY_P3_TRANSFORM_LU as P3_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in the Display P3 color space, with gamma-encoded components that range from 0 to 1 for
+/// colors in gamut. Like [`RGBColor`], conversion into this space does not clamp, so colors outside
+/// of the gamut have components outside of that range.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::DisplayP3Color;
+/// // the reddest red a P3 display can show is too red for sRGB
+/// let p3_red = DisplayP3Color{r: 1., g: 0., b: 0.};
+/// let srgb_red: RGBColor = p3_red.convert();
+/// assert!(!srgb_red.is_in_gamut());
+/// ```
+///
+/// [`RGBColor`]: ../../color/struct.RGBColor.html
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct DisplayP3Color {
+    /// The red primary component, from 0 to 1 for colors in gamut.
+    pub r: f64,
+    /// The green primary component, from 0 to 1 for colors in gamut.
+    pub g: f64,
+    /// The blue primary component, from 0 to 1 for colors in gamut.
+    pub b: f64,
+}
+
+impl Color for DisplayP3Color {
+    /// Converts from XYZ to Display P3. As Display P3 uses D65 as its white point, any other
+    /// illuminant is chromatically adapted first.
+    fn from_xyz(xyz: XYZColor) -> DisplayP3Color {
+        let xyz_d65 = xyz.color_adapt(Illuminant::D65);
+        // the matrix goes from linear RGB to XYZ: solve it to go the other way
+        let lin_rgb = P3_LU
+            .solve(&vector![xyz_d65.x, xyz_d65.y, xyz_d65.z])
+            .expect("Matrix is invertible.");
+        // this is the sRGB transfer curve
+        DisplayP3Color {
+            r: linear_to_srgb(lin_rgb[0]),
+            g: linear_to_srgb(lin_rgb[1]),
+            b: linear_to_srgb(lin_rgb[2]),
+        }
+    }
+    /// Converts from Display P3 to XYZ, and then adapts from D65 to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let xyz = *P3
+            * vector![
+                srgb_to_linear(self.r),
+                srgb_to_linear(self.g),
+                srgb_to_linear(self.b)
+            ];
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for DisplayP3Color {
+    fn from(c: Coord) -> DisplayP3Color {
+        DisplayP3Color {
+            r: c.x,
+            g: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<DisplayP3Color> for Coord {
+    fn from(val: DisplayP3Color) -> Self {
+        Coord {
+            x: val.r,
+            y: val.g,
+            z: val.b,
+        }
+    }
+}
+
+impl Bound for DisplayP3Color {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 1.), (0., 1.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_display_p3_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.5,
+            z: 0.6,
+            illuminant: Illuminant::D50,
+        };
+        let p3 = DisplayP3Color::from_xyz(xyz);
+        let xyz2 = p3.to_xyz(Illuminant::D50);
+        assert!((xyz.x - xyz2.x).abs() <= 1e-14);
+        assert!((xyz.y - xyz2.y).abs() <= 1e-14);
+        assert!((xyz.z - xyz2.z).abs() <= 1e-14);
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_display_p3_primaries() {
+        let chromaticity = |xyz: XYZColor| {
+            let sum = xyz.x + xyz.y + xyz.z;
+            (xyz.x / sum, xyz.y / sum)
+        };
+        for (rgb, (x, y)) in [
+            ((1., 0., 0.), (0.680, 0.320)),
+            ((0., 1., 0.), (0.265, 0.690)),
+            ((0., 0., 1.), (0.150, 0.060)),
+        ]
+        .iter()
+        {
+            let (x2, y2) = chromaticity(
+                DisplayP3Color {
+                    r: rgb.0,
+                    g: rgb.1,
+                    b: rgb.2,
+                }
+                .to_xyz(Illuminant::D65),
+            );
+            assert!((x - x2).abs() <= 1e-6);
+            assert!((y - y2).abs() <= 1e-6);
+        }
+        // white is white
+        let white: DisplayP3Color = RGBColor::from_hex_code("#ffffff").unwrap().convert();
+        assert!((white.r - 1.).abs() <= 1e-3);
+        assert!((white.g - 1.).abs() <= 1e-3);
+        assert!((white.b - 1.).abs() <= 1e-3);
+    }
+    #[test]
+    fn test_srgb_red_in_display_p3() {
+        // sRGB red is inside P3, so it isn't at the edge of the gamut anymore: it has some green and
+        // blue, because P3's red primary is more orange and more saturated than sRGB's
+        let srgb_red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let red: DisplayP3Color = srgb_red.convert();
+        assert!((red.r - 0.9175).abs() <= 1e-3);
+        assert!((red.g - 0.2003).abs() <= 1e-3);
+        assert!((red.b - 0.1387).abs() <= 1e-3);
+        // but the chromaticity stays the same, (0.64, 0.33)
+        let xyz = red.to_xyz(Illuminant::D65);
+        let sum = xyz.x + xyz.y + xyz.z;
+        assert!((xyz.x / sum - 0.64).abs() <= 1e-3);
+        assert!((xyz.y / sum - 0.33).abs() <= 1e-3);
+        let srgb_red2: RGBColor = red.convert();
+        assert_eq!(srgb_red2.to_string(), "#FF0000");
+    }
+}
@@ -128,6 +128,9 @@ impl Color for HSVColor {
         let b = b1 + offset;
         RGBColor { r, g, b }.to_xyz(illuminant)
     }
+    fn hue_axis() -> Option<usize> {
+        Some(0)
+    }
 }
 
 impl From<Coord> for HSVColor {
@@ -0,0 +1,163 @@
+//! This module implements the IPT color space, introduced by Ebner and Fairchild in "Development and
+//! Testing of a Color Space (IPT) with Improved Hue Uniformity" (1998). Like CIELAB, it has a
+//! lightness (I) and two opponent color axes (P, roughly red-green, and T, roughly yellow-blue), but
+//! it was fit so that lines of constant perceived hue are as straight as possible. That makes it a
+//! good space for gamut mapping: reducing chroma toward the lightness axis in IPT keeps the hue
+//! looking the same, where in CIELAB blues notoriously drift toward purple.
+
+use color::{Color, XYZColor};
+use consts::IPT_LMS_TRANSFORM as LMS;
+use consts::IPT_LMS_TRANSFORM_LU as LMS_LU;
+use consts::IPT_TRANSFORM as IPT;
+use consts::IPT_TRANSFORM_LU as IPT_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// The exponent of the nonlinearity applied to the cone responses.
+const IPT_EXPONENT: f64 = 0.43;
+
+/// A color in the IPT color space. White has an I of 1, and P and T of 0.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::IPTColor;
+/// let blue: IPTColor = RGBColor::from_hex_code("#0000ff").unwrap().convert();
+/// // blue is in the negative T direction
+/// assert!(blue.t < -0.5);
+/// println!("hue angle: {}", blue.hue_angle());
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct IPTColor {
+    /// The lightness, from 0 for black to 1 for white.
+    pub i: f64,
+    /// The first opponent color axis, running from green (negative) to red (positive).
+    pub p: f64,
+    /// The second opponent color axis, running from blue (negative) to yellow (positive).
+    pub t: f64,
+}
+
+impl IPTColor {
+    /// Gets the hue angle of the color in degrees, between 0 and 360, measured counterclockwise from
+    /// the positive P axis toward the positive T axis, just like the hue of CIELCH is measured in
+    /// CIELAB. Colors that look like they have the same hue have very nearly the same angle.
+    pub fn hue_angle(&self) -> f64 {
+        self.t.atan2(self.p).to_degrees().rem_euclid(360.0)
+    }
+}
+
+impl Color for IPTColor {
+    /// Converts from XYZ to IPT. IPT is defined for D65, so any other illuminant is chromatically
+    /// adapted first.
+    fn from_xyz(xyz: XYZColor) -> IPTColor {
+        let xyz = xyz.color_adapt(Illuminant::D65);
+        let lms = *LMS * vector![xyz.x, xyz.y, xyz.z];
+        // the nonlinearity is applied to the magnitude, keeping the sign
+        let lms_prime = lms.map(|c| c.signum() * c.abs().powf(IPT_EXPONENT));
+        let ipt = *IPT * lms_prime;
+        IPTColor {
+            i: ipt[0],
+            p: ipt[1],
+            t: ipt[2],
+        }
+    }
+    /// Converts from IPT back to XYZ, using LU decomposition to invert both matrices precisely, and
+    /// then adapts from D65 to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let lms_prime = IPT_LU
+            .solve(&vector![self.i, self.p, self.t])
+            .expect("Matrix is invertible.");
+        let lms = lms_prime.map(|c| c.signum() * c.abs().powf(1.0 / IPT_EXPONENT));
+        let xyz = LMS_LU.solve(&lms).expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for IPTColor {
+    fn from(c: Coord) -> IPTColor {
+        IPTColor {
+            i: c.x,
+            p: c.y,
+            t: c.z,
+        }
+    }
+}
+
+impl From<IPTColor> for Coord {
+    fn from(val: IPTColor) -> Self {
+        Coord {
+            x: val.i,
+            y: val.p,
+            z: val.t,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_ipt_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.2,
+            y: 0.42,
+            z: 0.23,
+            illuminant: Illuminant::D50,
+        };
+        let ipt: IPTColor = xyz.convert();
+        let xyz2: XYZColor = ipt.convert();
+        assert!((xyz.x - xyz2.x).abs() <= 1e-14);
+        assert!((xyz.y - xyz2.y).abs() <= 1e-14);
+        assert!((xyz.z - xyz2.z).abs() <= 1e-14);
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_ipt_rgb_round_trip() {
+        for code in [
+            "#000000", "#ffffff", "#ff0000", "#00ff00", "#0000ff", "#3a7fd0",
+        ]
+        .iter()
+        {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            let ipt: IPTColor = rgb.convert();
+            let rgb2: RGBColor = ipt.convert();
+            assert_eq!(rgb.to_string(), rgb2.to_string());
+            assert!(rgb.distance(&rgb2) <= 1e-10);
+        }
+        // white is neutral
+        let white: IPTColor = RGBColor::from_hex_code("#ffffff").unwrap().convert();
+        assert!((white.i - 1.).abs() <= 1e-3);
+        assert!(white.p.abs() <= 1e-3);
+        assert!(white.t.abs() <= 1e-3);
+    }
+    #[test]
+    fn test_ipt_blue_hue_stable() {
+        // pure blue at any intensity has the same hue angle
+        let blue = RGBColor::from_hex_code("#0000ff").unwrap();
+        let blue_ipt: IPTColor = blue.convert();
+        let blue_xyz: XYZColor = blue.convert();
+        for &scale in [0.01, 0.1, 0.3, 0.6, 0.9].iter() {
+            let dim = XYZColor {
+                x: blue_xyz.x * scale,
+                y: blue_xyz.y * scale,
+                z: blue_xyz.z * scale,
+                illuminant: blue_xyz.illuminant,
+            };
+            let dim_ipt: IPTColor = dim.convert();
+            assert!(dim_ipt.i < blue_ipt.i);
+            assert!((dim_ipt.hue_angle() - blue_ipt.hue_angle()).abs() <= 1e-9);
+        }
+        assert!((blue_ipt.hue_angle() - 252.4).abs() <= 0.1);
+    }
+}
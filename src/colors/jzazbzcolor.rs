@@ -0,0 +1,246 @@
+//! This module implements the JzAzBz color space, introduced by Safdar et al. in "Perceptually
+//! uniform color space for image signals including high dynamic range and wide gamut" (2017). Like
+//! CIELAB, it has a lightness and two opponent color axes, but instead of a cube root it compresses
+//! cone responses with the PQ curve used for HDR video, which models vision over luminances from
+//! almost nothing to 10,000 cd/m². This makes it much more uniform than CIELAB for very bright and
+//! very saturated colors, and it predicts hue better in the blues.
+//!
+//! Unlike every other space in Scarlet, JzAzBz depends on *absolute* luminance. Scarlet's
+//! [`XYZColor`] is relative, with white at Y = 1, so the [`Color`] conversions assume that white is
+//! [`JZAZBZ_WHITE_LUMINANCE`] cd/m². For HDR data, where the actual luminance matters, use
+//! [`JzAzBzColor::from_absolute_xyz`] and [`JzAzBzColor::to_absolute_xyz`] instead.
+//!
+//! [`XYZColor`]: ../../color/struct.XYZColor.html
+//! [`Color`]: ../../color/trait.Color.html
+//! [`JZAZBZ_WHITE_LUMINANCE`]: constant.JZAZBZ_WHITE_LUMINANCE.html
+//! [`JzAzBzColor::from_absolute_xyz`]: struct.JzAzBzColor.html#method.from_absolute_xyz
+//! [`JzAzBzColor::to_absolute_xyz`]: struct.JzAzBzColor.html#method.to_absolute_xyz
+
+use color::{Color, XYZColor};
+use consts::JZAZBZ_IAB_TRANSFORM as IAB;
+use consts::JZAZBZ_IAB_TRANSFORM_LU as IAB_LU;
+use consts::JZAZBZ_LMS_TRANSFORM as LMS;
+use consts::JZAZBZ_LMS_TRANSFORM_LU as LMS_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// The luminance, in cd/m², that relative white (Y = 1) is assumed to have when converting between
+/// JzAzBz and other colors through the [`Color`] trait. This is the 203 cd/m² of diffuse white
+/// recommended for HDR content by ITU-R BT.2408, which also keeps SDR colors well within the range
+/// where JzAzBz was fit to data.
+///
+/// [`Color`]: ../../color/trait.Color.html
+pub const JZAZBZ_WHITE_LUMINANCE: f64 = 203.0;
+
+// the constants of the PQ curve, as given in SMPTE ST 2084, except for the exponent p, which is
+// changed for JzAzBz
+const C1: f64 = 3424.0 / 4096.0;
+const C2: f64 = 2413.0 / 128.0;
+const C3: f64 = 2392.0 / 128.0;
+const N: f64 = 2610.0 / 16384.0;
+const P: f64 = 1.7 * 2523.0 / 32.0;
+// the constants that adjust lightness and put black at 0
+const D: f64 = -0.56;
+const D0: f64 = 1.629_549_953_282_156_6e-11;
+// the constants that adjust X and Y to improve blue hue prediction
+const B: f64 = 1.15;
+const G: f64 = 0.66;
+
+/// A color in the JzAzBz color space, with a lightness and two opponent color axes.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::JzAzBzColor;
+/// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+/// let jab: JzAzBzColor = red.convert();
+/// // red is in the positive az direction
+/// assert!(jab.az > 0.);
+/// let red2: RGBColor = jab.convert();
+/// assert_eq!(red2.to_string(), "#FF0000");
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct JzAzBzColor {
+    /// The lightness. 0 is black: [`JZAZBZ_WHITE_LUMINANCE`] maps to roughly 0.13, and the
+    /// brightest light the space covers, 10,000 cd/m², maps to roughly 1.
+    ///
+    /// [`JZAZBZ_WHITE_LUMINANCE`]: constant.JZAZBZ_WHITE_LUMINANCE.html
+    pub jz: f64,
+    /// The first opponent color axis, running from green (negative) to red (positive). Values are
+    /// on a much smaller scale than in CIELAB, rarely more than 0.5 in either direction.
+    pub az: f64,
+    /// The second opponent color axis, running from blue (negative) to yellow (positive), on the
+    /// same scale as `az`.
+    pub bz: f64,
+}
+
+impl JzAzBzColor {
+    /// Converts from XYZ given in absolute units, with Y as the luminance in cd/m², to JzAzBz.
+    /// JzAzBz is defined for D65, so any other illuminant is chromatically adapted first.
+    pub fn from_absolute_xyz(xyz: XYZColor) -> JzAzBzColor {
+        let xyz = xyz.color_adapt(Illuminant::D65);
+        // adjust X and Y, then get cone responses
+        let x = B * xyz.x - (B - 1.0) * xyz.z;
+        let y = G * xyz.y - (G - 1.0) * xyz.x;
+        let lms = *LMS * vector![x, y, xyz.z];
+        // compress them with the PQ curve
+        let pq = |c: f64| {
+            let c = (c / 10000.0).powf(N);
+            ((C1 + C2 * c) / (1.0 + C3 * c)).powf(P)
+        };
+        let iab = *IAB * lms.map(pq);
+        // now adjust lightness
+        let jz = (1.0 + D) * iab[0] / (1.0 + D * iab[0]) - D0;
+        JzAzBzColor {
+            jz,
+            az: iab[1],
+            bz: iab[2],
+        }
+    }
+    /// Converts from JzAzBz to XYZ in absolute units, with Y as the luminance in cd/m². This is the
+    /// inverse of [`JzAzBzColor::from_absolute_xyz`], and the result is in D65.
+    ///
+    /// [`JzAzBzColor::from_absolute_xyz`]: #method.from_absolute_xyz
+    pub fn to_absolute_xyz(&self) -> XYZColor {
+        let jz = self.jz + D0;
+        let iz = jz / (1.0 + D - D * jz);
+        // invert the matrices with LU decomposition for accuracy
+        let lms_pq = IAB_LU
+            .solve(&vector![iz, self.az, self.bz])
+            .expect("Matrix is invertible.");
+        let pq_inv = |c: f64| {
+            let c = c.powf(1.0 / P);
+            10000.0 * ((C1 - c) / (C3 * c - C2)).powf(1.0 / N)
+        };
+        let xyz_adj = LMS_LU
+            .solve(&lms_pq.map(pq_inv))
+            .expect("Matrix is invertible.");
+        // undo the adjustment of X and Y
+        let z = xyz_adj[2];
+        let x = (xyz_adj[0] + (B - 1.0) * z) / B;
+        let y = (xyz_adj[1] + (G - 1.0) * x) / G;
+        XYZColor {
+            x,
+            y,
+            z,
+            illuminant: Illuminant::D65,
+        }
+    }
+}
+
+impl Color for JzAzBzColor {
+    /// Converts from relative XYZ to JzAzBz, taking white to have a luminance of
+    /// [`JZAZBZ_WHITE_LUMINANCE`].
+    ///
+    /// [`JZAZBZ_WHITE_LUMINANCE`]: constant.JZAZBZ_WHITE_LUMINANCE.html
+    fn from_xyz(xyz: XYZColor) -> JzAzBzColor {
+        JzAzBzColor::from_absolute_xyz(XYZColor {
+            x: xyz.x * JZAZBZ_WHITE_LUMINANCE,
+            y: xyz.y * JZAZBZ_WHITE_LUMINANCE,
+            z: xyz.z * JZAZBZ_WHITE_LUMINANCE,
+            illuminant: xyz.illuminant,
+        })
+    }
+    /// Converts from JzAzBz to relative XYZ, taking white to have a luminance of
+    /// [`JZAZBZ_WHITE_LUMINANCE`], and then adapts to the given illuminant.
+    ///
+    /// [`JZAZBZ_WHITE_LUMINANCE`]: constant.JZAZBZ_WHITE_LUMINANCE.html
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let xyz = self.to_absolute_xyz();
+        XYZColor {
+            x: xyz.x / JZAZBZ_WHITE_LUMINANCE,
+            y: xyz.y / JZAZBZ_WHITE_LUMINANCE,
+            z: xyz.z / JZAZBZ_WHITE_LUMINANCE,
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for JzAzBzColor {
+    fn from(c: Coord) -> JzAzBzColor {
+        JzAzBzColor {
+            jz: c.x,
+            az: c.y,
+            bz: c.z,
+        }
+    }
+}
+
+impl From<JzAzBzColor> for Coord {
+    fn from(val: JzAzBzColor) -> Self {
+        Coord {
+            x: val.jz,
+            y: val.az,
+            z: val.bz,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_jzazbz_reference_values() {
+        // the paper gives no worked examples: these are the reference values used by the colour
+        // Python library, computed from the same formulas, and match to all 8 digits given
+        let xyz = XYZColor {
+            x: 0.20654008,
+            y: 0.12197225,
+            z: 0.05136952,
+            illuminant: Illuminant::D65,
+        };
+        let jab = JzAzBzColor::from_absolute_xyz(xyz);
+        assert!((jab.jz - 0.00535048).abs() <= 1e-8);
+        assert!((jab.az - 0.00924302).abs() <= 1e-8);
+        assert!((jab.bz - 0.00526007).abs() <= 1e-8);
+        // black is 0
+        let black = JzAzBzColor::from_absolute_xyz(XYZColor {
+            x: 0.,
+            y: 0.,
+            z: 0.,
+            illuminant: Illuminant::D65,
+        });
+        assert!(black.jz.abs() <= 1e-12);
+        // white is close to neutral
+        let white: JzAzBzColor = XYZColor::white_point(Illuminant::D65).convert();
+        assert!(white.az.abs() <= 1e-3);
+        assert!(white.bz.abs() <= 1e-3);
+    }
+    #[test]
+    fn test_jzazbz_absolute_round_trip() {
+        // from dim to very bright HDR highlights
+        for &lum in [0.1, 1., 100., 1000., 8000.].iter() {
+            let xyz = XYZColor {
+                x: 0.4 * lum,
+                y: 0.5 * lum,
+                z: 0.3 * lum,
+                illuminant: Illuminant::D65,
+            };
+            let xyz2 = JzAzBzColor::from_absolute_xyz(xyz).to_absolute_xyz();
+            assert!((xyz.x - xyz2.x).abs() <= 1e-9 * lum);
+            assert!((xyz.y - xyz2.y).abs() <= 1e-9 * lum);
+            assert!((xyz.z - xyz2.z).abs() <= 1e-9 * lum);
+        }
+    }
+    #[test]
+    fn test_jzazbz_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.2,
+            y: 0.42,
+            z: 0.23,
+            illuminant: Illuminant::D50,
+        };
+        let jab: JzAzBzColor = xyz.convert();
+        let xyz2: XYZColor = jab.convert();
+        // the PQ curve raises to a power of about 134, so undoing it loses a few more digits than
+        // the other spaces do
+        assert!((xyz.x - xyz2.x).abs() <= 1e-12);
+        assert!((xyz.y - xyz2.y).abs() <= 1e-12);
+        assert!((xyz.z - xyz2.z).abs() <= 1e-12);
+        assert!(xyz.distance(&xyz2) <= 1e-10);
+    }
+}
@@ -0,0 +1,217 @@
+//! This module implements linear-light sRGB: the same primaries and white point as [`RGBColor`], but
+//! without the transfer function, so each component is proportional to the amount of light emitted.
+//! [`RGBColor`] is gamma-encoded to spend its precision where the eye is most sensitive, which makes
+//! it the right choice for storage and display, but the wrong one for arithmetic. Mixing two colors,
+//! blurring an image, or scaling brightness all model light adding together, and light only adds
+//! linearly in linear light.
+//!
+//! This is why using [`weighted_midpoint`] on two [`RGBColor`]s is technically incorrect: the
+//! midpoint of black and white in gamma-encoded sRGB is `#808080`, which emits only about 22% as much
+//! light as white, not half. Converting to `LinearRGBColor`, blending, and converting back gives the
+//! physically correct result, `#BCBCBC`.
+//!
+//! [`RGBColor`]: ../../color/struct.RGBColor.html
+//! [`weighted_midpoint`]: ../../colorpoint/trait.ColorPoint.html#method.weighted_midpoint
+
+use bound::Bound;
+use color::{Color, RGBColor, XYZColor};
+use consts::STANDARD_RGB_TRANSFORM as SRGB;
+use consts::STANDARD_RGB_TRANSFORM_LU as SRGB_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in linear-light sRGB, with components that range from 0 to 1 for colors in gamut. Like
+/// [`RGBColor`], conversion into this space does not clamp.
+///
+/// # Example
+/// Blending in linear light keeps the brightness of the mix where it should be.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::LinearRGBColor;
+/// let black = RGBColor::from_hex_code("#000000").unwrap();
+/// let white = RGBColor::from_hex_code("#ffffff").unwrap();
+/// // naive blending of gamma-encoded values is too dark
+/// assert_eq!(black.midpoint(white).to_string(), "#808080");
+/// // blending in linear light isn't
+/// let mid = LinearRGBColor::from_srgb(black).midpoint(LinearRGBColor::from_srgb(white));
+/// assert_eq!(mid.to_srgb().to_string(), "#BCBCBC");
+/// ```
+///
+/// [`RGBColor`]: ../../color/struct.RGBColor.html
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct LinearRGBColor {
+    /// The linear red component, from 0 to 1 for colors in gamut.
+    pub r: f64,
+    /// The linear green component, from 0 to 1 for colors in gamut.
+    pub g: f64,
+    /// The linear blue component, from 0 to 1 for colors in gamut.
+    pub b: f64,
+}
+
+impl LinearRGBColor {
+    /// Decodes a gamma-encoded sRGB color into linear light, using the piecewise sRGB transfer
+    /// function. This doesn't need to go through XYZ, so it's exact and fast.
+    pub fn from_srgb(rgb: RGBColor) -> LinearRGBColor {
+        LinearRGBColor {
+            r: srgb_to_linear(rgb.r),
+            g: srgb_to_linear(rgb.g),
+            b: srgb_to_linear(rgb.b),
+        }
+    }
+    /// Encodes this color with the sRGB transfer function, giving the [`RGBColor`] that looks the
+    /// same. The inverse of `from_srgb`.
+    ///
+    /// [`RGBColor`]: ../../color/struct.RGBColor.html
+    pub fn to_srgb(&self) -> RGBColor {
+        RGBColor {
+            r: linear_to_srgb(self.r),
+            g: linear_to_srgb(self.g),
+            b: linear_to_srgb(self.b),
+        }
+    }
+}
+
+/// Decodes a single gamma-encoded sRGB component into linear light, using the piecewise sRGB
+/// transfer function. Everything else that needs the sRGB curve uses this or `linear_to_srgb`, so
+/// there is only one copy of it.
+pub(crate) fn srgb_to_linear(x: f64) -> f64 {
+    if x <= 0.04045 {
+        x / 12.92
+    } else {
+        ((x + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Encodes a single linear-light component with the sRGB transfer function. The inverse of
+/// `srgb_to_linear`.
+pub(crate) fn linear_to_srgb(x: f64) -> f64 {
+    if x <= 0.0031308 {
+        12.92 * x
+    } else {
+        1.055 * x.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+impl Color for LinearRGBColor {
+    /// Converts from XYZ to linear sRGB. As sRGB uses D65 as its white point, any other illuminant
+    /// is chromatically adapted first.
+    fn from_xyz(xyz: XYZColor) -> LinearRGBColor {
+        let xyz_d65 = xyz.color_adapt(Illuminant::D65);
+        let lin_rgb = *SRGB * vector![xyz_d65.x, xyz_d65.y, xyz_d65.z];
+        LinearRGBColor {
+            r: lin_rgb[0],
+            g: lin_rgb[1],
+            b: lin_rgb[2],
+        }
+    }
+    fn from_rgb(rgb: RGBColor) -> LinearRGBColor {
+        LinearRGBColor::from_srgb(rgb)
+    }
+    /// Converts from linear sRGB to XYZ, and then adapts from D65 to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let xyz = SRGB_LU
+            .solve(&vector![self.r, self.g, self.b])
+            .expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for LinearRGBColor {
+    fn from(c: Coord) -> LinearRGBColor {
+        LinearRGBColor {
+            r: c.x,
+            g: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<LinearRGBColor> for Coord {
+    fn from(val: LinearRGBColor) -> Self {
+        Coord {
+            x: val.r,
+            y: val.g,
+            z: val.b,
+        }
+    }
+}
+
+impl Bound for LinearRGBColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 1.), (0., 1.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use colorpoint::ColorPoint;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_linear_rgb_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.5,
+            z: 0.6,
+            illuminant: Illuminant::D50,
+        };
+        let rgb = LinearRGBColor::from_xyz(xyz);
+        let xyz2 = rgb.to_xyz(Illuminant::D50);
+        assert!((xyz.x - xyz2.x).abs() <= 1e-14);
+        assert!((xyz.y - xyz2.y).abs() <= 1e-14);
+        assert!((xyz.z - xyz2.z).abs() <= 1e-14);
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_linear_gray_companding() {
+        let gray = LinearRGBColor {
+            r: 0.5,
+            g: 0.5,
+            b: 0.5,
+        };
+        let srgb = gray.to_srgb();
+        assert!((srgb.r - 0.735).abs() <= 1e-3);
+        assert!((srgb.g - 0.735).abs() <= 1e-3);
+        assert!((srgb.b - 0.735).abs() <= 1e-3);
+        let gray2 = LinearRGBColor::from_srgb(srgb);
+        assert!((gray2.r - 0.5).abs() <= 1e-12);
+        // the linear segment near black round trips too
+        let dark = LinearRGBColor::from_srgb(RGBColor {
+            r: 0.01,
+            g: 0.02,
+            b: 0.5,
+        })
+        .to_srgb();
+        assert!((dark.r - 0.01).abs() <= 1e-12);
+        assert!((dark.g - 0.02).abs() <= 1e-12);
+        assert!((dark.b - 0.5).abs() <= 1e-12);
+    }
+    #[test]
+    fn test_linear_rgb_matches_xyz_path() {
+        // companding directly agrees with going through XYZ
+        let rgb = RGBColor::from_hex_code("#3a7fd0").unwrap();
+        let direct = LinearRGBColor::from_srgb(rgb);
+        let via_xyz = LinearRGBColor::from_xyz(rgb.to_xyz(Illuminant::D65));
+        assert!((direct.r - via_xyz.r).abs() <= 1e-12);
+        assert!((direct.g - via_xyz.g).abs() <= 1e-12);
+        assert!((direct.b - via_xyz.b).abs() <= 1e-12);
+        let rgb2: RGBColor = direct.convert();
+        assert_eq!(rgb.to_string(), rgb2.to_string());
+        // blending is brighter than in gamma-encoded space
+        let black = RGBColor::from_hex_code("#000000").unwrap();
+        let white = RGBColor::from_hex_code("#ffffff").unwrap();
+        let mid = LinearRGBColor::from_srgb(black)
+            .midpoint(LinearRGBColor::from_srgb(white))
+            .to_srgb();
+        assert!(mid.r > black.midpoint(white).r);
+    }
+}
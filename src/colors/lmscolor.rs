@@ -0,0 +1,246 @@
+//! This module implements the LMS color space, which describes a color by how strongly it excites
+//! each of the three kinds of cone cells in the human eye: the long-wavelength (L), medium-wavelength
+//! (M), and short-wavelength (S) cones. Because it models the first stage of vision directly, LMS is
+//! the natural space for simulating color vision deficiencies: a person with dichromacy is missing
+//! one of these cone types, and what they see can be modeled by reconstructing that cone's response
+//! from the other two. This uses the Hunt-Pointer-Estévez transform from XYZ, normalized so that
+//! D65 white has equal L, M, and S responses of 1: this is the same matrix von Kries chromatic
+//! adaptation uses.
+
+use bound::Bound;
+use color::{Color, CvdKind, XYZColor};
+// the von Kries matrix is the Hunt-Pointer-Estévez transform, normalized to D65
+use consts::VON_KRIES_TRANSFORM as HPE;
+use consts::VON_KRIES_TRANSFORM_LU as HPE_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in the LMS color space, given as the responses of the long-, medium-, and
+/// short-wavelength cones. D65 white has a response of 1 for all three, up to rounding.
+///
+/// # Example
+/// A person with deuteranopia has no working M cones, so the M response of a color can be
+/// reconstructed from its L and S responses without changing what they see.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::color::CvdKind;
+/// # use scarlet::colors::LMSColor;
+/// let olive: LMSColor = RGBColor::from_hex_code("#808000").unwrap().convert();
+/// let seen = olive.to_dichromat(CvdKind::Deuteranopia);
+/// // the L and S responses are still there...
+/// assert_eq!(seen.l, olive.l);
+/// assert_eq!(seen.s, olive.s);
+/// // ...but the M response is whatever the other two imply
+/// assert!(seen.m != olive.m);
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct LMSColor {
+    /// The response of the long-wavelength cones, which are most sensitive to yellowish-green light.
+    pub l: f64,
+    /// The response of the medium-wavelength cones, which are most sensitive to green light.
+    pub m: f64,
+    /// The response of the short-wavelength cones, which are most sensitive to blue light.
+    pub s: f64,
+}
+
+/// Gets the cone responses to the pure spectral light of the given wavelength in nanometers, as
+/// tabulated in the CIE 1931 standard observer data. Only the wavelengths used as anchors for
+/// dichromacy are needed, so only those are listed.
+fn spectral_lms(wavelength: u32) -> LMSColor {
+    // values taken from cie-1931-standard-matching.csv
+    let (x, y, z) = match wavelength {
+        475 => (0.14210000, 0.11260000, 1.04190000),
+        485 => (0.05795001, 0.16930000, 0.61620000),
+        575 => (0.84250000, 0.91540000, 0.00180000),
+        660 => (0.16490000, 0.06100000, 0.00000000),
+        _ => unreachable!("no spectral data for {} nm", wavelength),
+    };
+    LMSColor::from_xyz(XYZColor {
+        x,
+        y,
+        z,
+        illuminant: Illuminant::D65,
+    })
+}
+
+/// Computes the cross product of two colors as vectors in LMS space.
+fn cross(a: LMSColor, b: LMSColor) -> [f64; 3] {
+    [
+        a.m * b.s - a.s * b.m,
+        a.s * b.l - a.l * b.s,
+        a.l * b.m - a.m * b.l,
+    ]
+}
+
+impl LMSColor {
+    /// Simulates how a color is seen by a person with full dichromacy of the given kind, following
+    /// the method of Brettel, Viénot, and Mollon (1997). The response of the missing cone is
+    /// replaced by one reconstructed from the other two, so that the result lies on the surface of
+    /// colors the dichromat sees the same way as a trichromat would. That surface is made of two
+    /// half-planes, each spanned by the neutral axis through white and by a spectral color that
+    /// dichromats are known to see unchanged: 475 and 575 nm for protanopia and deuteranopia, and
+    /// 485 and 660 nm for tritanopia. The two responses that remain are kept exactly as they were.
+    pub fn to_dichromat(&self, kind: CvdKind) -> LMSColor {
+        let (missing, anchors) = match kind {
+            CvdKind::Protanopia => (0, (475, 575)),
+            CvdKind::Deuteranopia => (1, (475, 575)),
+            CvdKind::Tritanopia => (2, (485, 660)),
+        };
+        let white = LMSColor::from_xyz(XYZColor::white_point(Illuminant::D65));
+        let (anchor1, anchor2) = (spectral_lms(anchors.0), spectral_lms(anchors.1));
+        let lms = [self.l, self.m, self.s];
+        let dot = |n: [f64; 3], v: [f64; 3]| n[0] * v[0] + n[1] * v[1] + n[2] * v[2];
+
+        // the plane through white and the missing cone's axis separates the two half-planes: use
+        // whichever one is on the same side as the color
+        let mut axis = LMSColor {
+            l: 0.,
+            m: 0.,
+            s: 0.,
+        };
+        match missing {
+            0 => axis.l = 1.,
+            1 => axis.m = 1.,
+            _ => axis.s = 1.,
+        };
+        let separator = cross(white, axis);
+        let anchor1_side = dot(separator, [anchor1.l, anchor1.m, anchor1.s]);
+        let anchor = if dot(separator, lms) * anchor1_side >= 0.0 {
+            anchor1
+        } else {
+            anchor2
+        };
+
+        // now solve for the missing response that puts the color on that half-plane
+        let normal = cross(white, anchor);
+        let rest: f64 = (0..3)
+            .filter(|&i| i != missing)
+            .map(|i| normal[i] * lms[i])
+            .sum();
+        let mut projected = lms;
+        projected[missing] = -rest / normal[missing];
+        LMSColor {
+            l: projected[0],
+            m: projected[1],
+            s: projected[2],
+        }
+    }
+}
+
+impl Color for LMSColor {
+    /// Converts from XYZ to LMS. LMS is defined relative to D65 here, so any other illuminant is
+    /// chromatically adapted first.
+    fn from_xyz(xyz: XYZColor) -> LMSColor {
+        let xyz_d65 = xyz.color_adapt(Illuminant::D65);
+        let lms = *HPE * vector![xyz_d65.x, xyz_d65.y, xyz_d65.z];
+        LMSColor {
+            l: lms[0],
+            m: lms[1],
+            s: lms[2],
+        }
+    }
+    /// Converts from LMS back to XYZ, using LU decomposition to invert the transform precisely, and
+    /// then adapts from D65 to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let xyz = HPE_LU
+            .solve(&vector![self.l, self.m, self.s])
+            .expect("Matrix is invertible.");
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for LMSColor {
+    fn from(c: Coord) -> LMSColor {
+        LMSColor {
+            l: c.x,
+            m: c.y,
+            s: c.z,
+        }
+    }
+}
+
+impl From<LMSColor> for Coord {
+    fn from(val: LMSColor) -> Self {
+        Coord {
+            x: val.l,
+            y: val.m,
+            z: val.s,
+        }
+    }
+}
+
+impl Bound for LMSColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 1.), (0., 1.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_lms_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.5,
+            z: 0.6,
+            illuminant: Illuminant::D50,
+        };
+        let lms = LMSColor::from_xyz(xyz);
+        let xyz2 = lms.to_xyz(Illuminant::D50);
+        assert!(xyz.approx_equal(&xyz2));
+    }
+    #[test]
+    fn test_lms_white() {
+        let white = LMSColor::from_xyz(XYZColor::white_point(Illuminant::D65));
+        // the published matrix is rounded to 5 decimal places
+        assert!((white.l - 1.).abs() <= 1e-3);
+        assert!((white.m - 1.).abs() <= 1e-3);
+        assert!((white.s - 1.).abs() <= 1e-3);
+    }
+    #[test]
+    fn test_dichromat_fixed_points() {
+        // white and the anchor colors are seen the same way by every kind of dichromat
+        let white = LMSColor::from_xyz(XYZColor::white_point(Illuminant::D65));
+        for (kind, anchors) in [
+            (CvdKind::Protanopia, [475, 575]),
+            (CvdKind::Deuteranopia, [475, 575]),
+            (CvdKind::Tritanopia, [485, 660]),
+        ]
+        .iter()
+        {
+            for color in [white, spectral_lms(anchors[0]), spectral_lms(anchors[1])].iter() {
+                let seen = color.to_dichromat(*kind);
+                assert!((seen.l - color.l).abs() <= 1e-10);
+                assert!((seen.m - color.m).abs() <= 1e-10);
+                assert!((seen.s - color.s).abs() <= 1e-10);
+            }
+        }
+    }
+    #[test]
+    fn test_dichromat_idempotent() {
+        let color: LMSColor = RGBColor::from_hex_code("#3a7fd0").unwrap().convert();
+        for kind in [
+            CvdKind::Protanopia,
+            CvdKind::Deuteranopia,
+            CvdKind::Tritanopia,
+        ]
+        .iter()
+        {
+            let once = color.to_dichromat(*kind);
+            let twice = once.to_dichromat(*kind);
+            assert!(once.distance(&twice) <= TEST_PRECISION);
+        }
+    }
+}
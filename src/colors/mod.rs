@@ -3,20 +3,46 @@
 //!
 //! [`Color`]: ../color/trait.Color.html
 pub mod adobergbcolor;
+pub mod alphacolor;
 pub mod cielabcolor;
 pub mod cielchcolor;
 pub mod cielchuvcolor;
 pub mod cieluvcolor;
+pub mod cmykcolor;
+pub mod din99color;
+pub mod displayp3color;
 pub mod hslcolor;
 pub mod hsvcolor;
+pub mod iptcolor;
+pub mod jzazbzcolor;
+pub mod linearrgbcolor;
+pub mod lmscolor;
+pub mod prophotocolor;
+pub mod rec2020color;
+pub mod rgbacolor;
 pub mod rommrgbcolor;
+pub mod yiqcolor;
+pub mod yuvcolor;
 
 // for convenience, use this namespace for the color objects
 pub use self::adobergbcolor::AdobeRGBColor;
+pub use self::alphacolor::AlphaColor;
 pub use self::cielabcolor::CIELABColor;
 pub use self::cielchcolor::CIELCHColor;
 pub use self::cielchuvcolor::CIELCHuvColor;
 pub use self::cieluvcolor::CIELUVColor;
+pub use self::cmykcolor::CMYKColor;
+pub use self::din99color::DIN99Color;
+pub use self::displayp3color::DisplayP3Color;
 pub use self::hslcolor::HSLColor;
 pub use self::hsvcolor::HSVColor;
+pub use self::iptcolor::IPTColor;
+pub use self::jzazbzcolor::JzAzBzColor;
+pub use self::linearrgbcolor::LinearRGBColor;
+pub use self::lmscolor::LMSColor;
+pub use self::prophotocolor::ProPhotoRGBColor;
+pub use self::rec2020color::Rec2020Color;
+pub use self::rgbacolor::RGBAColor;
 pub use self::rommrgbcolor::ROMMRGBColor;
+pub use self::yiqcolor::YIQColor;
+pub use self::yuvcolor::YUVColor;
@@ -0,0 +1,204 @@
+//! This module implements ProPhoto RGB, also known as ROMM RGB, as specified in ISO 22028-2. It is
+//! a very wide-gamut RGB space designed by Kodak for editing photographs: its primaries enclose
+//! nearly every surface color a camera can capture, at the cost of including some imaginary colors.
+//! Its white point is D50, the same one Scarlet uses internally and the one print workflows use, so
+//! no chromatic adaptation is needed for most conversions.
+//!
+//! The primaries, as CIE 1931 xy chromaticities, are red (0.7347, 0.2653), green (0.1596, 0.8404),
+//! and blue (0.0366, 0.0001). Values are encoded with a gamma of 1.8, except that linear values
+//! below 1/512 are instead multiplied by 16 to avoid an infinite slope at black.
+//!
+//! Scarlet's older [`ROMMRGBColor`] uses the same primaries but doesn't follow the specification,
+//! so the two give different components for the same color: see its module documentation.
+//!
+//! [`ROMMRGBColor`]: ../rommrgbcolor/struct.ROMMRGBColor.html
+
+use bound::Bound;
+use color::{Color, XYZColor};
+use consts::ROMM_RGB_TRANSFORM as ROMM;
+use consts::ROMM_RGB_TRANSFORM_LU as ROMM_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in the ProPhoto RGB color space, with gamma-encoded components that range from 0 to 1
+/// for colors in gamut. Like [`RGBColor`], conversion into this space does not clamp, so colors
+/// outside of the gamut have components outside of that range.
+///
+/// # Example
+/// ProPhoto RGB can hold colors far more saturated than sRGB's.
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::ProPhotoRGBColor;
+/// let green = ProPhotoRGBColor{r: 0.2, g: 0.8, b: 0.1};
+/// let srgb_green: RGBColor = green.convert();
+/// assert!(!srgb_green.is_in_gamut());
+/// // but every sRGB color fits in ProPhoto RGB
+/// let srgb_red = RGBColor::from_hex_code("#ff0000").unwrap();
+/// let red: ProPhotoRGBColor = srgb_red.convert();
+/// assert!(red.r <= 1. && red.g >= 0. && red.b >= 0.);
+/// ```
+///
+/// [`RGBColor`]: ../../color/struct.RGBColor.html
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct ProPhotoRGBColor {
+    /// The red primary component, from 0 to 1 for colors in gamut.
+    pub r: f64,
+    /// The green primary component, from 0 to 1 for colors in gamut.
+    pub g: f64,
+    /// The blue primary component, from 0 to 1 for colors in gamut.
+    pub b: f64,
+}
+
+impl Color for ProPhotoRGBColor {
+    /// Converts from XYZ to ProPhoto RGB. As ProPhoto RGB uses D50 as its white point, any other
+    /// illuminant is chromatically adapted first.
+    fn from_xyz(xyz: XYZColor) -> ProPhotoRGBColor {
+        let xyz_d50 = xyz.color_adapt(Illuminant::D50);
+        // the specification gives the matrix from linear RGB to XYZ: solve it to go the other way
+        let lin_rgb = ROMM_LU
+            .solve(&vector![xyz_d50.x, xyz_d50.y, xyz_d50.z])
+            .expect("Matrix is invertible.");
+        let gamma = |x: f64| {
+            if x < 1.0 / 512.0 {
+                16.0 * x
+            } else {
+                x.powf(1.0 / 1.8)
+            }
+        };
+        ProPhotoRGBColor {
+            r: gamma(lin_rgb[0]),
+            g: gamma(lin_rgb[1]),
+            b: gamma(lin_rgb[2]),
+        }
+    }
+    /// Converts from ProPhoto RGB to XYZ, and then adapts from D50 to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        // 16 / 512 is where the two parts of the encoding meet
+        let gamma_inv = |x: f64| {
+            if x < 16.0 / 512.0 {
+                x / 16.0
+            } else {
+                x.powf(1.8)
+            }
+        };
+        let xyz = *ROMM * vector![gamma_inv(self.r), gamma_inv(self.g), gamma_inv(self.b)];
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D50,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for ProPhotoRGBColor {
+    fn from(c: Coord) -> ProPhotoRGBColor {
+        ProPhotoRGBColor {
+            r: c.x,
+            g: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<ProPhotoRGBColor> for Coord {
+    fn from(val: ProPhotoRGBColor) -> Self {
+        Coord {
+            x: val.r,
+            y: val.g,
+            z: val.b,
+        }
+    }
+}
+
+impl Bound for ProPhotoRGBColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 1.), (0., 1.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_prophoto_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.5,
+            z: 0.6,
+            illuminant: Illuminant::D50,
+        };
+        let rgb = ProPhotoRGBColor::from_xyz(xyz);
+        let xyz2 = rgb.to_xyz(Illuminant::D50);
+        assert!((xyz.x - xyz2.x).abs() <= 1e-14);
+        assert!((xyz.y - xyz2.y).abs() <= 1e-14);
+        assert!((xyz.z - xyz2.z).abs() <= 1e-14);
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+    #[test]
+    fn test_prophoto_srgb_round_trip() {
+        // includes values on both sides of the linear segment
+        for code in [
+            "#000000", "#010101", "#ffffff", "#ff0000", "#00ff00", "#3a7fd0",
+        ]
+        .iter()
+        {
+            let srgb = RGBColor::from_hex_code(code).unwrap();
+            let prophoto: ProPhotoRGBColor = srgb.convert();
+            let srgb2: RGBColor = prophoto.convert();
+            assert!((srgb.r - srgb2.r).abs() <= 1e-10);
+            assert!((srgb.g - srgb2.g).abs() <= 1e-10);
+            assert!((srgb.b - srgb2.b).abs() <= 1e-10);
+        }
+        // and an out-of-gamut ProPhoto color survives too
+        let rgb = ProPhotoRGBColor {
+            r: 1.1,
+            g: -0.05,
+            b: 0.001,
+        };
+        let rgb2 = ProPhotoRGBColor::from_xyz(rgb.to_xyz(Illuminant::D50));
+        assert!((rgb.r - rgb2.r).abs() <= 1e-12);
+        assert!((rgb.g - rgb2.g).abs() <= 1e-12);
+        assert!((rgb.b - rgb2.b).abs() <= 1e-12);
+    }
+    #[test]
+    fn test_prophoto_primaries() {
+        let chromaticity = |rgb: ProPhotoRGBColor| {
+            let xyz = rgb.to_xyz(Illuminant::D50);
+            let sum = xyz.x + xyz.y + xyz.z;
+            (xyz.x / sum, xyz.y / sum)
+        };
+        for (rgb, (x, y)) in [
+            ((1., 0., 0.), (0.7347, 0.2653)),
+            ((0., 1., 0.), (0.1596, 0.8404)),
+            ((0., 0., 1.), (0.0366, 0.0001)),
+        ]
+        .iter()
+        {
+            let (x2, y2) = chromaticity(ProPhotoRGBColor {
+                r: rgb.0,
+                g: rgb.1,
+                b: rgb.2,
+            });
+            assert!((x - x2).abs() <= 1e-4);
+            assert!((y - y2).abs() <= 1e-4);
+        }
+        // white is D50
+        let white = ProPhotoRGBColor {
+            r: 1.,
+            g: 1.,
+            b: 1.,
+        }
+        .to_xyz(Illuminant::D50);
+        let wp = Illuminant::D50.white_point();
+        assert!((white.x - wp[0]).abs() <= 1e-6);
+        assert!((white.y - wp[1]).abs() <= 1e-6);
+        assert!((white.z - wp[2]).abs() <= 1e-6);
+    }
+}
@@ -0,0 +1,190 @@
+//! This module implements the RGB space of ITU-R BT.2020, or Rec. 2020, the standard for
+//! ultra-high-definition television. Its primaries are monochromatic lights on the edge of the
+//! visible gamut, so it covers about three quarters of all visible colors, far more than sRGB or
+//! Display P3: it is the usual container for wide-gamut and HDR video. The white point is D65.
+//!
+//! The primaries, as CIE 1931 xy chromaticities, are red (0.708, 0.292), green (0.170, 0.797), and
+//! blue (0.131, 0.046). The transfer function is the one given in BT.2020, which is like sRGB's but
+//! with different constants: a linear segment of slope 4.5 near black and a power of 0.45 above
+//! it. HDR content usually uses PQ or HLG encoding instead, which is outside the scope of this type.
+
+use bound::Bound;
+use color::{Color, XYZColor};
+use consts::REC2020_TRANSFORM as REC2020;
+use consts::REC2020_TRANSFORM_LU as REC2020_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+// the constants of the BT.2020 transfer function, to the precision needed for 12-bit video
+const ALPHA: f64 = 1.099_296_826_809_44;
+const BETA: f64 = 0.018_053_968_510_807;
+
+/// A color in the Rec. 2020 color space, with gamma-encoded components that range from 0 to 1 for
+/// colors in gamut. Like [`RGBColor`], conversion into this space does not clamp, so colors outside
+/// of the gamut have components outside of that range.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::Rec2020Color;
+/// // every sRGB color fits comfortably inside Rec. 2020
+/// let srgb_green = RGBColor::from_hex_code("#00ff00").unwrap();
+/// let green: Rec2020Color = srgb_green.convert();
+/// assert!(green.r > 0. && green.g < 1. && green.b > 0.);
+/// ```
+///
+/// [`RGBColor`]: ../../color/struct.RGBColor.html
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Rec2020Color {
+    /// The red primary component, from 0 to 1 for colors in gamut.
+    pub r: f64,
+    /// The green primary component, from 0 to 1 for colors in gamut.
+    pub g: f64,
+    /// The blue primary component, from 0 to 1 for colors in gamut.
+    pub b: f64,
+}
+
+impl Color for Rec2020Color {
+    /// Converts from XYZ to Rec. 2020. As Rec. 2020 uses D65 as its white point, any other
+    /// illuminant is chromatically adapted first.
+    fn from_xyz(xyz: XYZColor) -> Rec2020Color {
+        let xyz_d65 = xyz.color_adapt(Illuminant::D65);
+        // the matrix goes from linear RGB to XYZ: solve it to go the other way
+        let lin_rgb = REC2020_LU
+            .solve(&vector![xyz_d65.x, xyz_d65.y, xyz_d65.z])
+            .expect("Matrix is invertible.");
+        let gamma = |x: f64| {
+            if x < BETA {
+                4.5 * x
+            } else {
+                ALPHA * x.powf(0.45) - (ALPHA - 1.0)
+            }
+        };
+        Rec2020Color {
+            r: gamma(lin_rgb[0]),
+            g: gamma(lin_rgb[1]),
+            b: gamma(lin_rgb[2]),
+        }
+    }
+    /// Converts from Rec. 2020 to XYZ, and then adapts from D65 to the given illuminant.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let linearize = |x: f64| {
+            if x < 4.5 * BETA {
+                x / 4.5
+            } else {
+                ((x + (ALPHA - 1.0)) / ALPHA).powf(1.0 / 0.45)
+            }
+        };
+        let xyz = *REC2020 * vector![linearize(self.r), linearize(self.g), linearize(self.b)];
+        XYZColor {
+            x: xyz[0],
+            y: xyz[1],
+            z: xyz[2],
+            illuminant: Illuminant::D65,
+        }
+        .color_adapt(illuminant)
+    }
+}
+
+impl From<Coord> for Rec2020Color {
+    fn from(c: Coord) -> Rec2020Color {
+        Rec2020Color {
+            r: c.x,
+            g: c.y,
+            b: c.z,
+        }
+    }
+}
+
+impl From<Rec2020Color> for Coord {
+    fn from(val: Rec2020Color) -> Self {
+        Coord {
+            x: val.r,
+            y: val.g,
+            z: val.b,
+        }
+    }
+}
+
+impl Bound for Rec2020Color {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (0., 1.), (0., 1.)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use color::RGBColor;
+    use colors::DisplayP3Color;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_rec2020_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.4,
+            y: 0.5,
+            z: 0.6,
+            illuminant: Illuminant::D50,
+        };
+        let rgb = Rec2020Color::from_xyz(xyz);
+        let xyz2 = rgb.to_xyz(Illuminant::D50);
+        assert!((xyz.x - xyz2.x).abs() <= 1e-14);
+        assert!((xyz.y - xyz2.y).abs() <= 1e-14);
+        assert!((xyz.z - xyz2.z).abs() <= 1e-14);
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+        // including dark colors in the linear segment
+        let dark = Rec2020Color {
+            r: 0.05,
+            g: 0.01,
+            b: 0.3,
+        };
+        let dark2 = Rec2020Color::from_xyz(dark.to_xyz(Illuminant::D65));
+        assert!((dark.r - dark2.r).abs() <= 1e-12);
+        assert!((dark.g - dark2.g).abs() <= 1e-12);
+        assert!((dark.b - dark2.b).abs() <= 1e-12);
+    }
+    #[test]
+    fn test_rec2020_primaries() {
+        let chromaticity = |xyz: XYZColor| {
+            let sum = xyz.x + xyz.y + xyz.z;
+            (xyz.x / sum, xyz.y / sum)
+        };
+        for (rgb, (x, y)) in [
+            ((1., 0., 0.), (0.708, 0.292)),
+            ((0., 1., 0.), (0.170, 0.797)),
+            ((0., 0., 1.), (0.131, 0.046)),
+        ]
+        .iter()
+        {
+            let (x2, y2) = chromaticity(
+                Rec2020Color {
+                    r: rgb.0,
+                    g: rgb.1,
+                    b: rgb.2,
+                }
+                .to_xyz(Illuminant::D65),
+            );
+            assert!((x - x2).abs() <= 1e-6);
+            assert!((y - y2).abs() <= 1e-6);
+        }
+    }
+    #[test]
+    fn test_srgb_red_in_rec2020() {
+        // sRGB red is well inside Rec. 2020, even further from the edge than it is in Display P3
+        let srgb_red = RGBColor::from_hex_code("#ff0000").unwrap();
+        let red: Rec2020Color = srgb_red.convert();
+        let p3_red: DisplayP3Color = srgb_red.convert();
+        assert!(red.r < 1. && red.g > 0. && red.b > 0.);
+        assert!(red.r < p3_red.r);
+        // but the chromaticity stays the same, (0.64, 0.33)
+        let xyz = red.to_xyz(Illuminant::D65);
+        let sum = xyz.x + xyz.y + xyz.z;
+        assert!((xyz.x / sum - 0.64).abs() <= 1e-3);
+        assert!((xyz.y / sum - 0.33).abs() <= 1e-3);
+        let srgb_red2: RGBColor = red.convert();
+        assert_eq!(srgb_red2.to_string(), "#FF0000");
+    }
+}
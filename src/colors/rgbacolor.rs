@@ -0,0 +1,201 @@
+//! A module that implements [`RGBAColor`], an sRGB color with an alpha channel for compositing.
+//! Alpha describes coverage rather than color, so it doesn't fit into the 3D coordinate spaces
+//! that the rest of Scarlet works with: an `RGBAColor` is not a [`Color`] or a [`ColorPoint`].
+//! Instead, it wraps an [`RGBColor`] and provides the operations needed to layer colors on top of
+//! each other.
+//!
+//! [`RGBAColor`]: struct.RGBAColor.html
+//! [`Color`]: ../../color/trait.Color.html
+//! [`ColorPoint`]: ../../colorpoint/trait.ColorPoint.html
+//! [`RGBColor`]: ../../color/struct.RGBColor.html
+
+use color::{RGBColor, RGBParseError};
+use colors::alphacolor::AlphaColor;
+
+/// An sRGB color with an alpha value, using straight (not premultiplied) alpha: `rgb` is the color
+/// of whatever is covered, and `a` is how much is covered, from 0 (fully transparent) to 1 (fully
+/// opaque).
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::RGBAColor;
+/// let glass = RGBAColor::from_hex_code("#0000ff40").unwrap();
+/// let paper = RGBAColor::from_hex_code("#ffffff").unwrap();
+/// let seen = glass.over(paper);
+/// assert_eq!(seen.a, 1.);
+/// assert_eq!(seen.rgb.to_string(), "#BFBFFF");
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct RGBAColor {
+    /// The color, as sRGB. For a fully transparent color, this has no visible effect.
+    pub rgb: RGBColor,
+    /// The alpha value, from 0 (fully transparent) to 1 (fully opaque).
+    pub a: f64,
+}
+
+impl RGBAColor {
+    /// Given a string that represents a hex code, returns the color it represents. Accepts any
+    /// format that [`RGBColor::from_hex_code`] does, which are treated as fully opaque, as well as
+    /// `"#rrggbbaa"` (or `"rrggbbaa"`), with the last two digits giving the alpha value as in CSS.
+    ///
+    /// [`RGBColor::from_hex_code`]: ../../color/struct.RGBColor.html#method.from_hex_code
+    pub fn from_hex_code(hex: &str) -> Result<RGBAColor, RGBParseError> {
+        let digits = hex.strip_prefix('#').unwrap_or(hex);
+        if digits.len() == 8 {
+            if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(RGBParseError::InvalidHexSyntax);
+            }
+            let (rgb, a) = digits.split_at(6);
+            Ok(RGBAColor {
+                rgb: RGBColor::from_hex_code(rgb)?,
+                a: f64::from(u8::from_str_radix(a, 16)?) / 255.0,
+            })
+        } else {
+            Ok(RGBAColor {
+                rgb: RGBColor::from_hex_code(hex)?,
+                a: 1.0,
+            })
+        }
+    }
+    /// Composites this color on top of `background`, using the standard "source-over" operation
+    /// used by CSS, SVG, and most image editors. As in those, the blending is done on the sRGB
+    /// values directly, and the result uses straight alpha. If both colors are fully transparent,
+    /// so is the result, with a color of black.
+    pub fn over(self, background: RGBAColor) -> RGBAColor {
+        let a = self.a + background.a * (1.0 - self.a);
+        if a == 0.0 {
+            return RGBAColor {
+                rgb: RGBColor {
+                    r: 0.0,
+                    g: 0.0,
+                    b: 0.0,
+                },
+                a,
+            };
+        }
+        let (fg, bg) = (self.premultiplied(), background.premultiplied());
+        let mix = |f: f64, b: f64| (f + b * (1.0 - self.a)) / a;
+        RGBAColor {
+            rgb: RGBColor {
+                r: mix(fg.rgb.r, bg.rgb.r),
+                g: mix(fg.rgb.g, bg.rgb.g),
+                b: mix(fg.rgb.b, bg.rgb.b),
+            },
+            a,
+        }
+    }
+    /// Returns this color with each of its components multiplied by alpha, the premultiplied form
+    /// many graphics APIs expect. The result is still an `RGBAColor`, but its `rgb` should not be
+    /// interpreted as a color on its own: use [`RGBAColor::unpremultiplied`] to undo this.
+    ///
+    /// [`RGBAColor::unpremultiplied`]: #method.unpremultiplied
+    pub fn premultiplied(self) -> RGBAColor {
+        RGBAColor {
+            rgb: RGBColor {
+                r: self.rgb.r * self.a,
+                g: self.rgb.g * self.a,
+                b: self.rgb.b * self.a,
+            },
+            a: self.a,
+        }
+    }
+    /// Treats this color as premultiplied and divides each component by alpha, converting it back
+    /// to straight alpha. A fully transparent color has no color information left, so it becomes
+    /// transparent black.
+    pub fn unpremultiplied(self) -> RGBAColor {
+        let div = |x: f64| if self.a == 0.0 { 0.0 } else { x / self.a };
+        RGBAColor {
+            rgb: RGBColor {
+                r: div(self.rgb.r),
+                g: div(self.rgb.g),
+                b: div(self.rgb.b),
+            },
+            a: self.a,
+        }
+    }
+}
+
+impl From<AlphaColor<RGBColor>> for RGBAColor {
+    fn from(color: AlphaColor<RGBColor>) -> RGBAColor {
+        RGBAColor {
+            rgb: color.color,
+            a: color.alpha,
+        }
+    }
+}
+
+impl From<RGBAColor> for AlphaColor<RGBColor> {
+    fn from(color: RGBAColor) -> AlphaColor<RGBColor> {
+        AlphaColor::new(color.rgb, color.a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_rgba_from_hex_code() {
+        let opaque = RGBAColor::from_hex_code("#1a2b3c").unwrap();
+        assert_eq!(opaque.rgb.to_string(), "#1A2B3C");
+        assert_eq!(opaque.a, 1.0);
+        let short = RGBAColor::from_hex_code("f0f").unwrap();
+        assert_eq!(short.rgb.to_string(), "#FF00FF");
+        let translucent = RGBAColor::from_hex_code("1a2b3c80").unwrap();
+        assert_eq!(translucent.rgb.to_string(), "#1A2B3C");
+        assert!((translucent.a - 128. / 255.).abs() <= 1e-10);
+        for bad in ["#1a2b3c8", "#1a2b3cgg", "#1a2b3c8000"].iter() {
+            assert_eq!(
+                RGBAColor::from_hex_code(bad).unwrap_err(),
+                RGBParseError::InvalidHexSyntax
+            );
+        }
+    }
+    #[test]
+    fn test_rgba_over() {
+        let red = RGBAColor {
+            rgb: RGBColor::from_hex_code("#ff0000").unwrap(),
+            a: 0.5,
+        };
+        let white = RGBAColor::from_hex_code("#ffffff").unwrap();
+        let pink = red.over(white);
+        assert_eq!(pink.a, 1.0);
+        assert!((pink.rgb.r - 1.).abs() <= 1e-10);
+        assert!((pink.rgb.g - 0.5).abs() <= 1e-10);
+        assert!((pink.rgb.b - 0.5).abs() <= 1e-10);
+        assert_eq!(pink.rgb.to_string(), "#FF8080");
+        // two half-transparent layers make a three-quarters opaque one, weighted to the top
+        let blue = RGBAColor {
+            rgb: RGBColor::from_hex_code("#0000ff").unwrap(),
+            a: 0.5,
+        };
+        let layered = red.over(blue);
+        assert!((layered.a - 0.75).abs() <= 1e-10);
+        assert!((layered.rgb.r - 2. / 3.).abs() <= 1e-10);
+        assert!((layered.rgb.b - 1. / 3.).abs() <= 1e-10);
+        // nothing over nothing is nothing
+        let clear = RGBAColor { a: 0., ..red };
+        assert_eq!(clear.over(clear).a, 0.);
+    }
+    #[test]
+    fn test_rgba_premultiplied() {
+        let color = RGBAColor {
+            rgb: RGBColor {
+                r: 0.8,
+                g: 0.4,
+                b: 0.2,
+            },
+            a: 0.5,
+        };
+        let pre = color.premultiplied();
+        assert!((pre.rgb.r - 0.4).abs() <= 1e-10);
+        assert!((pre.rgb.g - 0.2).abs() <= 1e-10);
+        assert!((pre.rgb.b - 0.1).abs() <= 1e-10);
+        let back = pre.unpremultiplied();
+        assert_eq!(back.rgb.to_string(), color.rgb.to_string());
+        assert_eq!(RGBAColor { a: 0., ..color }.unpremultiplied().rgb.r, 0.);
+    }
+}
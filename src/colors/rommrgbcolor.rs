@@ -7,6 +7,13 @@
 //! as a basis for a change-of-basis matrix, scaling by the values of D50 reference white so (1, 1,
 //! 1) maps to it. It also have to undo the nonlinearity and flare correction, which could still
 //! contain small errors.
+//!
+//! Note that this does not match the ROMM RGB of ISO 22028-2, which other software uses: the matrix
+//! of primaries is applied in the opposite direction, and a flare term is added to the encoding. It
+//! is kept as it is so existing results don't change, but [`ProPhotoRGBColor`] follows the
+//! specification and should be used to exchange colors with other programs.
+//!
+//! [`ProPhotoRGBColor`]: ../prophotocolor/struct.ProPhotoRGBColor.html
 
 use bound::Bound;
 use color::{Color, XYZColor};
@@ -0,0 +1,163 @@
+//! This module implements the YIQ color space, the encoding used for color in NTSC analog
+//! television. Luma (Y) carries the brightness, which black-and-white sets displayed on their own,
+//! and the two chroma components are rotated so that I (in-phase) runs from blue-green to orange,
+//! the direction the eye is most sensitive to, and Q (quadrature) from green to purple, which could
+//! be sent with less bandwidth. Mixing up or blurring I and Q is an easy way to imitate NTSC
+//! artifacts.
+//!
+//! YIQ is computed directly from gamma-encoded RGB values with a fixed matrix. The original
+//! standard assumed the 1953 NTSC primaries, but modern practice, followed here, is to apply the
+//! same matrix to sRGB: the conversions go through [`RGBColor`], so YIQ has sRGB's D65 white point
+//! and gamut. The matrix is
+//!
+//! ```text
+//! | Y |   | 0.2990  0.5870  0.1140 | | R' |
+//! | I | = | 0.5959 -0.2746 -0.3213 | | G' |
+//! | Q |   | 0.2115 -0.5227  0.3112 | | B' |
+//! ```
+//!
+//! [`RGBColor`]: ../../color/struct.RGBColor.html
+
+use bound::Bound;
+use color::{Color, RGBColor, XYZColor};
+use consts::YIQ_TRANSFORM as YIQ;
+use consts::YIQ_TRANSFORM_LU as YIQ_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in the YIQ color space: a luma component, the same as in other analog and digital
+/// video encodings, and two chroma components that are 0 for any shade of gray.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::YIQColor;
+/// let gray: YIQColor = RGBColor::from_hex_code("#808080").unwrap().convert();
+/// assert!(gray.i.abs() <= 1e-10);
+/// assert!(gray.q.abs() <= 1e-10);
+/// // black-and-white TV only showed Y
+/// let orange: YIQColor = RGBColor::from_hex_code("#ff8000").unwrap().convert();
+/// let bw_orange = YIQColor{y: orange.y, i: 0., q: 0.};
+/// println!("{}", bw_orange.convert::<RGBColor>().to_string());
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct YIQColor {
+    /// The luma, a weighted sum of the gamma-encoded RGB components that ranges from 0 to 1. This is
+    /// not the same as relative luminance, which is computed from linear RGB.
+    pub y: f64,
+    /// The in-phase chroma component, running from blue-green (negative) to orange (positive).
+    /// Ranges from about -0.5959 to 0.5959.
+    pub i: f64,
+    /// The quadrature chroma component, running from green (negative) to purple (positive).
+    /// Ranges from about -0.5227 to 0.5227.
+    pub q: f64,
+}
+
+impl Color for YIQColor {
+    /// Converts from XYZ to YIQ by way of sRGB.
+    fn from_xyz(xyz: XYZColor) -> YIQColor {
+        let rgb = RGBColor::from_xyz(xyz);
+        let v = *YIQ * vector![rgb.r, rgb.g, rgb.b];
+        YIQColor {
+            y: v[0],
+            i: v[1],
+            q: v[2],
+        }
+    }
+    /// Converts from YIQ back to XYZ by way of sRGB, using LU decomposition to invert the matrix
+    /// precisely.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let rgb = YIQ_LU
+            .solve(&vector![self.y, self.i, self.q])
+            .expect("Matrix is invertible.");
+        RGBColor {
+            r: rgb[0],
+            g: rgb[1],
+            b: rgb[2],
+        }
+        .to_xyz(illuminant)
+    }
+}
+
+impl From<Coord> for YIQColor {
+    fn from(c: Coord) -> YIQColor {
+        YIQColor {
+            y: c.x,
+            i: c.y,
+            q: c.z,
+        }
+    }
+}
+
+impl From<YIQColor> for Coord {
+    fn from(val: YIQColor) -> Self {
+        Coord {
+            x: val.y,
+            y: val.i,
+            z: val.q,
+        }
+    }
+}
+
+impl Bound for YIQColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (-0.5959, 0.5959), (-0.5227, 0.5227)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_yiq_gray_has_no_chroma() {
+        for i in 0..=255u8 {
+            let gray: YIQColor = RGBColor::from((i, i, i)).convert();
+            assert!((gray.y - f64::from(i) / 255.).abs() <= 1e-10);
+            assert!(gray.i.abs() <= 1e-10);
+            assert!(gray.q.abs() <= 1e-10);
+        }
+    }
+    #[test]
+    fn test_yiq_rgb_round_trip() {
+        for code in [
+            "#000000", "#ffffff", "#ff0000", "#00ff00", "#0000ff", "#3a7fd0",
+        ]
+        .iter()
+        {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            let yiq: YIQColor = rgb.convert();
+            let rgb2: RGBColor = yiq.convert();
+            assert_eq!(rgb.to_string(), rgb2.to_string());
+            assert!((rgb.r - rgb2.r).abs() <= 1e-10);
+            assert!((rgb.g - rgb2.g).abs() <= 1e-10);
+            assert!((rgb.b - rgb2.b).abs() <= 1e-10);
+        }
+        // the extremes of sRGB stay in bounds
+        let bounds = YIQColor::bounds();
+        for code in [
+            "#ff0000", "#00ff00", "#0000ff", "#ffff00", "#00ffff", "#ff00ff",
+        ]
+        .iter()
+        {
+            let yiq: YIQColor = RGBColor::from_hex_code(code).unwrap().convert();
+            assert!(yiq.i.abs() <= bounds[1].1 + 1e-10);
+            assert!(yiq.q.abs() <= bounds[2].1 + 1e-10);
+        }
+    }
+    #[test]
+    fn test_yiq_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.2,
+            y: 0.42,
+            z: 0.23,
+            illuminant: Illuminant::D50,
+        };
+        let yiq: YIQColor = xyz.convert();
+        let xyz2: XYZColor = yiq.convert();
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+}
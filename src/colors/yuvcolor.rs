@@ -0,0 +1,164 @@
+//! This module implements the YUV color space, the encoding used for color in PAL analog
+//! television. Luma (Y) carries the brightness, which black-and-white sets displayed on their own,
+//! and the two chroma components are scaled color differences: U is proportional to B' - Y and V
+//! to R' - Y, with the scale chosen to keep the composite signal within range. The same split, with
+//! different scaling, is the basis of YCbCr in digital video and JPEG.
+//!
+//! YUV is computed directly from gamma-encoded RGB values with a fixed matrix. The original
+//! standard assumed the EBU primaries, but modern practice, followed here, is to apply the same
+//! matrix to sRGB: the conversions go through [`RGBColor`], so YUV has sRGB's D65 white point
+//! and gamut. The matrix is
+//!
+//! ```text
+//! | Y |   | 0.299      0.587      0.114     | | R' |
+//! | U | = | -0.147141  -0.288869  0.436010  | | G' |
+//! | V |   | 0.614975   -0.514965  -0.100010 | | B' |
+//! ```
+//!
+//! where U = 0.492111 (B' - Y) and V = 0.877283 (R' - Y), as in ITU-R BT.470.
+//!
+//! [`RGBColor`]: ../../color/struct.RGBColor.html
+
+use bound::Bound;
+use color::{Color, RGBColor, XYZColor};
+use consts::YUV_TRANSFORM as YUV;
+use consts::YUV_TRANSFORM_LU as YUV_LU;
+use coord::Coord;
+use illuminants::Illuminant;
+
+/// A color in the YUV color space: a luma component, the same as in other analog and digital
+/// video encodings, and two chroma components that are 0 for any shade of gray.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::colors::YUVColor;
+/// let gray: YUVColor = RGBColor::from_hex_code("#808080").unwrap().convert();
+/// assert!(gray.u.abs() <= 1e-10);
+/// assert!(gray.v.abs() <= 1e-10);
+/// // black-and-white TV only showed Y
+/// let orange: YUVColor = RGBColor::from_hex_code("#ff8000").unwrap().convert();
+/// let bw_orange = YUVColor{y: orange.y, u: 0., v: 0.};
+/// println!("{}", bw_orange.convert::<RGBColor>().to_string());
+/// ```
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct YUVColor {
+    /// The luma, a weighted sum of the gamma-encoded RGB components that ranges from 0 to 1. This is
+    /// not the same as relative luminance, which is computed from linear RGB.
+    pub y: f64,
+    /// The blue-difference chroma component, proportional to B' - Y. Ranges from about -0.436
+    /// to 0.436.
+    pub u: f64,
+    /// The red-difference chroma component, proportional to R' - Y. Ranges from about -0.615 to
+    /// 0.615.
+    pub v: f64,
+}
+
+impl Color for YUVColor {
+    /// Converts from XYZ to YUV by way of sRGB.
+    fn from_xyz(xyz: XYZColor) -> YUVColor {
+        let rgb = RGBColor::from_xyz(xyz);
+        let v = *YUV * vector![rgb.r, rgb.g, rgb.b];
+        YUVColor {
+            y: v[0],
+            u: v[1],
+            v: v[2],
+        }
+    }
+    /// Converts from YUV back to XYZ by way of sRGB, using LU decomposition to invert the matrix
+    /// precisely.
+    fn to_xyz(&self, illuminant: Illuminant) -> XYZColor {
+        let rgb = YUV_LU
+            .solve(&vector![self.y, self.u, self.v])
+            .expect("Matrix is invertible.");
+        RGBColor {
+            r: rgb[0],
+            g: rgb[1],
+            b: rgb[2],
+        }
+        .to_xyz(illuminant)
+    }
+}
+
+impl From<Coord> for YUVColor {
+    fn from(c: Coord) -> YUVColor {
+        YUVColor {
+            y: c.x,
+            u: c.y,
+            v: c.z,
+        }
+    }
+}
+
+impl From<YUVColor> for Coord {
+    fn from(val: YUVColor) -> Self {
+        Coord {
+            x: val.y,
+            y: val.u,
+            z: val.v,
+        }
+    }
+}
+
+impl Bound for YUVColor {
+    fn bounds() -> [(f64, f64); 3] {
+        [(0., 1.), (-0.437, 0.437), (-0.615, 0.615)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use consts::TEST_PRECISION;
+
+    #[test]
+    fn test_yuv_gray_has_no_chroma() {
+        for i in 0..=255u8 {
+            let gray: YUVColor = RGBColor::from((i, i, i)).convert();
+            assert!((gray.y - f64::from(i) / 255.).abs() <= 1e-10);
+            assert!(gray.u.abs() <= 1e-10);
+            assert!(gray.v.abs() <= 1e-10);
+        }
+    }
+    #[test]
+    fn test_yuv_rgb_round_trip() {
+        for code in [
+            "#000000", "#ffffff", "#ff0000", "#00ff00", "#0000ff", "#3a7fd0",
+        ]
+        .iter()
+        {
+            let rgb = RGBColor::from_hex_code(code).unwrap();
+            let yuv: YUVColor = rgb.convert();
+            let rgb2: RGBColor = yuv.convert();
+            assert_eq!(rgb.to_string(), rgb2.to_string());
+            assert!((rgb.r - rgb2.r).abs() <= 1e-10);
+            assert!((rgb.g - rgb2.g).abs() <= 1e-10);
+            assert!((rgb.b - rgb2.b).abs() <= 1e-10);
+        }
+        // the extremes of sRGB stay in bounds
+        let bounds = YUVColor::bounds();
+        for code in [
+            "#ff0000", "#00ff00", "#0000ff", "#ffff00", "#00ffff", "#ff00ff",
+        ]
+        .iter()
+        {
+            let yuv: YUVColor = RGBColor::from_hex_code(code).unwrap().convert();
+            assert!(yuv.u.abs() <= bounds[1].1 + 1e-10);
+            assert!(yuv.v.abs() <= bounds[2].1 + 1e-10);
+        }
+    }
+    #[test]
+    fn test_yuv_xyz_conversion() {
+        let xyz = XYZColor {
+            x: 0.2,
+            y: 0.42,
+            z: 0.23,
+            illuminant: Illuminant::D50,
+        };
+        let yuv: YUVColor = xyz.convert();
+        let xyz2: XYZColor = yuv.convert();
+        assert!(xyz.distance(&xyz2) <= TEST_PRECISION);
+    }
+}
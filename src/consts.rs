@@ -51,6 +51,65 @@ lazy_static! {
     };
     pub(crate) static ref BRADFORD_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
     nalgebra::linalg::LU::new(*BRADFORD_TRANSFORM);
+    pub(crate) static ref CAT02_TRANSFORM: Matrix3<f64> = {
+        matrix![00.7328, 0.4296, -0.1624;
+                -0.7036, 1.6975, 00.0061;
+                00.0030, 0.0136, 00.9834]
+    };
+    pub(crate) static ref CAT02_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*CAT02_TRANSFORM);
+    pub(crate) static ref VON_KRIES_TRANSFORM: Matrix3<f64> = {
+        matrix![00.40024, 0.70760, -0.08081;
+                -0.22630, 1.16532, 00.04570;
+                00.00000, 0.00000, 00.91822]
+    };
+    pub(crate) static ref VON_KRIES_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*VON_KRIES_TRANSFORM);
+    pub(crate) static ref XYZ_SCALING_TRANSFORM: Matrix3<f64> = Matrix3::identity();
+    pub(crate) static ref XYZ_SCALING_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*XYZ_SCALING_TRANSFORM);
+    pub(crate) static ref DISPLAY_P3_TRANSFORM: Matrix3<f64> = {
+        matrix![0.4866306905, 0.2656632253, 0.1981760842;
+                0.2290026779, 0.6917268884, 0.0792704337;
+                0.0000000000, 0.0451126232, 1.0437273768]
+    };
+    pub(crate) static ref DISPLAY_P3_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*DISPLAY_P3_TRANSFORM);
+    pub(crate) static ref IPT_LMS_TRANSFORM: Matrix3<f64> = {
+        matrix![00.4002, 0.7075, -0.0807;
+                -0.2280, 1.1500, 00.0612;
+                00.0000, 0.0000, 00.9184]
+    };
+    pub(crate) static ref IPT_LMS_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*IPT_LMS_TRANSFORM);
+    pub(crate) static ref IPT_TRANSFORM: Matrix3<f64> = {
+        matrix![0.4000, 00.4000, 00.2000;
+                4.4550, -4.8510, 00.3960;
+                0.8056, 00.3572, -1.1628]
+    };
+    pub(crate) static ref IPT_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*IPT_TRANSFORM);
+    pub(crate) static ref JZAZBZ_LMS_TRANSFORM: Matrix3<f64> = {
+        matrix![00.41478972, 0.579999, 0.0146480;
+                -0.2015100, 1.120649, 0.0531008;
+                -0.0166008, 0.264800, 0.6684799]
+    };
+    pub(crate) static ref JZAZBZ_LMS_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*JZAZBZ_LMS_TRANSFORM);
+    pub(crate) static ref JZAZBZ_IAB_TRANSFORM: Matrix3<f64> = {
+        matrix![0.500000, 00.500000, 00.000000;
+                3.524000, -4.066708, 00.542708;
+                0.199076, 01.096799, -1.295875]
+    };
+    pub(crate) static ref JZAZBZ_IAB_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*JZAZBZ_IAB_TRANSFORM);
+    pub(crate) static ref REC2020_TRANSFORM: Matrix3<f64> = {
+        matrix![0.6370085776, 0.1446150502, 0.1688463722;
+                0.2627210518, 0.6779893824, 0.0592895658;
+                0.0000000000, 0.0280723333, 1.0607676667]
+    };
+    pub(crate) static ref REC2020_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*REC2020_TRANSFORM);
     pub(crate) static ref ROMM_RGB_TRANSFORM: Matrix3<f64> = {
         matrix![0.7976749, 0.1351917, 0.0313534;
                 0.2880402, 0.7118741, 0.0000857;
@@ -58,6 +117,20 @@ lazy_static! {
     };
     pub(crate) static ref ROMM_RGB_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
     nalgebra::linalg::LU::new(*ROMM_RGB_TRANSFORM);
+    pub(crate) static ref YIQ_TRANSFORM: Matrix3<f64> = {
+        matrix![0.2990, 00.5870, 00.1140;
+                0.5959, -0.2746, -0.3213;
+                0.2115, -0.5227, 00.3112]
+    };
+    pub(crate) static ref YIQ_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*YIQ_TRANSFORM);
+    pub(crate) static ref YUV_TRANSFORM: Matrix3<f64> = {
+        matrix![00.299000000, 00.587000000, 00.114000000;
+                -0.147141189, -0.288869157, 00.436010346;
+                00.614975383, -0.514965121, -0.100010262]
+    };
+    pub(crate) static ref YUV_TRANSFORM_LU: nalgebra::linalg::LU<f64, Const<3>, Const<3>> =
+    nalgebra::linalg::LU::new(*YUV_TRANSFORM);
     pub(crate) static ref STANDARD_RGB_TRANSFORM: Matrix3<f64> = {
         matrix![03.2406, -1.5372, -0.4986;
                 -0.9689, 01.8758, 00.0415;
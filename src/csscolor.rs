@@ -52,115 +52,112 @@ fn parse_rgb_num(num: &str) -> Result<u8, CSSParseError> {
     }
 }
 
-/// Parses a string of the form "rgb(r, g, b)", where r, g, and b are numbers, returning a tuple of
-/// u8s for the three components. Gives a CSSParseError on invalid input.
-pub(crate) fn parse_rgb_str(num: &str) -> Result<(u8, u8, u8), CSSParseError> {
-    // must have at least 10 characters
-    // has to start with "rgb(" or not a valid color
-    if !num.starts_with("rgb(") || num.len() < 10 {
-        return Err(CSSParseError::InvalidColorSyntax);
+/// Checks whether a CSS numeric is in the range CSS allows for an RGB component: 0 to 255 for
+/// integers, 0 to 1 for floats, and 0% to 100% for percentages.
+fn rgb_num_in_range(num: CSSNumeric) -> bool {
+    match num {
+        CSSNumeric::Integer(val) => (0..=255).contains(&val),
+        CSSNumeric::Float(val) => (0.0..=1.0).contains(&val),
+        CSSNumeric::Percentage(val) => (0..=100).contains(&val),
     }
-    // remove first four chars, put in Vec
-    let mut chars: Vec<char> = num.chars().skip(4).collect();
-    // check for and remove parenthesis
-    if chars.iter().last().unwrap() != &')' {
-        return Err(CSSParseError::InvalidColorSyntax);
+}
+
+/// Splits the arguments of a CSS color function, without the surrounding parentheses, into their
+/// components. CSS allows either commas, as in "255, 0, 0", or whitespace, as in "255 0 0", and
+/// surrounding whitespace is removed.
+fn split_css_args(args: &str) -> Vec<&str> {
+    if args.contains(',') {
+        args.split(',').map(|arg| arg.trim()).collect()
+    } else {
+        args.split_whitespace().collect()
     }
-    chars.pop();
+}
+
+/// Parses a string of the form "rgb(r, g, b)" or "rgb(r g b)", where r, g, and b are numbers,
+/// returning a tuple of u8s for the three components. Components out of range are clamped. Gives a
+/// CSSParseError on invalid input.
+pub(crate) fn parse_rgb_str(num: &str) -> Result<(u8, u8, u8), CSSParseError> {
+    parse_rgb_args(num, false)
+}
+
+/// Parses a string like [`parse_rgb_str`], but gives `CSSParseError::OutOfRange` for components out
+/// of range instead of clamping them.
+///
+/// [`parse_rgb_str`]: fn.parse_rgb_str.html
+pub(crate) fn parse_rgb_str_strict(num: &str) -> Result<(u8, u8, u8), CSSParseError> {
+    parse_rgb_args(num, true)
+}
 
-    // test for disallowed characters
-    if chars.iter().any(|&c| !"0123456789+-,. %".contains(c)) {
-        println!("hi");
+/// Parses an "rgb()" string, either clamping components that are out of range or, if `strict`,
+/// rejecting them.
+fn parse_rgb_args(num: &str, strict: bool) -> Result<(u8, u8, u8), CSSParseError> {
+    // has to start with "rgb(" and end with ")" or not a valid color
+    if !num.starts_with("rgb(") || !num.ends_with(')') {
         return Err(CSSParseError::InvalidColorSyntax);
     }
-    // this now requires a very specific format: three commas, a parenthesis at the end, and spaces
-    // in between
-    // check for commas (the right number of them) and split into numbers, remove whitespace,
-    // parse, and recombine
-    let split_iter = chars.split(|c| c == &',');
-    // now remove surrounding whitespace and pass to number parsing, propagating errors
-    let mut nums: Vec<u8> = vec![];
-    for split in split_iter {
-        nums.push(parse_rgb_num(split.iter().collect::<String>().trim())?);
+    // split into numbers, either by commas or by whitespace: there must be exactly three
+    let args = split_css_args(&num[4..num.len() - 1]);
+    if args.len() != 3 {
+        return Err(CSSParseError::WrongComponentCount);
     }
-    if nums.len() != 3 {
-        return Err(CSSParseError::InvalidColorSyntax);
+    let mut nums = [0u8; 3];
+    for (i, arg) in args.iter().enumerate() {
+        if strict && !rgb_num_in_range(parse_css_number(arg)?) {
+            return Err(CSSParseError::OutOfRange);
+        }
+        nums[i] = parse_rgb_num(arg)?;
     }
     Ok((nums[0], nums[1], nums[2]))
 }
 
 /// Parses an HSL or HSV tuple, given after "hsl" or "hsv" in normal CSS, such as "(250, 50%, 50%)"
-/// into a tuple (f64, f64, f64) such that the first float lies within the range 0-360 and the other
-/// two lie within the range 0-1. Gives a CSSParseError if invalid.
+/// or "(250 50% 50%)", into a tuple (f64, f64, f64) such that the first float lies within the range
+/// 0-360 and the other two lie within the range 0-1. Hues outside of 0-360 are wrapped around, and
+/// percentages outside of 0-100 are clamped. Gives a CSSParseError if invalid.
 pub(crate) fn parse_hsl_hsv_tuple(tup: &str) -> Result<(f64, f64, f64), CSSParseError> {
-    // must have '(' at start and ')' at end: remove them, and store in chars vec
+    parse_hsl_hsv_args(tup, false)
+}
+
+/// Parses a tuple like [`parse_hsl_hsv_tuple`], but gives `CSSParseError::OutOfRange` for
+/// percentages outside of 0-100 instead of clamping them. Hues still wrap around, as in CSS.
+///
+/// [`parse_hsl_hsv_tuple`]: fn.parse_hsl_hsv_tuple.html
+pub(crate) fn parse_hsl_hsv_tuple_strict(tup: &str) -> Result<(f64, f64, f64), CSSParseError> {
+    parse_hsl_hsv_args(tup, true)
+}
+
+/// Parses an HSL or HSV tuple, either clamping percentages that are out of range or, if `strict`,
+/// rejecting them.
+fn parse_hsl_hsv_args(tup: &str, strict: bool) -> Result<(f64, f64, f64), CSSParseError> {
+    // must have '(' at start and ')' at end: remove them
     if !tup.starts_with('(') || !tup.ends_with(')') {
         return Err(CSSParseError::InvalidColorSyntax);
     }
-    let mut chars: Vec<char> = tup.chars().skip(1).collect();
-    chars.pop();
-
-    // split with commas: must be 3 distinct things
-    let split_iter = chars.split(|c| c == &',');
-    let mut numerics: Vec<CSSNumeric> = vec![];
-    for split in split_iter {
-        numerics.push(parse_css_number(split.iter().collect::<String>().trim())?);
+    // split with commas or whitespace: must be 3 distinct things
+    let args = split_css_args(&tup[1..tup.len() - 1]);
+    if args.len() != 3 {
+        return Err(CSSParseError::WrongComponentCount);
     }
-    if numerics.len() != 3 {
-        return Err(CSSParseError::InvalidColorSyntax);
+    let mut numerics: Vec<CSSNumeric> = vec![];
+    for split in args {
+        numerics.push(parse_css_number(split)?);
     }
     // hue is special: require float or integer, normalize to 0-360
     let hue: f64 = match numerics[0] {
-        CSSNumeric::Integer(val) => {
-            let mut clamped = val;
-            while clamped < 0 {
-                clamped += 360;
-            }
-            while clamped >= 360 {
-                clamped -= 360;
-            }
-            clamped as f64
-        }
-        CSSNumeric::Float(val) => {
-            let mut clamped = val;
-            while clamped < 0. {
-                clamped += 360.;
-            }
-            while clamped >= 360. {
-                clamped -= 360.;
-            }
-            clamped
-        }
-        _ => return Err(CSSParseError::InvalidColorSyntax),
-    };
-    // saturation and lightness/value all work the same way: clamp between 0 and 1 and expect a
-    // percentage
-    let sat: f64 = match numerics[1] {
-        CSSNumeric::Percentage(val) => {
-            if val < 0 {
-                0.
-            } else if val > 100 {
-                1.
-            } else {
-                (val as f64) / 100.
-            }
-        }
-        _ => return Err(CSSParseError::InvalidColorSyntax),
+        CSSNumeric::Integer(val) => (val as f64).rem_euclid(360.),
+        CSSNumeric::Float(val) => val.rem_euclid(360.),
+        _ => return Err(CSSParseError::InvalidComponent),
     };
-    let l_or_v: f64 = match numerics[2] {
-        CSSNumeric::Percentage(val) => {
-            if val < 0 {
-                0.
-            } else if val > 100 {
-                1.
-            } else {
-                (val as f64) / 100.
-            }
+    // saturation and lightness/value all work the same way: expect a percentage and clamp between
+    // 0 and 1
+    let percent = |num: CSSNumeric| match num {
+        CSSNumeric::Percentage(val) if strict && !(0..=100).contains(&val) => {
+            Err(CSSParseError::OutOfRange)
         }
-        _ => return Err(CSSParseError::InvalidColorSyntax),
+        CSSNumeric::Percentage(val) => Ok((val.clamp(0, 100) as f64) / 100.),
+        _ => Err(CSSParseError::InvalidComponent),
     };
-    // now return
-    Ok((hue, sat, l_or_v))
+    Ok((hue, percent(numerics[1])?, percent(numerics[2])?))
 }
 
 #[cfg(test)]
@@ -204,13 +201,29 @@ mod tests {
             parse_rgb_str("rgB(123, 33, 2)")
         );
         assert_eq!(
-            Err(CSSParseError::InvalidColorSyntax),
+            Err(CSSParseError::WrongComponentCount),
             parse_rgb_str("rgb(123, 123, 41, 22)")
         );
         assert_eq!(
             Err(CSSParseError::InvalidColorSyntax),
             parse_rgb_str("rgB(())")
         );
+        // whitespace works instead of commas
+        let rgb = parse_rgb_str("rgb(125 20% 0.5)").unwrap();
+        assert_eq!(rgb, (125, 51, 127));
+        assert_eq!(
+            Err(CSSParseError::WrongComponentCount),
+            parse_rgb_str("rgb(125 20% 0.5 1)")
+        );
+        // the strict version rejects what the other clamps
+        assert_eq!(
+            Err(CSSParseError::OutOfRange),
+            parse_rgb_str_strict("rgb(-125, -20%, 10.5)")
+        );
+        assert_eq!(
+            parse_rgb_str_strict("rgb(125, 20%, 0.5)"),
+            Ok((125, 51, 127))
+        );
     }
 
     #[test]
@@ -234,10 +247,22 @@ mod tests {
         assert_eq!(hsl.0.round() as u8, 123u8);
         assert_eq!((hsl.1 * 100.).round() as u8, 100u8);
         assert_eq!((hsl.2 * 100.).round() as u8, 0u8);
+        // test whitespace syntax
+        let hsl = parse_hsl_hsv_tuple("( 123 40%  40% )").unwrap();
+        assert_eq!(hsl.0.round() as u8, 123u8);
+        assert_eq!((hsl.2 * 100.).round() as u8, 40u8);
         // test error
         assert_eq!(
             parse_hsl_hsv_tuple("(14%, 140%, 12%)"),
-            Err(CSSParseError::InvalidColorSyntax)
+            Err(CSSParseError::InvalidComponent)
+        );
+        assert_eq!(
+            parse_hsl_hsv_tuple("(14, 40%)"),
+            Err(CSSParseError::WrongComponentCount)
+        );
+        assert_eq!(
+            parse_hsl_hsv_tuple_strict("(123, 140%, -40%)"),
+            Err(CSSParseError::OutOfRange)
         );
     }
 }
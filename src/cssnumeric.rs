@@ -31,6 +31,13 @@ pub enum CSSParseError {
     /// This indicates that a general color syntax error occurred, such as mismatching parentheses or
     /// uninterpretable tokens.
     InvalidColorSyntax,
+    /// This indicates that a color function was given the wrong number of components.
+    WrongComponentCount,
+    /// This indicates that a component was a valid number of the wrong kind, such as a percentage
+    /// where a hue angle was expected.
+    InvalidComponent,
+    /// This indicates that a component was outside of the range allowed for it.
+    OutOfRange,
 }
 
 impl fmt::Display for CSSParseError {
@@ -45,6 +52,9 @@ impl Error for CSSParseError {
             CSSParseError::InvalidNumericCharacters => "Unexpected non-numeric characters",
             CSSParseError::InvalidNumericSyntax => "Invalid numeric syntax",
             CSSParseError::InvalidColorSyntax => "Invalid color syntax",
+            CSSParseError::WrongComponentCount => "Wrong number of color components",
+            CSSParseError::InvalidComponent => "Color component of the wrong kind",
+            CSSParseError::OutOfRange => "Color component out of range",
         }
     }
 }
@@ -68,6 +78,10 @@ pub(crate) fn parse_css_number(num: &str) -> Result<CSSNumeric, CSSParseError> {
     if !chars.iter().all(|&c| "0123456789-+.%".contains(c)) {
         return Err(CSSParseError::InvalidNumericCharacters);
     }
+    // nothing to parse
+    if chars.is_empty() {
+        return Err(CSSParseError::InvalidNumericSyntax);
+    }
     // test if initial character is '-' or '+'. Remove and set sign flag accordingly.
     let is_positive = match chars[0] {
         '-' => false,
@@ -82,7 +96,11 @@ pub(crate) fn parse_css_number(num: &str) -> Result<CSSNumeric, CSSParseError> {
         return Err(CSSParseError::InvalidNumericSyntax);
     }
     // if any other pluses or minuses, throw error
-    if chars.iter().any(|&c| "-=".contains(c)) {
+    if chars.iter().any(|&c| "-+".contains(c)) {
+        return Err(CSSParseError::InvalidNumericSyntax);
+    }
+    // there must be at least one digit
+    if !chars.iter().any(|c| c.is_ascii_digit()) {
         return Err(CSSParseError::InvalidNumericSyntax);
     }
     // Test if number contains exactly one period. If more than one, throw error: otherwise, split to
@@ -244,5 +262,12 @@ mod tests {
             parse_css_number("1%2%"),
             Err(CSSParseError::InvalidNumericSyntax)
         );
+        // test signs in the wrong place and missing digits
+        for num in ["", "-", "5+", "%", "-.", ".%"].iter() {
+            assert_eq!(
+                parse_css_number(num),
+                Err(CSSParseError::InvalidNumericSyntax)
+            );
+        }
     }
 }
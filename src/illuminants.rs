@@ -9,7 +9,7 @@
 /// lighting conditions. The most common ones for computers are D50 and D65, differing kinds of
 /// daylight. Other ones may be added as time goes on, but they won't be removed and backwards
 /// compatibility won't break without warning.
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Illuminant {
     /// The CIE D50 standard illuminant. See [this
     /// page](https://en.wikipedia.org/wiki/Standard_illuminant#Illuminant_series_D) for more
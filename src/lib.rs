@@ -15,9 +15,13 @@
 
 extern crate csv;
 extern crate geo;
+#[cfg(feature = "image")]
+extern crate image;
 #[macro_use]
 extern crate nalgebra;
 extern crate num;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
@@ -37,6 +41,7 @@ mod cssnumeric;
 pub mod illuminants;
 pub mod material_colors;
 mod matplotlib_cmaps;
+pub mod palette;
 pub mod prelude;
 mod visual_gamut;
 // pub mod doc;
@@ -278,7 +278,7 @@ mod tests {
         let mean_l = cols.iter().map(|x| x.lightness()).sum::<f64>() / 8.;
         for mut col in cols {
             col.set_lightness(mean_l);
-            println!("{}", col.to_string());
+            println!("{}", col);
         }
     }
 }
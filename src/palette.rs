@@ -0,0 +1,222 @@
+//! This module reads and writes palettes in the `.gpl` format used by GIMP, Inkscape, Krita, and
+//! other design tools, so that colors chosen in Scarlet can be used in them and vice versa.
+//!
+//! A `.gpl` file starts with the line `GIMP Palette`, optionally followed by a `Name:` line and a
+//! `Columns:` line, and then has one color per line: three integers from 0 to 255 for red, green,
+//! and blue, separated by whitespace, and then optionally a name for the color. Lines starting with
+//! `#` are comments.
+
+use color::RGBColor;
+use std::error::Error;
+use std::fmt;
+
+/// A color in a `.gpl` palette, with its name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GplEntry {
+    /// The color.
+    pub color: RGBColor,
+    /// The name of the color, which may be empty.
+    pub name: String,
+}
+
+impl GplEntry {
+    /// Constructs a new entry with the given color and name.
+    pub fn new(color: RGBColor, name: &str) -> GplEntry {
+        GplEntry {
+            color,
+            name: name.to_string(),
+        }
+    }
+}
+
+impl From<RGBColor> for GplEntry {
+    /// Names the color with its hex code, as GIMP does for colors without a name.
+    fn from(color: RGBColor) -> GplEntry {
+        GplEntry {
+            color,
+            name: color.to_string(),
+        }
+    }
+}
+
+/// An error type that results from an invalid attempt to read a `.gpl` palette.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub enum GplParseError {
+    /// This indicates that the text didn't start with the `GIMP Palette` header.
+    MissingHeader,
+    /// This indicates that a color line, numbered from 1 at the top of the file, didn't start with
+    /// three integers from 0 to 255.
+    InvalidColor(usize),
+}
+
+impl fmt::Display for GplParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            GplParseError::MissingHeader => write!(f, "GPL parsing error: missing header"),
+            GplParseError::InvalidColor(line) => {
+                write!(f, "GPL parsing error: invalid color on line {}", line)
+            }
+        }
+    }
+}
+
+impl Error for GplParseError {
+    fn description(&self) -> &str {
+        match *self {
+            GplParseError::MissingHeader => "Missing \"GIMP Palette\" header",
+            GplParseError::InvalidColor(_) => "Invalid color line",
+        }
+    }
+}
+
+/// Reads the colors of a `.gpl` palette and their names, in order. A color without a name gets an
+/// empty one. The palette name, the number of columns, and comments are ignored. Both Unix and
+/// Windows line endings are accepted.
+/// # Errors
+/// Returns `GplParseError::MissingHeader` if the first line isn't `GIMP Palette`, and
+/// `GplParseError::InvalidColor` with the line number if a line that isn't blank, a comment, or a
+/// header field doesn't start with three integers from 0 to 255.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::palette::from_gpl;
+/// let text = "GIMP Palette\nName: Flags\n#\n255   0   0\tRed\n  0   0 255\tDeep Blue\n";
+/// let entries = from_gpl(text).unwrap();
+/// assert_eq!(entries[1].color.to_string(), "#0000FF");
+/// assert_eq!(entries[1].name, "Deep Blue");
+/// ```
+pub fn from_gpl(text: &str) -> Result<Vec<GplEntry>, GplParseError> {
+    let mut lines = text.lines().enumerate();
+    match lines.next() {
+        Some((_, header)) if header.trim() == "GIMP Palette" => {}
+        _ => return Err(GplParseError::MissingHeader),
+    }
+    let mut entries = vec![];
+    for (i, line) in lines {
+        let line = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with("Name:")
+            || line.starts_with("Columns:")
+        {
+            continue;
+        }
+        // three numbers, and then everything else is the name
+        let mut rest = line;
+        let mut rgb = [0u8; 3];
+        for component in rgb.iter_mut() {
+            let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+            *component = rest[..end]
+                .parse()
+                .map_err(|_| GplParseError::InvalidColor(i + 1))?;
+            rest = rest[end..].trim_start();
+        }
+        entries.push(GplEntry::new(
+            RGBColor::from((rgb[0], rgb[1], rgb[2])),
+            rest,
+        ));
+    }
+    Ok(entries)
+}
+
+/// Writes named colors as a `.gpl` palette with the given name. Components are rounded to integers
+/// like [`RGBColor::int_rgb_tup`], and the columns are aligned like GIMP's own output, so palettes
+/// read with [`from_gpl`] and written back out with the same palette name are unchanged if they
+/// were written by this or by GIMP. Use `GplEntry::from` to name colors with their hex codes, as
+/// GIMP does for colors without a name.
+///
+/// # Example
+///
+/// ```
+/// # use scarlet::prelude::*;
+/// # use scarlet::palette::{from_gpl, to_gpl, GplEntry};
+/// let red = RGBColor::from_hex_code("#ff0000").unwrap();
+/// let dark_red = RGBColor::from_hex_code("#800000").unwrap();
+/// let text = to_gpl(&[GplEntry::new(red, "Signal Red"), GplEntry::from(dark_red)], "Reds");
+/// assert_eq!(
+///     text,
+///     "GIMP Palette\nName: Reds\n#\n255   0   0\tSignal Red\n128   0   0\t#800000\n"
+/// );
+/// assert_eq!(from_gpl(&text).unwrap()[0].name, "Signal Red");
+/// ```
+///
+/// [`RGBColor::int_rgb_tup`]: ../color/struct.RGBColor.html#method.int_rgb_tup
+/// [`from_gpl`]: fn.from_gpl.html
+pub fn to_gpl(entries: &[GplEntry], name: &str) -> String {
+    let mut text = format!("GIMP Palette\nName: {}\n#\n", name);
+    for entry in entries {
+        let (r, g, b) = entry.color.int_rgb_tup();
+        text.push_str(&format!("{:3} {:3} {:3}\t{}\n", r, g, b, entry.name));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+
+    #[test]
+    fn test_gpl_round_trip() {
+        let text = concat!(
+            "GIMP Palette\n",
+            "Name: Sample\n",
+            "#\n",
+            "255   0   0\t#FF0000\n",
+            "  0 128  64\t#008040\n",
+            " 18  52  86\t#123456\n",
+        );
+        let entries = from_gpl(text).unwrap();
+        let codes: Vec<String> = entries.iter().map(|e| e.color.to_string()).collect();
+        assert_eq!(codes, vec!["#FF0000", "#008040", "#123456"]);
+        assert_eq!(to_gpl(&entries, "Sample"), text);
+        // real names survive too, spaces and all
+        let text = concat!(
+            "GIMP Palette\n",
+            "Name: Named\n",
+            "#\n",
+            " 10  20  30\tDark Blue Gray\n",
+            "255 255 255\tWhite\n",
+            "  0   0   0\tUntitled\n",
+        );
+        let entries = from_gpl(text).unwrap();
+        assert_eq!(entries[0].name, "Dark Blue Gray");
+        assert_eq!(entries[1].name, "White");
+        assert_eq!(to_gpl(&entries, "Named"), text);
+        // colors named by their hex codes by default
+        let color = RGBColor::from_hex_code("#123456").unwrap();
+        assert_eq!(GplEntry::from(color).name, "#123456");
+    }
+    #[test]
+    fn test_gpl_parsing() {
+        // GIMP's own files have a column count, comments, names with spaces, and sometimes no names
+        let text = "GIMP Palette\r\nName: Test\r\nColumns: 4\r\n# a comment\r\n\r\n\
+                    10 20 30 Dark Blue Gray\r\n40 50 60\r\n";
+        let entries = from_gpl(text).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].color.int_rgb_tup(), (10, 20, 30));
+        assert_eq!(entries[0].name, "Dark Blue Gray");
+        assert_eq!(entries[1].color.int_rgb_tup(), (40, 50, 60));
+        assert_eq!(entries[1].name, "");
+        assert!(from_gpl("GIMP Palette\n").unwrap().is_empty());
+
+        assert_eq!(
+            from_gpl("Name: Test\n1 2 3\n").unwrap_err(),
+            GplParseError::MissingHeader
+        );
+        assert_eq!(from_gpl("").unwrap_err(), GplParseError::MissingHeader);
+        assert_eq!(
+            from_gpl("GIMP Palette\n1 2 3\n1 2 300\n").unwrap_err(),
+            GplParseError::InvalidColor(3)
+        );
+        assert_eq!(
+            from_gpl("GIMP Palette\n1 2\n").unwrap_err(),
+            GplParseError::InvalidColor(2)
+        );
+        assert_eq!(
+            from_gpl("GIMP Palette\nred green blue\n").unwrap_err(),
+            GplParseError::InvalidColor(2)
+        );
+    }
+}